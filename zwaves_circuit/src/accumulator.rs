@@ -0,0 +1,313 @@
+//! Worker of the `utxoAccumulator` calls: it takes the output hashes of the transfers in the order of the `UM:<n>`
+//! entries of the contract, keeps the frontier of the UTXO tree and proves the accumulation of every pair.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use bellman::groth16::Parameters;
+use bellman::SynthesisError;
+use pairing::{PrimeField, PrimeFieldRepr};
+use rand::Rng;
+use sapling_crypto::jubjub::JubjubEngine;
+
+use zwaves_primitives::merkle_tree::IncrementalMerkleTree;
+use zwaves_primitives::serialization::read_fr_repr_be;
+
+use crate::circuit::MERKLE_PROOF_LEN;
+use crate::prover::{prove_accumulator, AccumulatorWitness};
+
+
+/// Length of the `UM:<n>` value, two 32 bytes big-endian output hashes.
+pub const UTXO_PAIR_LEN: usize = 64;
+
+
+fn error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Output hashes of the `UM:<n>` value.
+pub fn read_utxo_pair<E: JubjubEngine>(data: &[u8]) -> io::Result<[E::Fr; 2]> {
+    if data.len() != UTXO_PAIR_LEN {
+        return Err(error("wrong utxo pair length"));
+    }
+
+    let read = |chunk: &[u8]| E::Fr::from_repr(read_fr_repr_be::<E::Fr>(chunk)?).map_err(|_| error("not in field"));
+    Ok([read(&data[..32])?, read(&data[32..])?])
+}
+
+
+/// Ordered output hashes of the transfers, the pair `n` is the `UM:<n>` entry.
+pub trait UtxoSource<E: JubjubEngine> {
+    /// The next pair, `None` if there is none yet.
+    fn next_pair(&mut self) -> io::Result<Option<[E::Fr; 2]>>;
+}
+
+/// In-memory queue of pairs.
+#[derive(Clone)]
+pub struct MemorySource<E: JubjubEngine> {
+    pub pairs: VecDeque<[E::Fr; 2]>
+}
+
+impl<E: JubjubEngine> Default for MemorySource<E> {
+    fn default() -> Self {
+        MemorySource { pairs: VecDeque::new() }
+    }
+}
+
+impl<E: JubjubEngine> MemorySource<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, pair: [E::Fr; 2]) {
+        self.pairs.push_back(pair);
+    }
+}
+
+impl<E: JubjubEngine> UtxoSource<E> for MemorySource<E> {
+    fn next_pair(&mut self) -> io::Result<Option<[E::Fr; 2]>> {
+        Ok(self.pairs.pop_front())
+    }
+}
+
+/// Pairs as lines of the `UM:<n>` values in base58 or in the `base64:` form of the node API, empty lines are skipped.
+pub struct LineSource<R: BufRead> {
+    reader: R
+}
+
+impl<R: BufRead> LineSource<R> {
+    pub fn new(reader: R) -> Self {
+        LineSource { reader }
+    }
+}
+
+impl<E: JubjubEngine, R: BufRead> UtxoSource<E> for LineSource<R> {
+    fn next_pair(&mut self) -> io::Result<Option<[E::Fr; 2]>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if !line.trim().is_empty() {
+                break;
+            }
+        }
+
+        let line = line.trim();
+        let data = if let Some(data) = line.strip_prefix("base64:") {
+            base64::decode(data).map_err(|_| error("wrong base64 utxo pair"))?
+        } else {
+            bs58::decode(line).into_vec().map_err(|_| error("wrong base58 utxo pair"))?
+        };
+
+        read_utxo_pair::<E>(&data).map(Some)
+    }
+}
+
+
+/// Arguments of the `utxoAccumulator` call for the message `root_num`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatorCall {
+    pub root_num: u64,
+    pub proof: Vec<u8>,
+    pub new_root: Vec<u8>
+}
+
+#[derive(Clone)]
+pub struct AccumulatorWorker<E: JubjubEngine> {
+    tree: IncrementalMerkleTree<E>
+}
+
+impl<E: JubjubEngine> AccumulatorWorker<E> {
+    /// Worker of a new contract, the tree is empty.
+    pub fn new(params: &E::Params) -> Self {
+        AccumulatorWorker { tree: IncrementalMerkleTree::new_frontier(MERKLE_PROOF_LEN, params).unwrap() }
+    }
+
+    /// Worker after `root_num` accumulations, `sibling` is the proof of the next leaf.
+    pub fn from_frontier(root_num: u64, sibling: &[E::Fr], params: &E::Params) -> io::Result<Self> {
+        let size = root_num.checked_mul(2).ok_or_else(|| error("root_num overflow"))?;
+        Ok(AccumulatorWorker { tree: IncrementalMerkleTree::from_frontier(MERKLE_PROOF_LEN, size, sibling, params)? })
+    }
+
+    /// Number of the accumulated pairs, the `ROOT_NUM` of the contract.
+    pub fn root_num(&self) -> u64 {
+        self.tree.size() / 2
+    }
+
+    /// The `RV:<root_num>` of the contract.
+    pub fn root(&self) -> E::Fr {
+        self.tree.root()
+    }
+
+    /// Proof of the next leaf, with `root_num` it resumes the worker. Fails if the tree is full.
+    pub fn frontier(&self) -> io::Result<Vec<E::Fr>> {
+        if self.tree.size() >> self.tree.depth() != 0 {
+            return Err(error("tree is full"));
+        }
+        self.tree.proof(self.tree.size())
+    }
+
+    /// Witness of the accumulation of the `pair` as the next message, `index = 2 * root_num`.
+    pub fn witness(&self, pair: [E::Fr; 2]) -> io::Result<AccumulatorWitness<E>> {
+        Ok(AccumulatorWitness {
+            note_hashes: pair,
+            index: self.tree.size(),
+            sibling: self.frontier()?[1..].to_vec()
+        })
+    }
+
+    /// Adds the pair without a proof, for the messages accumulated already.
+    pub fn append(&mut self, pair: [E::Fr; 2], params: &E::Params) -> io::Result<()> {
+        self.tree.append_many(&pair, params).map(|_| ())
+    }
+
+    /// Proves the accumulation of the `pair` and adds it to the tree, the tree is not changed on errors.
+    pub fn accumulate<R: Rng>(
+        &mut self,
+        pair: [E::Fr; 2],
+        params: &Parameters<E>,
+        jubjub_params: &E::Params,
+        rng: &mut R
+    ) -> Result<AccumulatorCall, SynthesisError> {
+        let root_num = self.root_num();
+        let (proof, inputs) = prove_accumulator(params, &self.witness(pair)?, jubjub_params, rng)?;
+
+        let mut tree = self.tree.clone();
+        tree.append_many(&pair, jubjub_params)?;
+        if inputs.new_root != tree.root() {
+            return Err(error("new root is not the same as of the tree").into());
+        }
+        self.tree = tree;
+
+        let mut proof_bytes = vec![];
+        proof.write(&mut proof_bytes)?;
+        let mut new_root = vec![];
+        inputs.new_root.into_repr().write_be(&mut new_root)?;
+
+        Ok(AccumulatorCall { root_num, proof: proof_bytes, new_root })
+    }
+
+    /// Accumulates the next pair of the `source`, `None` if it has none.
+    pub fn next_call<S: UtxoSource<E>, R: Rng>(
+        &mut self,
+        source: &mut S,
+        params: &Parameters<E>,
+        jubjub_params: &E::Params,
+        rng: &mut R
+    ) -> Result<Option<AccumulatorCall>, SynthesisError> {
+        match source.next_pair()? {
+            Some(pair) => self.accumulate(pair, params, jubjub_params, rng).map(Some),
+            None => Ok(None)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod accumulator_tests {
+    use super::*;
+    use bellman::groth16::{generate_random_parameters, prepare_verifying_key, verify_proof, Proof};
+    use pairing::bls12_381::{Bls12, Fr};
+    use rand::os::OsRng;
+    use sapling_crypto::jubjub::JubjubBls12;
+    use zwaves_primitives::public_inputs::AccumulatorPublicInputs;
+
+    use crate::circuit::UtxoAccumulator;
+
+    fn pair_to_bytes(pair: &[Fr; 2]) -> Vec<u8> {
+        let mut res = vec![];
+        pair[0].into_repr().write_be(&mut res).unwrap();
+        pair[1].into_repr().write_be(&mut res).unwrap();
+        res
+    }
+
+    #[test]
+    fn test_line_source() {
+        let rng = &mut OsRng::new().unwrap();
+        let pairs = (0..3).map(|_| [rng.gen(), rng.gen()]).collect::<Vec<[Fr; 2]>>();
+
+        let text = format!("{}\n\nbase64:{}\n{}",
+            bs58::encode(pair_to_bytes(&pairs[0])).into_string(),
+            base64::encode(&pair_to_bytes(&pairs[1])),
+            bs58::encode(pair_to_bytes(&pairs[2])).into_string());
+        let mut source = LineSource::new(text.as_bytes());
+
+        for pair in pairs.iter() {
+            assert!(UtxoSource::<Bls12>::next_pair(&mut source).unwrap() == Some(*pair));
+        }
+        assert!(UtxoSource::<Bls12>::next_pair(&mut source).unwrap().is_none());
+
+        let mut source = LineSource::new("base64:AAAA\n".as_bytes());
+        assert!(UtxoSource::<Bls12>::next_pair(&mut source).is_err(), "pair should be 64 bytes");
+    }
+
+    #[test]
+    fn test_accumulator_worker() {
+        let jubjub_params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+
+        let params = generate_random_parameters(UtxoAccumulator::<Bls12>::blank(&jubjub_params), rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+
+        let mut source = MemorySource::<Bls12>::new();
+        for _ in 0..3 {
+            source.push([rng.gen(), rng.gen()]);
+        }
+        let pairs = source.pairs.clone();
+
+        let mut worker = AccumulatorWorker::<Bls12>::new(&jubjub_params);
+        let mut old_root = worker.root();
+
+        for (n, pair) in pairs.iter().enumerate() {
+            let call = worker.next_call(&mut source, &params, &jubjub_params, rng).unwrap().unwrap();
+            assert_eq!(call.root_num, n as u64);
+
+            // the inputs of the contract: UM:n, 2*n, RV:n and the new root
+            let mut inputs = pair_to_bytes(pair);
+            inputs.extend_from_slice(&[0u8; 24]);
+            inputs.extend_from_slice(&(2 * n as u64).to_be_bytes());
+            old_root.into_repr().write_be(&mut inputs).unwrap();
+            inputs.extend_from_slice(&call.new_root);
+
+            let inputs = AccumulatorPublicInputs::<Bls12>::from_bytes(&inputs).unwrap();
+            let proof = Proof::<Bls12>::read(&call.proof[..]).unwrap();
+            assert!(verify_proof(&pvk, &proof, &inputs.to_vec()).unwrap(), "proof should be valid for the contract inputs");
+
+            old_root = worker.root();
+        }
+        assert!(worker.next_call(&mut source, &params, &jubjub_params, rng).unwrap().is_none());
+
+        // a worker resumed from the frontier continues the same tree
+        let mut resumed = AccumulatorWorker::<Bls12>::from_frontier(worker.root_num(), &worker.frontier().unwrap(), &jubjub_params).unwrap();
+        let mut replayed = AccumulatorWorker::<Bls12>::new(&jubjub_params);
+        for pair in pairs.iter() {
+            replayed.append(*pair, &jubjub_params).unwrap();
+        }
+        assert!(resumed.root() == worker.root() && replayed.root() == worker.root());
+
+        let pair = [rng.gen(), rng.gen()];
+        worker.append(pair, &jubjub_params).unwrap();
+        resumed.append(pair, &jubjub_params).unwrap();
+        assert!(resumed.root() == worker.root());
+    }
+
+    #[test]
+    fn test_full_tree() {
+        let jubjub_params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+
+        // two pairs fill the tree of depth 2
+        let mut worker = AccumulatorWorker::<Bls12> { tree: IncrementalMerkleTree::new_frontier(2, &jubjub_params).unwrap() };
+        for _ in 0..2 {
+            assert!(worker.witness([rng.gen(), rng.gen()]).is_ok());
+            worker.append([rng.gen(), rng.gen()], &jubjub_params).unwrap();
+        }
+
+        assert_eq!(worker.frontier().unwrap_err().to_string(), "tree is full");
+        assert!(worker.witness([rng.gen(), rng.gen()]).is_err());
+        assert!(worker.append([rng.gen(), rng.gen()], &jubjub_params).is_err());
+        assert_eq!(worker.root_num(), 2);
+    }
+}
@@ -104,6 +104,23 @@ pub struct Transfer<'a, E: JubjubEngine> {
 }
 
 
+impl <'a, E: JubjubEngine> Transfer<'a, E> {
+    /// Circuit without the assignment, for the parameters generation and verification.
+    pub fn blank(params: &'a E::Params) -> Self {
+        Transfer {
+            receiver: None,
+            in_note: [None, None],
+            in_proof: [None, None],
+            out_note: [None, None],
+            root_hash: None,
+            sk: None,
+            packed_asset: None,
+            params
+        }
+    }
+}
+
+
 impl <'a, E: JubjubEngine> Circuit<E> for Transfer<'a, E> {
     fn synthesize<CS: ConstraintSystem<E>>(
         self,
@@ -153,6 +170,39 @@ impl <'a, E: JubjubEngine> Circuit<E> for Transfer<'a, E> {
 }
 
 
+/// Transfer with the receiver and the `memo_hash` of the fee and the memo (`public_inputs::memo_hash`) bound by
+/// the proof as public inputs, so they cannot be replaced in a transaction. The `memo_hash` is the last public input.
+#[derive(Clone)]
+pub struct BoundTransfer<'a, E: JubjubEngine> {
+    pub transfer: Transfer<'a, E>,
+    pub memo_hash: Option<E::Fr>
+}
+
+
+impl <'a, E: JubjubEngine> BoundTransfer<'a, E> {
+    /// Circuit without the assignment, for the parameters generation and verification.
+    pub fn blank(params: &'a E::Params) -> Self {
+        BoundTransfer { transfer: Transfer::blank(params), memo_hash: None }
+    }
+}
+
+
+impl <'a, E: JubjubEngine> Circuit<E> for BoundTransfer<'a, E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        self.transfer.synthesize(cs)?;
+
+        let memo_hash = AllocatedNum::alloc(cs.namespace(|| "alloc memo_hash"), || self.memo_hash.ok_or(SynthesisError::AssignmentMissing))?;
+        memo_hash.inputize(cs.namespace(|| "inputize memo_hash"))?;
+
+        Ok(())
+    }
+}
+
+
 #[derive(Clone)]
 pub struct UtxoAccumulator<'a, E: JubjubEngine> {
     pub note_hashes: [Option<E::Fr>; 2],
@@ -163,6 +213,20 @@ pub struct UtxoAccumulator<'a, E: JubjubEngine> {
 }
 
 
+impl <'a, E: JubjubEngine> UtxoAccumulator<'a, E> {
+    /// Circuit without the assignment, for the parameters generation and verification.
+    pub fn blank(params: &'a E::Params) -> Self {
+        UtxoAccumulator {
+            note_hashes: [None, None],
+            index: None,
+            old_proof: None,
+            new_proof: None,
+            params
+        }
+    }
+}
+
+
 
 impl <'a, E: JubjubEngine> Circuit<E> for UtxoAccumulator<'a, E> {
     fn synthesize<CS: ConstraintSystem<E>>(
@@ -1,4 +1,6 @@
 #[cfg(test)]
 pub mod test;
 
-pub mod circuit;
\ No newline at end of file
+pub mod circuit;
+pub mod prover;
+pub mod accumulator;
\ No newline at end of file
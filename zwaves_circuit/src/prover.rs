@@ -0,0 +1,331 @@
+//! Native proving of the circuits, shared by the Node, JNI and wasm bindings.
+
+use std::io;
+
+use bellman::groth16::{Parameters, Proof};
+#[cfg(not(target_arch = "wasm32"))]
+use bellman::groth16::create_random_proof;
+// bellman proves on a thread pool, wasm has no threads
+#[cfg(target_arch = "wasm32")]
+use zwaves_primitives::groth16::create_random_proof;
+use bellman::SynthesisError;
+use rand::Rng;
+use sapling_crypto::jubjub::JubjubEngine;
+
+use pairing::{Field, PrimeField};
+
+use zwaves_primitives::pedersen_hasher;
+use zwaves_primitives::transactions::{self, NoteData};
+
+pub use zwaves_primitives::public_inputs::{AccumulatorPublicInputs, BoundTransferPublicInputs, TransferPublicInputs};
+use zwaves_primitives::public_inputs::memo_hash;
+
+use crate::circuit::{BoundTransfer, Transfer, UtxoAccumulator, MERKLE_PROOF_LEN};
+
+
+/// Private data of a transfer. `in_proof_sibling[i]` are the `MERKLE_PROOF_LEN` siblings
+/// of the `in_note[i]` at the leaf `in_proof_index[i]`, from the bottom up.
+#[derive(Clone)]
+pub struct TransferWitness<E: JubjubEngine> {
+    pub receiver: E::Fr,
+    pub in_note: [NoteData<E>; 2],
+    pub in_proof_sibling: [Vec<E::Fr>; 2],
+    pub in_proof_index: [u64; 2],
+    pub out_note: [NoteData<E>; 2],
+    pub root_hash: E::Fr,
+    pub sk: E::Fr,
+    pub packed_asset: E::Fr
+}
+
+impl<E: JubjubEngine> TransferWitness<E> {
+    /// The circuit assignment of the witness, fails on proofs of a wrong length or index.
+    pub fn circuit<'a>(&self, params: &'a E::Params) -> io::Result<Transfer<'a, E>> {
+        let in_proof = |i: usize| {
+            let sibling = &self.in_proof_sibling[i];
+            let index = self.in_proof_index[i];
+
+            if sibling.len() != MERKLE_PROOF_LEN || index >> MERKLE_PROOF_LEN != 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong merkle proof"));
+            }
+
+            Ok(Some(sibling.iter().enumerate().map(|(j, &e)| (e, (index >> j) & 1 == 1)).collect()))
+        };
+
+        Ok(Transfer {
+            receiver: Some(self.receiver),
+            in_note: [Some(self.in_note[0].clone()), Some(self.in_note[1].clone())],
+            in_proof: [in_proof(0)?, in_proof(1)?],
+            out_note: [Some(self.out_note[0].clone()), Some(self.out_note[1].clone())],
+            root_hash: Some(self.root_hash),
+            sk: Some(self.sk),
+            packed_asset: Some(self.packed_asset),
+            params
+        })
+    }
+
+    /// Computes the output note hashes and the nullifiers of the inputs natively, fails if the packed
+    /// asset does not fit in 192 bits.
+    pub fn public_inputs(&self, params: &E::Params) -> io::Result<TransferPublicInputs<E>> {
+        let out_hash = [
+            transactions::note_hash(&self.out_note[0], params),
+            transactions::note_hash(&self.out_note[1], params)
+        ];
+
+        let nf = [0, 1].map(|i| {
+            let in_hash = transactions::note_hash(&self.in_note[i], params);
+            transactions::nullifier::<E>(&in_hash, &self.sk, params)
+        });
+
+        TransferPublicInputs::from_vec(&[self.receiver, self.root_hash, self.packed_asset, out_hash[0], out_hash[1], nf[0], nf[1]])
+    }
+
+    /// The assignment of the BoundTransfer circuit binding the `fee` and the `memo` of the transaction.
+    pub fn bound_circuit<'a>(&self, fee: u64, memo: &[u8], params: &'a E::Params) -> io::Result<BoundTransfer<'a, E>> {
+        Ok(BoundTransfer {
+            transfer: self.circuit(params)?,
+            memo_hash: Some(memo_hash::<E>(fee, memo))
+        })
+    }
+
+    pub fn bound_public_inputs(&self, fee: u64, memo: &[u8], params: &E::Params) -> io::Result<BoundTransferPublicInputs<E>> {
+        Ok(BoundTransferPublicInputs {
+            transfer: self.public_inputs(params)?,
+            memo_hash: memo_hash::<E>(fee, memo)
+        })
+    }
+}
+
+/// Proves a transfer with the Groth16 parameters of the Transfer circuit. The parameters should be
+/// read once and reused, `jubjub_params` are the curve parameters the circuit was built with.
+pub fn prove_transfer<E: JubjubEngine, R: Rng>(
+    params: &Parameters<E>,
+    witness: &TransferWitness<E>,
+    jubjub_params: &E::Params,
+    rng: &mut R
+) -> Result<(Proof<E>, TransferPublicInputs<E>), SynthesisError> {
+    let inputs = witness.public_inputs(jubjub_params)?;
+    let circuit = witness.circuit(jubjub_params)?;
+    let proof = create_random_proof(circuit, params, rng)?;
+
+    Ok((proof, inputs))
+}
+
+/// Proves a transfer with the Groth16 parameters of the BoundTransfer circuit, the proof is valid only
+/// for the receiver of the witness, the `fee` and the `memo`.
+pub fn prove_bound_transfer<E: JubjubEngine, R: Rng>(
+    params: &Parameters<E>,
+    witness: &TransferWitness<E>,
+    fee: u64,
+    memo: &[u8],
+    jubjub_params: &E::Params,
+    rng: &mut R
+) -> Result<(Proof<E>, BoundTransferPublicInputs<E>), SynthesisError> {
+    let inputs = witness.bound_public_inputs(fee, memo, jubjub_params)?;
+    let circuit = witness.bound_circuit(fee, memo, jubjub_params)?;
+    let proof = create_random_proof(circuit, params, rng)?;
+
+    Ok((proof, inputs))
+}
+
+
+/// Private data of an accumulation of the `note_hashes` at the leaves `index` and `index + 1`. `sibling` are
+/// the `MERKLE_PROOF_LEN - 1` siblings of their parent from the bottom up, the same in the old and the new tree.
+#[derive(Clone)]
+pub struct AccumulatorWitness<E: JubjubEngine> {
+    pub note_hashes: [E::Fr; 2],
+    pub index: u64,
+    pub sibling: Vec<E::Fr>
+}
+
+impl<E: JubjubEngine> AccumulatorWitness<E> {
+    fn check(&self) -> io::Result<()> {
+        if self.sibling.len() != MERKLE_PROOF_LEN - 1 || self.index & 1 != 0 || self.index >> MERKLE_PROOF_LEN != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong merkle proof"));
+        }
+        Ok(())
+    }
+
+    /// The circuit assignment of the witness, fails on proofs of a wrong length and odd indexes.
+    pub fn circuit<'a>(&self, params: &'a E::Params) -> io::Result<UtxoAccumulator<'a, E>> {
+        self.check()?;
+
+        let mut index = <E::Fr as PrimeField>::Repr::default();
+        index.as_mut()[0] = self.index;
+
+        Ok(UtxoAccumulator {
+            note_hashes: [Some(self.note_hashes[0]), Some(self.note_hashes[1])],
+            index: Some(E::Fr::from_repr(index).unwrap()),
+            old_proof: Some(self.sibling.clone()),
+            new_proof: Some(self.sibling.clone()),
+            params
+        })
+    }
+
+    /// Computes the roots of the tree before and after the accumulation natively.
+    pub fn public_inputs(&self, params: &E::Params) -> io::Result<AccumulatorPublicInputs<E>> {
+        self.check()?;
+
+        let root = |leaf: &E::Fr, right: &E::Fr| {
+            let sibling = std::iter::once(*right).chain(self.sibling.iter().cloned()).collect::<Vec<_>>();
+            pedersen_hasher::merkle_root::<E>(&sibling, self.index, leaf, params)
+        };
+
+        Ok(AccumulatorPublicInputs {
+            note_hashes: self.note_hashes,
+            index: self.index,
+            old_root: root(&E::Fr::zero(), &E::Fr::zero()),
+            new_root: root(&self.note_hashes[0], &self.note_hashes[1])
+        })
+    }
+}
+
+/// Proves an accumulation with the Groth16 parameters of the UtxoAccumulator circuit.
+pub fn prove_accumulator<E: JubjubEngine, R: Rng>(
+    params: &Parameters<E>,
+    witness: &AccumulatorWitness<E>,
+    jubjub_params: &E::Params,
+    rng: &mut R
+) -> Result<(Proof<E>, AccumulatorPublicInputs<E>), SynthesisError> {
+    let inputs = witness.public_inputs(jubjub_params)?;
+    let circuit = witness.circuit(jubjub_params)?;
+    let proof = create_random_proof(circuit, params, rng)?;
+
+    Ok((proof, inputs))
+}
+
+
+#[cfg(test)]
+mod prover_tests {
+    use super::*;
+    use bellman::Circuit;
+    use bellman::groth16::{generate_random_parameters, prepare_verifying_key, verify_proof};
+    use pairing::bls12_381::{Bls12, Fr, FrRepr};
+    use pairing::{Field, PrimeField};
+    use rand::os::OsRng;
+    use sapling_crypto::circuit::test::TestConstraintSystem;
+    use sapling_crypto::jubjub::JubjubBls12;
+    use zwaves_primitives::merkle_tree::IncrementalMerkleTree;
+
+    fn fr(n: u64) -> Fr {
+        Fr::from_repr(FrRepr([n, 0, 0, 0])).unwrap()
+    }
+
+    fn note(amount: u64, native_amount: u64, txid: Fr, owner: Fr) -> NoteData<Bls12> {
+        NoteData { asset_id: Fr::zero(), amount: fr(amount), native_amount: fr(native_amount), txid, owner }
+    }
+
+    /// A balanced transfer of the two leaves of a tree.
+    fn transfer_witness(params: &JubjubBls12) -> TransferWitness<Bls12> {
+        let rng = &mut OsRng::new().unwrap();
+
+        let sk: Fr = rng.gen();
+        let pk = transactions::pubkey::<Bls12>(&sk, params);
+
+        let in_note = [note(5, 1, rng.gen(), pk), note(3, 2, rng.gen(), pk)];
+        let out_note = [note(6, 2, rng.gen(), rng.gen()), note(2, 1, rng.gen(), rng.gen())];
+
+        let in_hash = [0, 1].map(|i| transactions::note_hash(&in_note[i], params));
+        let defaults = pedersen_hasher::merkle_defaults::<Bls12>(MERKLE_PROOF_LEN, params);
+        let in_proof_sibling = [1, 0].map(|i| {
+            let mut sibling = defaults.clone();
+            sibling[0] = in_hash[i];
+            sibling
+        });
+        let root_hash = pedersen_hasher::merkle_root::<Bls12>(&in_proof_sibling[0], 0, &in_hash[0], params);
+
+        TransferWitness {
+            receiver: rng.gen(),
+            in_note,
+            in_proof_sibling,
+            in_proof_index: [0, 1],
+            out_note,
+            root_hash,
+            sk,
+            packed_asset: Fr::zero()
+        }
+    }
+
+    #[test]
+    fn test_transfer_public_inputs() {
+        let params = JubjubBls12::new();
+        let witness = transfer_witness(&params);
+
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        witness.circuit(&params).unwrap().synthesize(&mut cs).unwrap();
+
+        if !cs.is_satisfied() {
+            panic!("Constraints not satisfied: {}", cs.which_is_unsatisfied().unwrap_or(""));
+        }
+
+        let inputs = witness.public_inputs(&params).unwrap().to_vec();
+        assert!(cs.verify(&inputs), "public inputs should be the same as in the circuit");
+    }
+
+    #[test]
+    fn test_wrong_merkle_proof() {
+        let params = JubjubBls12::new();
+        let mut witness = transfer_witness(&params);
+
+        witness.in_proof_index[1] = 1 << MERKLE_PROOF_LEN;
+        assert!(witness.circuit(&params).is_err());
+
+        witness.in_proof_index[1] = 1;
+        witness.in_proof_sibling[0].pop();
+        assert!(witness.circuit(&params).is_err());
+    }
+
+    #[test]
+    fn test_bound_transfer_proof() {
+        let jubjub_params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let witness = transfer_witness(&jubjub_params);
+
+        let params = generate_random_parameters(BoundTransfer::<Bls12>::blank(&jubjub_params), rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+
+        let (proof, inputs) = prove_bound_transfer(&params, &witness, 400000, b"memo", &jubjub_params, rng).unwrap();
+        assert!(verify_proof(&pvk, &proof, &inputs.to_vec()).unwrap(), "proof should be valid");
+
+        let mut wrong = inputs.clone();
+        wrong.transfer.receiver = rng.gen();
+        assert!(!verify_proof(&pvk, &proof, &wrong.to_vec()).unwrap(), "proof should not be valid for another receiver");
+
+        let wrong = witness.bound_public_inputs(400000, b"other memo", &jubjub_params).unwrap();
+        assert!(!verify_proof(&pvk, &proof, &wrong.to_vec()).unwrap(), "proof should not be valid for another memo");
+
+        let wrong = witness.bound_public_inputs(500000, b"memo", &jubjub_params).unwrap();
+        assert!(!verify_proof(&pvk, &proof, &wrong.to_vec()).unwrap(), "proof should not be valid for another fee");
+    }
+
+    #[test]
+    fn test_accumulator_public_inputs() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let leaves = (0..6).map(|_| rng.gen()).collect::<Vec<Fr>>();
+
+        let mut tree = IncrementalMerkleTree::<Bls12>::new(MERKLE_PROOF_LEN, &params).unwrap();
+        tree.append_many(&leaves[..4], &params).unwrap();
+        let old_root = tree.root();
+
+        let witness = AccumulatorWitness::<Bls12> {
+            note_hashes: [leaves[4], leaves[5]],
+            index: 4,
+            sibling: tree.proof(4).unwrap()[1..].to_vec()
+        };
+        tree.append_many(&leaves[4..], &params).unwrap();
+
+        let inputs = witness.public_inputs(&params).unwrap();
+        assert!(inputs.old_root == old_root && inputs.new_root == tree.root(), "roots should be the same as of the tree");
+
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        witness.circuit(&params).unwrap().synthesize(&mut cs).unwrap();
+
+        if !cs.is_satisfied() {
+            panic!("Constraints not satisfied: {}", cs.which_is_unsatisfied().unwrap_or(""));
+        }
+        assert!(cs.verify(&inputs.to_vec()), "public inputs should be the same as in the circuit");
+
+        let odd = AccumulatorWitness::<Bls12> { index: 5, ..witness.clone() };
+        assert!(odd.circuit(&params).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 const { broadcast, waitForTx, setScript, invokeScript, nodeInteraction } = require("@waves/waves-transactions");
 const { address, base58Encode, base58Decode, publicKey, privateKey } = require("@waves/waves-crypto");
-const {extract_vk, MerkleTree, transfer, pubkey, fs_random, fr_random, verify, bufferizeBigints, debufferizeBigints, note_hash} = require("../zwaves_node/lib/index.js");
+const {extract_vk, loadParams, MerkleTree, transfer, pubkey, fs_random, fr_random, verify, bufferizeBigints, debufferizeBigints, note_hash} = require("../zwaves_node/lib/index.js");
 
 const fs = require("fs");
 const env = process.env;
@@ -81,7 +81,7 @@ let data = {
 };
 
 console.log(data);
-let res = transfer(transfer_mpc, data);
+let res = transfer(loadParams(transfer_mpc), data);
 
 console.log(res);
 console.log(verify(extract_vk(transfer_mpc), res));
@@ -126,7 +126,7 @@ let data = {
 
 console.log(data);
 let vk = extract_vk(mpc_params);
-let res = utxoAccumulator(mpc_params, data);
+let res = utxoAccumulator(loadParams(mpc_params), data);
 console.log(res);
 console.log(verify(vk, res));
 */
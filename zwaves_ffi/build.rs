@@ -0,0 +1,19 @@
+use std::{env, path::Path};
+
+/// The committed `include/zwaves.h` is the header of the crate, builds do not touch the source tree.
+/// It is regenerated with `ZWAVES_FFI_GENERATE_HEADER=1 cargo build -p zwaves_ffi` after API changes.
+fn main() {
+    println!("cargo:rerun-if-env-changed=ZWAVES_FFI_GENERATE_HEADER");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    if env::var_os("ZWAVES_FFI_GENERATE_HEADER").is_none() {
+        return;
+    }
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    cbindgen::generate(&crate_dir)
+        .expect("Unable to generate the C header")
+        .write_to_file(Path::new(&crate_dir).join("include/zwaves.h"));
+}
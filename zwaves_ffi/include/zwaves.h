@@ -0,0 +1,99 @@
+#ifndef ZWAVES_H
+#define ZWAVES_H
+
+/* Generated by cbindgen from zwaves_ffi, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded.
+ */
+#define ZWAVES_OK 0
+
+/**
+ * A required pointer is null.
+ */
+#define ZWAVES_ERR_NULL_POINTER -1
+
+/**
+ * An input has a wrong length or encoding.
+ */
+#define ZWAVES_ERR_INVALID_INPUT -2
+
+/**
+ * The output buffer is shorter than the result.
+ */
+#define ZWAVES_ERR_BUFFER_TOO_SMALL -3
+
+/**
+ * Unexpected internal failure.
+ */
+#define ZWAVES_ERR_INTERNAL -4
+
+/**
+ * Length of a serialized field element.
+ */
+#define ZWAVES_FR_LEN 32
+
+/**
+ * Length of a serialized note: asset_id, amount, native_amount, txid and owner.
+ */
+#define ZWAVES_NOTE_LEN (5 * ZWAVES_FR_LEN)
+
+/**
+ * Verifies a BN256 Groth16 proof with the Ride `bn256Groth16Verify` layout, the result is written to `valid`.
+ */
+int32_t zwaves_bn256_groth16_verify(const uint8_t *vk,
+                                    size_t vk_len,
+                                    const uint8_t *proof,
+                                    size_t proof_len,
+                                    const uint8_t *inputs,
+                                    size_t inputs_len,
+                                    bool *valid);
+
+/**
+ * Verifies a BLS12-381 Groth16 proof with the Ride `groth16Verify` layout, the result is written to `valid`.
+ */
+int32_t zwaves_bls12_groth16_verify(const uint8_t *vk,
+                                    size_t vk_len,
+                                    const uint8_t *proof,
+                                    size_t proof_len,
+                                    const uint8_t *inputs,
+                                    size_t inputs_len,
+                                    bool *valid);
+
+/**
+ * Writes the hash of a `ZWAVES_NOTE_LEN` bytes note to `out`.
+ */
+int32_t zwaves_note_hash(const uint8_t *note, size_t note_len, uint8_t *out, size_t out_len);
+
+/**
+ * Writes the nullifier of a note hash for the secret key `sk` to `out`.
+ */
+int32_t zwaves_nullifier(const uint8_t *note_hash,
+                         size_t note_hash_len,
+                         const uint8_t *sk,
+                         size_t sk_len,
+                         uint8_t *out,
+                         size_t out_len);
+
+/**
+ * Writes the public key of the secret key `sk` to `out`.
+ */
+int32_t zwaves_pubkey(const uint8_t *sk, size_t sk_len, uint8_t *out, size_t out_len);
+
+/**
+ * Writes the Diffie-Hellman shared secret of the public key `pk` and the secret key `sk` to `out`.
+ */
+int32_t zwaves_edh(const uint8_t *pk,
+                   size_t pk_len,
+                   const uint8_t *sk,
+                   size_t sk_len,
+                   uint8_t *out,
+                   size_t out_len);
+
+#endif /* ZWAVES_H */
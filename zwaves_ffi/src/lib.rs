@@ -0,0 +1,342 @@
+//! C API of the zwaves verifiers and shielded primitives, the header is `include/zwaves.h`.
+//!
+//! All buffers are owned by the caller and passed with their lengths, field elements are 32 bytes
+//! big-endian. Every function returns `ZWAVES_OK` or a negative `ZWAVES_ERR_*` code, outputs are
+//! written only on `ZWAVES_OK`. Input pointers may be null only with a zero length, the pointers
+//! must be valid for the given lengths for the duration of the call.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+use lazy_static::lazy_static;
+use pairing::{
+    bls12_381::{Bls12, Fr},
+    PrimeField, PrimeFieldRepr,
+};
+use sapling_crypto::jubjub::JubjubBls12;
+use zwaves_primitives::{
+    serialization::read_fr_repr_be,
+    transactions::{self, NoteData},
+};
+
+/// The call succeeded.
+pub const ZWAVES_OK: i32 = 0;
+/// A required pointer is null.
+pub const ZWAVES_ERR_NULL_POINTER: i32 = -1;
+/// An input has a wrong length or encoding.
+pub const ZWAVES_ERR_INVALID_INPUT: i32 = -2;
+/// The output buffer is shorter than the result.
+pub const ZWAVES_ERR_BUFFER_TOO_SMALL: i32 = -3;
+/// Unexpected internal failure.
+pub const ZWAVES_ERR_INTERNAL: i32 = -4;
+
+/// Length of a serialized field element.
+pub const ZWAVES_FR_LEN: usize = 32;
+/// Length of a serialized note: asset_id, amount, native_amount, txid and owner.
+pub const ZWAVES_NOTE_LEN: usize = 5 * ZWAVES_FR_LEN;
+
+lazy_static! {
+    static ref JUBJUB_PARAMS: JubjubBls12 = JubjubBls12::new();
+}
+
+/// Verifies a BN256 Groth16 proof with the Ride `bn256Groth16Verify` layout, the result is written to `valid`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_bn256_groth16_verify(
+    vk: *const u8,
+    vk_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+    inputs: *const u8,
+    inputs_len: usize,
+    valid: *mut bool,
+) -> i32 {
+    ffi_guard(|| {
+        let res =
+            zwaves_jni::bn256::groth16_verify(input(vk, vk_len)?, input(proof, proof_len)?, input(inputs, inputs_len)?)
+                .map_err(|_| ZWAVES_ERR_INVALID_INPUT)?;
+
+        *output(valid)? = res == 1;
+        Ok(())
+    })
+}
+
+/// Verifies a BLS12-381 Groth16 proof with the Ride `groth16Verify` layout, the result is written to `valid`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_bls12_groth16_verify(
+    vk: *const u8,
+    vk_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+    inputs: *const u8,
+    inputs_len: usize,
+    valid: *mut bool,
+) -> i32 {
+    ffi_guard(|| {
+        let res =
+            zwaves_jni::bls12::groth16_verify(input(vk, vk_len)?, input(proof, proof_len)?, input(inputs, inputs_len)?)
+                .map_err(|_| ZWAVES_ERR_INVALID_INPUT)?;
+
+        *output(valid)? = res == 1;
+        Ok(())
+    })
+}
+
+/// Writes the hash of a `ZWAVES_NOTE_LEN` bytes note to `out`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_note_hash(note: *const u8, note_len: usize, out: *mut u8, out_len: usize) -> i32 {
+    ffi_guard(|| {
+        let note = input(note, note_len)?;
+        if note.len() != ZWAVES_NOTE_LEN {
+            return Err(ZWAVES_ERR_INVALID_INPUT);
+        }
+
+        let note = NoteData::<Bls12> {
+            asset_id: read_fr(&note[0..32])?,
+            amount: read_fr(&note[32..64])?,
+            native_amount: read_fr(&note[64..96])?,
+            txid: read_fr(&note[96..128])?,
+            owner: read_fr(&note[128..160])?,
+        };
+
+        write_fr(&transactions::note_hash(&note, &JUBJUB_PARAMS), out, out_len)
+    })
+}
+
+/// Writes the nullifier of a note hash for the secret key `sk` to `out`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_nullifier(
+    note_hash: *const u8,
+    note_hash_len: usize,
+    sk: *const u8,
+    sk_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        let note_hash = read_fr(input(note_hash, note_hash_len)?)?;
+        let sk = read_fr(input(sk, sk_len)?)?;
+
+        write_fr(
+            &transactions::nullifier::<Bls12>(&note_hash, &sk, &JUBJUB_PARAMS),
+            out,
+            out_len,
+        )
+    })
+}
+
+/// Writes the public key of the secret key `sk` to `out`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_pubkey(sk: *const u8, sk_len: usize, out: *mut u8, out_len: usize) -> i32 {
+    ffi_guard(|| {
+        let sk = read_fr(input(sk, sk_len)?)?;
+
+        write_fr(&transactions::pubkey::<Bls12>(&sk, &JUBJUB_PARAMS), out, out_len)
+    })
+}
+
+/// Writes the Diffie-Hellman shared secret of the public key `pk` and the secret key `sk` to `out`.
+#[no_mangle]
+pub unsafe extern "C" fn zwaves_edh(
+    pk: *const u8,
+    pk_len: usize,
+    sk: *const u8,
+    sk_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> i32 {
+    ffi_guard(|| {
+        let pk = read_fr(input(pk, pk_len)?)?;
+        let sk = read_fr(input(sk, sk_len)?)?;
+
+        let res = transactions::edh::<Bls12>(&pk, &sk, &JUBJUB_PARAMS).ok_or(ZWAVES_ERR_INVALID_INPUT)?;
+        write_fr(&res, out, out_len)
+    })
+}
+
+/// Panics must not unwind into C, they are reported as `ZWAVES_ERR_INTERNAL`.
+fn ffi_guard<F>(f: F) -> i32
+where
+    F: FnOnce() -> Result<(), i32>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => ZWAVES_OK,
+        Ok(Err(code)) => code,
+        Err(_) => ZWAVES_ERR_INTERNAL,
+    }
+}
+
+unsafe fn input<'a>(data: *const u8, len: usize) -> Result<&'a [u8], i32> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(ZWAVES_ERR_NULL_POINTER)
+    } else {
+        Ok(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn output<'a, T>(data: *mut T) -> Result<&'a mut T, i32> {
+    data.as_mut().ok_or(ZWAVES_ERR_NULL_POINTER)
+}
+
+fn read_fr(data: &[u8]) -> Result<Fr, i32> {
+    if data.len() != ZWAVES_FR_LEN {
+        return Err(ZWAVES_ERR_INVALID_INPUT);
+    }
+
+    let repr = read_fr_repr_be::<Fr>(data).map_err(|_| ZWAVES_ERR_INVALID_INPUT)?;
+    Fr::from_repr(repr).map_err(|_| ZWAVES_ERR_INVALID_INPUT)
+}
+
+unsafe fn write_fr(fr: &Fr, out: *mut u8, out_len: usize) -> Result<(), i32> {
+    if out.is_null() {
+        return Err(ZWAVES_ERR_NULL_POINTER);
+    }
+    if out_len < ZWAVES_FR_LEN {
+        return Err(ZWAVES_ERR_BUFFER_TOO_SMALL);
+    }
+
+    let out = slice::from_raw_parts_mut(out, ZWAVES_FR_LEN);
+    fr.into_repr().write_be(out).map_err(|_| ZWAVES_ERR_INTERNAL)
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use base64::decode;
+    use std::ptr;
+    use test_case::test_case;
+
+    const VK: &str = "kYYCAS8vM2T99GeCr4toQ+iQzvl5fI89mPrncYqx3C1d75BQbFk8LMtcnLWwntd6knkzSwcsialcheg69eZYPK8EzKRVI5FrRHKi8rgB+R5jyPV70ejmYEx1neTmfYKODRmARr/ld6pZTzBWYDfrCkiS1QB+3q3M08OQgYcLzs/vjW4epetDCmk0K1CEGcWdh7yLzdqr7HHQNOpZI8mdj/7lR0IBqB9zvRfyTr+guUG22kZo4y2KINDp272xGglKEeTglTxyDUriZJNF/+T6F8w70MR/rV+flvuo6EJ0+HA+A2ZnBbTjOIl9wjisBV+0jgld4oAppAOzvQ7eoIx2tbuuKVSdbJm65KDxl/T+boaYnjRm3omdETYnYRk3HAhrAeWpefX+dM/k7PrcheInnxHUyjzSzqlN03xYjg28kdda9FZJaVsQKqdEJ/St9ivXlp7+dPDIOfm77haSFnvr33VwYH/KbIalfOJPRvBLzqlHD8BxunNebMr6Gr6S+u+n";
+    const PROOF: &str = "sStVLdyxqInmv76iaNnRFB464lGq48iVeqYWSi2linE9DST0fTNhxSnvSXAoPpt8tFsanj5vPafC+ij/Fh98dOUlMbO42bf280pOZ4lm+zr63AWUpOOIugST+S6pq9zeB0OHp2NY8XFmriOEKhxeabhuV89ljqCDjlhXBeNZwM5zti4zg89Hd8TbKcw46jAsjIJe2Siw3Th7ELQQKR5ucX50f0GISmnOSceePPdvjbGJ8fSFOnSmSp8dK7uyehrU";
+
+    // Vector of `zwaves_jni::bn256` tests with one input
+    const BN256_VK: &str = "LDCJzjgi5HtcHEXHfU8TZz+ZUHD2ZwsQ7JIEvzdMPYKYs9SoGkKUmg1yya4TE0Ms7x+KOJ4Ze/CPfKp2s5jbniFNM71N/YlHVbNkytLtQi1DzReSh9SNBsvskdY5mavQJe+67PuPVEYnx+lJ97qIG8243njZbGWPqUJ2Vqj49NAunhqX+eIkK3zAB3IPWls3gruzX2t9wrmyE9cVVvf1kgWx63PsQV37qdH0KcFRpCH89k4TPS6fLmqdFxX3YGHCGFTpr6tLogvjbUFJPT98kJ/xck0C0B/s8PTVKdao4VQHT4DBIO8+GB3CQVh6VV4EcMLtDWWNxF4yloAlKcFT0Q4AzJSimpFqd/SwSz9Pb7uk5srte3nwphVamC+fHlJt";
+    const BN256_PROOF: &str = "GQPBoHuCPcIosF+WZKE5jZV13Ib4EdjLnABncpSHcMKBZl0LhllnPxcuzExIQwhxcfXvFFAjlnDGpKauQ9OQsjBKUBsdBZnGiV2Sg4TSdyHuLo2AbRRqJN0IV3iH3On8I4ngnL30ZAxVyGQH2EK58aUZGxMbbXGR9pQdh99QaiE=";
+    const BN256_INPUTS: &str = "IfZhAypdtgvecKDWzVyRuvXatmFf2ZYcMWVkCJ0/MQo=";
+
+    fn fr(n: u8) -> [u8; 32] {
+        let mut res = [0u8; 32];
+        res[31] = n;
+        res
+    }
+
+    fn call_pubkey(sk: &[u8], out_len: usize) -> (i32, Vec<u8>) {
+        let mut out = vec![0u8; out_len];
+        let code = unsafe { zwaves_pubkey(sk.as_ptr(), sk.len(), out.as_mut_ptr(), out.len()) };
+        (code, out)
+    }
+
+    #[test]
+    fn bls12_groth16_verify_test() {
+        let vk = decode(VK).unwrap();
+        let proof = decode(PROOF).unwrap();
+        let mut valid = false;
+
+        let code = unsafe {
+            zwaves_bls12_groth16_verify(
+                vk.as_ptr(),
+                vk.len(),
+                proof.as_ptr(),
+                proof.len(),
+                ptr::null(),
+                0,
+                &mut valid,
+            )
+        };
+        assert_eq!(code, ZWAVES_OK);
+        assert!(valid);
+
+        let code = unsafe {
+            zwaves_bls12_groth16_verify(
+                vk.as_ptr(),
+                vk.len(),
+                proof.as_ptr(),
+                proof.len() - 1,
+                ptr::null(),
+                0,
+                &mut valid,
+            )
+        };
+        assert_eq!(code, ZWAVES_ERR_INVALID_INPUT);
+
+        let code =
+            unsafe { zwaves_bls12_groth16_verify(vk.as_ptr(), vk.len(), ptr::null(), 192, ptr::null(), 0, &mut valid) };
+        assert_eq!(code, ZWAVES_ERR_NULL_POINTER);
+    }
+
+    fn call_bn256_verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> (i32, bool) {
+        let mut valid = false;
+        let code = unsafe {
+            zwaves_bn256_groth16_verify(
+                vk.as_ptr(),
+                vk.len(),
+                proof.as_ptr(),
+                proof.len(),
+                inputs.as_ptr(),
+                inputs.len(),
+                &mut valid,
+            )
+        };
+        (code, valid)
+    }
+
+    #[test]
+    fn bn256_groth16_verify_test() {
+        let vk = decode(BN256_VK).unwrap();
+        let proof = decode(BN256_PROOF).unwrap();
+        let inputs = decode(BN256_INPUTS).unwrap();
+
+        assert_eq!(call_bn256_verify(&vk, &proof, &inputs), (ZWAVES_OK, true));
+
+        let mut wrong_inputs = inputs.clone();
+        wrong_inputs[31] ^= 1;
+        assert_eq!(call_bn256_verify(&vk, &proof, &wrong_inputs), (ZWAVES_OK, false));
+
+        assert_eq!(call_bn256_verify(&vk, &proof[1..], &inputs).0, ZWAVES_ERR_INVALID_INPUT);
+    }
+
+    #[test]
+    fn primitives_test() {
+        let note = [fr(1), fr(2), fr(3), fr(4), fr(5)].concat();
+        let mut hash = [0u8; 32];
+        let code = unsafe { zwaves_note_hash(note.as_ptr(), note.len(), hash.as_mut_ptr(), hash.len()) };
+        assert_eq!(code, ZWAVES_OK);
+
+        let expected = transactions::note_hash::<Bls12>(
+            &NoteData {
+                asset_id: read_fr(&fr(1)).unwrap(),
+                amount: read_fr(&fr(2)).unwrap(),
+                native_amount: read_fr(&fr(3)).unwrap(),
+                txid: read_fr(&fr(4)).unwrap(),
+                owner: read_fr(&fr(5)).unwrap(),
+            },
+            &JUBJUB_PARAMS,
+        );
+        assert_eq!(read_fr(&hash).unwrap(), expected);
+
+        let (code, pk) = call_pubkey(&fr(7), 32);
+        assert_eq!(code, ZWAVES_OK);
+
+        let mut secret = [0u8; 32];
+        let code = unsafe { zwaves_edh(pk.as_ptr(), pk.len(), fr(9).as_ptr(), 32, secret.as_mut_ptr(), 32) };
+        assert_eq!(code, ZWAVES_OK);
+
+        let mut nf = [0u8; 32];
+        let code = unsafe { zwaves_nullifier(hash.as_ptr(), 32, fr(7).as_ptr(), 32, nf.as_mut_ptr(), 32) };
+        assert_eq!(code, ZWAVES_OK);
+        assert_ne!(nf, hash);
+    }
+
+    #[test_case(&[0u8; 31], 32 => ZWAVES_ERR_INVALID_INPUT; "short input")]
+    #[test_case(&[0xff; 32], 32 => ZWAVES_ERR_INVALID_INPUT; "not a field element")]
+    #[test_case(&[0u8; 32], 31 => ZWAVES_ERR_BUFFER_TOO_SMALL; "short output")]
+    #[test_case(&[0u8; 32], 64 => ZWAVES_OK; "long output")]
+    fn pubkey_error_test(sk: &[u8], out_len: usize) -> i32 {
+        call_pubkey(sk, out_len).0
+    }
+}
@@ -0,0 +1,48 @@
+package com.wavesplatform.zwaves;
+
+/**
+ * Outcome of a detailed proof verification, created by the native library.
+ */
+public final class VerificationResult {
+    /**
+     * The order of constants matches the reason codes of the native library.
+     */
+    public enum Reason {
+        VALID,
+        WRONG_BUFFER_LENGTH,
+        INVALID_ENCODING,
+        NOT_ON_CURVE,
+        POINT_AT_INFINITY,
+        NOT_IN_SUBGROUP,
+        INPUT_NOT_IN_FIELD,
+        PAIRING_MISMATCH
+    }
+
+    private final Reason reason;
+    private final String element;
+
+    VerificationResult(int reason, String element) {
+        this.reason = Reason.values()[reason];
+        this.element = element;
+    }
+
+    public boolean isValid() {
+        return reason == Reason.VALID;
+    }
+
+    public Reason getReason() {
+        return reason;
+    }
+
+    /**
+     * @return the failed element, e.g. "alpha", "ic[3]", "b" or "input[0]", or null if the failure is not tied to one
+     */
+    public String getElement() {
+        return element;
+    }
+
+    @Override
+    public String toString() {
+        return element == null ? reason.toString() : reason + " (" + element + ")";
+    }
+}
@@ -0,0 +1,10 @@
+package com.wavesplatform.zwaves;
+
+/**
+ * Thrown by native methods when the library fails for a reason unrelated to the passed data.
+ */
+public class ZWavesInternalException extends RuntimeException {
+    public ZWavesInternalException(String message) {
+        super(message);
+    }
+}
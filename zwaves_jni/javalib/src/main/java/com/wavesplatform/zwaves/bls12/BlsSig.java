@@ -0,0 +1,37 @@
+package com.wavesplatform.zwaves.bls12;
+
+/**
+ * BLS signatures over BLS12-381, minimal-pubkey-size variant with the proof of possession ciphersuite
+ * {@code BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_}. Public keys are 48 bytes compressed G1 points and
+ * signatures are 96 bytes compressed G2 points. Proofs of possession of the keys are not checked here.
+ */
+public class BlsSig {
+    /**
+     * Verifies a signature of the message by the public key.
+     *
+     * @throws IllegalArgumentException if the key or the signature is malformed, or the key is the point at infinity
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean verify(byte[] pk, byte[] msg, byte[] sig);
+
+    /**
+     * Verifies an aggregate signature of {@code msgs[i]} by {@code pks[i]} for all {@code i}.
+     * The messages should be distinct. Empty arrays are not valid.
+     *
+     * @throws IllegalArgumentException if the arrays lengths differ, or a key or the signature is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean aggregateVerify(byte[][] pks, byte[][] msgs, byte[] sig);
+
+    /**
+     * Verifies an aggregate signature of one message by all the public keys. Empty arrays are not valid.
+     *
+     * @throws IllegalArgumentException if a key or the signature is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean fastAggregateVerify(byte[][] pks, byte[] msg, byte[] sig);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
@@ -1,8 +1,53 @@
 package com.wavesplatform.zwaves.bls12;
 
 public class Groth16 {
+    /**
+     * @throws IllegalArgumentException if vk, proof or inputs are malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
     public static native boolean verify(byte[] vk, byte[] proof, byte[] inputs);
 
+    /**
+     * Verifies all (proofs[i], inputs[i]) pairs against the same verifying key.
+     *
+     * @return -1 if every proof is valid, otherwise the index of the first invalid proof
+     * @throws IllegalArgumentException if vk is malformed or the array lengths differ
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native int verifyBatch(byte[] vk, byte[][] proofs, byte[][] inputs);
+
+    /**
+     * Same check as {@link #verify}, but explains why and on which element the verification failed.
+     * Malformed data is reported in the result instead of being thrown.
+     *
+     * @throws IllegalArgumentException if any argument is null
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native com.wavesplatform.zwaves.VerificationResult verifyDetailed(byte[] vk, byte[] proof, byte[] inputs);
+
+    /**
+     * Parses the verifying key and precomputes its part of the pairing check, so it is not redone on every
+     * verification. The handle must be passed to {@link #release} when the key is no longer used.
+     *
+     * @return an opaque handle of the prepared key, never 0
+     * @throws IllegalArgumentException if vk is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native long prepareVerifyingKey(byte[] vk);
+
+    /**
+     * Same as {@link #verify} with the key prepared by {@link #prepareVerifyingKey}.
+     *
+     * @throws IllegalArgumentException if the handle is unknown or released, or proof or inputs are malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean verifyWithHandle(long vkHandle, byte[] proof, byte[] inputs);
+
+    /**
+     * Frees the prepared key. Releasing an unknown or already released handle does nothing.
+     */
+    public static native void release(long vkHandle);
+
     static {
         com.wavesplatform.zwaves.ZWavesJNILibrary.init();
     }
@@ -0,0 +1,44 @@
+package com.wavesplatform.zwaves.bls12;
+
+/**
+ * KZG polynomial commitment openings over BLS12-381 with the semantics of {@code verify_kzg_proof}
+ * and {@code verify_kzg_proof_batch} from EIP-4844. Commitments and proofs are 48 bytes compressed G1 points,
+ * field elements are 32 bytes big-endian.
+ */
+public class Kzg {
+    /**
+     * Loads a trusted setup in the {@code trusted_setup.txt} format of the EIP-4844 libraries.
+     * The handle must be passed to {@link #release} when the setup is no longer used.
+     *
+     * @return an opaque handle of the setup, never 0
+     * @throws IllegalArgumentException if the setup is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException if the file cannot be read or on internal errors
+     */
+    public static native long loadTrustedSetup(String path);
+
+    /**
+     * Verifies that the proof opens the commitment to the value {@code y} at the point {@code z}.
+     *
+     * @throws IllegalArgumentException if the handle is unknown or released, or an argument is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean verifyProof(long setupHandle, byte[] commitment, byte[] z, byte[] y, byte[] proof);
+
+    /**
+     * Verifies all (commitments[i], zs[i], ys[i], proofs[i]) openings at once. An empty batch is valid.
+     *
+     * @throws IllegalArgumentException if the handle is unknown or released, the array lengths differ
+     *                                  or an element is malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean verifyProofBatch(long setupHandle, byte[][] commitments, byte[][] zs, byte[][] ys, byte[][] proofs);
+
+    /**
+     * Frees the setup. Releasing an unknown or already released handle does nothing.
+     */
+    public static native void release(long setupHandle);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
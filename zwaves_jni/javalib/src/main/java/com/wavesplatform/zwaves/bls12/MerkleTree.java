@@ -0,0 +1,57 @@
+package com.wavesplatform.zwaves.bls12;
+
+/**
+ * Append-only Pedersen Merkle tree with the hashing of {@link Shielded#compress}, empty leaves are zeros.
+ * Field elements are 32 bytes big-endian and must be canonical. The tree keeps all the nodes and gives proofs
+ * for any index, or only the frontier and gives only the proof of the next leaf.
+ * The handle must be passed to {@link #release} when the tree is no longer used, it must not be used
+ * from several threads at once.
+ */
+public class MerkleTree {
+    /**
+     * Creates an empty tree of the {@code depth}, at most 63.
+     *
+     * @param frontier keep only the proof of the next leaf
+     * @return an opaque handle of the tree, never 0
+     */
+    public static native long create(int depth, boolean frontier);
+
+    /**
+     * Resumes a frontier tree with {@code size} leaves, {@code sibling} is the proof of the leaf at the index
+     * {@code size}.
+     *
+     * @throws IllegalArgumentException if the proof does not fit the depth or is not a proof of the next leaf
+     */
+    public static native long fromFrontier(int depth, long size, byte[][] sibling);
+
+    /**
+     * @return the index of the first leaf
+     * @throws IllegalArgumentException if the handle is unknown or released, a leaf is malformed
+     *                                  or the leaves do not fit the tree
+     */
+    public static native long append(long treeHandle, byte[][] leaves);
+
+    public static native byte[] root(long treeHandle);
+
+    /**
+     * Siblings of the leaf at the {@code index} from the bottom up.
+     *
+     * @throws IllegalArgumentException if the index does not fit the tree or the tree keeps only the frontier
+     *                                  and the index is not the next one
+     */
+    public static native byte[][] proof(long treeHandle, long index);
+
+    /**
+     * Number of the leaves, the index of the next one.
+     */
+    public static native long size(long treeHandle);
+
+    /**
+     * Frees the tree. Releasing an unknown or already released handle does nothing.
+     */
+    public static native void release(long treeHandle);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
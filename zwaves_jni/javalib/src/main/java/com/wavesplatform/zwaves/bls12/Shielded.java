@@ -0,0 +1,62 @@
+package com.wavesplatform.zwaves.bls12;
+
+/**
+ * Shielded pool primitives: Pedersen Merkle hashing, note hashes, nullifiers, keys and note encryption over
+ * the Jubjub curve.
+ * Field elements are 32 bytes big-endian and must be canonical, the same encoding as in the Node binding.
+ * All methods throw {@code IllegalArgumentException} on malformed field elements and
+ * {@code com.wavesplatform.zwaves.ZWavesInternalException} on internal errors of the native library.
+ */
+public class Shielded {
+    /**
+     * Hash of two children at the {@code level} of the Merkle tree, leaves are at the level 0.
+     */
+    public static native byte[] compress(byte[] left, byte[] right, int level);
+
+    /**
+     * Root of the tree with the {@code leaf} at the {@code index} and its siblings from the bottom up.
+     *
+     * @throws IllegalArgumentException also if the index does not fit the proof length
+     */
+    public static native byte[] merkleRoot(byte[][] sibling, long index, byte[] leaf);
+
+    /**
+     * Appends the {@code leaves} to the tree at the first empty {@code index}, {@code sibling} is the proof
+     * of that index.
+     *
+     * @return the new root followed by the proof of the index after the appended leaves
+     * @throws IllegalArgumentException also if the root does not match the proof or the leaves do not fit the tree
+     */
+    public static native byte[][] updateMerkleRootAndProof(byte[] root, byte[][] sibling, long index, byte[][] leaves);
+
+    public static native byte[] noteHash(byte[] assetId, byte[] amount, byte[] nativeAmount, byte[] txid, byte[] owner);
+
+    public static native byte[] nullifier(byte[] noteHash, byte[] sk);
+
+    public static native byte[] pubkey(byte[] sk);
+
+    /**
+     * Diffie-Hellman shared secret of the public key {@code pk} and the secret key {@code sk}.
+     */
+    public static native byte[] edh(byte[] pk, byte[] sk);
+
+    /**
+     * Encrypts the note to its {@code owner} with a random ephemeral key, the result is the {@code m} blob of
+     * {@code transferExternal}.
+     *
+     * @throws IllegalArgumentException also if the asset id or the amounts do not fit 64 bits
+     */
+    public static native byte[] encryptNote(byte[] assetId, byte[] amount, byte[] nativeAmount, byte[] txid, byte[] owner);
+
+    /**
+     * Trial decryption of a note with the secret key {@code sk}.
+     *
+     * @return the fields of the note in the order of {@link #noteHash}, {@code null} if the note is not encrypted
+     * to the owner of {@code sk}
+     */
+    public static native byte[][] tryDecryptNote(byte[] data, byte[] sk);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
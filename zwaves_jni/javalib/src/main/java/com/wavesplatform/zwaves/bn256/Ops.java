@@ -0,0 +1,38 @@
+package com.wavesplatform.zwaves.bn256;
+
+/**
+ * BN256 curve operations with the semantics of the Ethereum precompiles (EIP-196 and EIP-197).
+ * Points are 64 bytes for G1 and 128 bytes for G2, big-endian affine coordinates with the imaginary parts
+ * of G2 first, and all zeros for the point at infinity.
+ */
+public class Ops {
+    /**
+     * Sum of two G1 points. The input is padded with zeros or truncated to 128 bytes.
+     *
+     * @return the 64 bytes point
+     * @throws IllegalArgumentException if a point is not on the curve or a coordinate is not in the field
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native byte[] g1Add(byte[] input);
+
+    /**
+     * Product of a G1 point and a 32 bytes big-endian scalar. The input is padded with zeros or truncated to 96 bytes.
+     *
+     * @return the 64 bytes point
+     * @throws IllegalArgumentException if the point is not on the curve or a coordinate is not in the field
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native byte[] g1Mul(byte[] input);
+
+    /**
+     * Checks that the product of pairings of the 192 bytes (G1, G2) pairs is one. An empty input is valid.
+     *
+     * @throws IllegalArgumentException if the length is not a multiple of 192 or a point is invalid
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean pairingCheck(byte[] input);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
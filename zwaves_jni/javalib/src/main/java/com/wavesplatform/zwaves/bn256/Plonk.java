@@ -0,0 +1,22 @@
+package com.wavesplatform.zwaves.bn256;
+
+/**
+ * PLONK verifier with KZG commitments, compatible with snarkjs.
+ * <p>
+ * The proof is the solidity verifier calldata of snarkjs: A, B, C, Z, T1, T2, T3, Wxi, Wxiw as 64 bytes points
+ * followed by eval_a, eval_b, eval_c, eval_s1, eval_s2, eval_zw as 32 bytes scalars, 768 bytes in total.
+ * The vk is power, nPublic, k1, k2, w as 32 bytes words, Qm, Ql, Qr, Qo, Qc, S1, S2, S3 as 64 bytes points
+ * and X_2 as a 128 bytes point, 800 bytes in total. Points are in the EIP-196/197 encoding.
+ */
+public class Plonk {
+    /**
+     * @param inputs public inputs, 32 bytes big-endian each
+     * @throws IllegalArgumentException if vk, proof or inputs are malformed
+     * @throws com.wavesplatform.zwaves.ZWavesInternalException on internal errors of the native library
+     */
+    public static native boolean verify(byte[] vk, byte[] proof, byte[] inputs);
+
+    static {
+        com.wavesplatform.zwaves.ZWavesJNILibrary.init();
+    }
+}
@@ -0,0 +1,65 @@
+package com.wavesplatform.zwaves.bls12;
+
+import org.junit.Test;
+
+import static org.junit.Assert.*;
+
+public class BlsSigTest {
+    private static final String PK1 = "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a";
+    private static final String PK2 = "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81";
+    private static final String PK3 = "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f";
+
+    private static final String MSG0 = "0000000000000000000000000000000000000000000000000000000000000000";
+    private static final String MSG1 = "5656565656565656565656565656565656565656565656565656565656565656";
+    private static final String MSG2 = "abababababababababababababababababababababababababababababababab";
+
+    private static final String SIG1_MSG0 =
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb515809" +
+            "0352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55";
+    private static final String AGG_SIG =
+            "9104e74b9dfd3ad502f25d6a5ef57db0ed7d9a0e00f3500586d8ce44231212542fcfaf87840539b398bf07626705cf11" +
+            "05d246ca1062c6c2e1a53029a0f790ed5e3cb1f52f8234dc5144c45fc847c0cd37a92d68e7c5ba7c648a8a339f171244";
+    private static final String FAST_AGG_SIG_MSG2 =
+            "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a767" +
+            "0fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930";
+
+    private static byte[] hex(String s) {
+        byte[] res = new byte[s.length() / 2];
+        for (int i = 0; i < res.length; i++) {
+            res[i] = (byte) Integer.parseInt(s.substring(2 * i, 2 * i + 2), 16);
+        }
+        return res;
+    }
+
+    @Test
+    public void testVerify() {
+        assertTrue(BlsSig.verify(hex(PK1), hex(MSG0), hex(SIG1_MSG0)));
+        assertFalse(BlsSig.verify(hex(PK1), hex(MSG1), hex(SIG1_MSG0)));
+        assertFalse(BlsSig.verify(hex(PK2), hex(MSG0), hex(SIG1_MSG0)));
+    }
+
+    @Test
+    public void testAggregateVerify() {
+        byte[][] pks = {hex(PK1), hex(PK2), hex(PK3)};
+
+        assertTrue(BlsSig.aggregateVerify(pks, new byte[][]{hex(MSG0), hex(MSG1), hex(MSG2)}, hex(AGG_SIG)));
+        assertFalse(BlsSig.aggregateVerify(pks, new byte[][]{hex(MSG0), hex(MSG2), hex(MSG1)}, hex(AGG_SIG)));
+        assertFalse(BlsSig.aggregateVerify(new byte[0][], new byte[0][], hex(AGG_SIG)));
+    }
+
+    @Test
+    public void testFastAggregateVerify() {
+        assertTrue(BlsSig.fastAggregateVerify(new byte[][]{hex(PK1), hex(PK2), hex(PK3)}, hex(MSG2), hex(FAST_AGG_SIG_MSG2)));
+        assertFalse(BlsSig.fastAggregateVerify(new byte[][]{hex(PK1), hex(PK2)}, hex(MSG2), hex(FAST_AGG_SIG_MSG2)));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedSignature() {
+        BlsSig.verify(hex(PK1), hex(MSG0), new byte[95]);
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMessagesCount() {
+        BlsSig.aggregateVerify(new byte[][]{hex(PK1)}, new byte[0][], hex(SIG1_MSG0));
+    }
+}
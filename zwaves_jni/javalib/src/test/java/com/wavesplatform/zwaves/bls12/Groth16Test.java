@@ -1,5 +1,6 @@
 package com.wavesplatform.zwaves.bls12;
 
+import com.wavesplatform.zwaves.VerificationResult;
 import org.junit.Test;
 
 import java.util.Base64;
@@ -7,17 +8,69 @@ import java.util.Base64;
 import static org.junit.Assert.*;
 
 public class Groth16Test {
+    private static final byte[] VK = Base64.getDecoder().decode("hwk883gUlTKCyXYA6XWZa8H9/xKIYZaJ0xEs0M5hQOMxiGpxocuX/8maSDmeCk3bo5ViaDBdO7ZBxAhLSe5k/5TFQyF5Lv7KN2tLKnwgoWMqB16OL8WdbePIwTCuPtJNAFKoTZylLDbSf02kckMcZQDPF9iGh+JC99Pio74vDpwTEjUx5tQ99gNQwxULtztsqDRsPnEvKvLmsxHt8LQVBkEBm2PBJFY+OXf1MNW021viDBpR10mX4WQ6zrsGL5L0GY4cwf4tlbh+Obit+LnN/SQTnREf8fPpdKZ1sa/ui3pGi8lMT6io4D7Ujlwx2RdCkBF+isfMf77HCEGsZANw0hSrO2FGg14Sl26xLAIohdaW8O7gEaag8JdVAZ3OVLd5Df1NkZBEr753Xb8WwaXsJjE7qxwINL1KdqA4+EiYW4edb7+a9bbBeOPtb67ZxmFqgyTNS/4obxahezNkjk00ytswsENg//Ee6dWBJZyLH+QGsaU2jO/W4WvRyZhmKKPdipOhiz4Rlrd2XYgsfHsfWf5v4GOTL+13ZB24dW1/m39n2woJ+v686fXbNW85XP/r");
+    private static final byte[] PROOF = Base64.getDecoder().decode("lvQLU/KqgFhsLkt/5C/scqs7nWR+eYtyPdWiLVBux9GblT4AhHYMdCgwQfSJcudvsgV6fXoK+DUSRgJ++Nqt+Wvb7GlYlHpxCysQhz26TTu8Nyo7zpmVPH92+UYmbvbQCSvX2BhWtvkfHmqDVjmSIQ4RUMfeveA1KZbSf999NE4qKK8Do+8oXcmTM4LZVmh1rlyqznIdFXPN7x3pD4E0gb6/y69xtWMChv9654FMg05bAdueKt9uA4BEcAbpkdHF");
+    private static final byte[] INPUTS = Base64.getDecoder().decode("LcMT3OOlkHLzJBKCKjjzzVMg+r+FVgd52LlhZPB4RFg=");
+    private static final byte[] INPUTS2 = Base64.getDecoder().decode("cmzVCcRVnckw3QUPhmG4Bkppeg4K50oDQwQ9EH+Fq1s=");
+
     @Test
     public void test() {
-        byte[] vk = Base64.getDecoder().decode("hwk883gUlTKCyXYA6XWZa8H9/xKIYZaJ0xEs0M5hQOMxiGpxocuX/8maSDmeCk3bo5ViaDBdO7ZBxAhLSe5k/5TFQyF5Lv7KN2tLKnwgoWMqB16OL8WdbePIwTCuPtJNAFKoTZylLDbSf02kckMcZQDPF9iGh+JC99Pio74vDpwTEjUx5tQ99gNQwxULtztsqDRsPnEvKvLmsxHt8LQVBkEBm2PBJFY+OXf1MNW021viDBpR10mX4WQ6zrsGL5L0GY4cwf4tlbh+Obit+LnN/SQTnREf8fPpdKZ1sa/ui3pGi8lMT6io4D7Ujlwx2RdCkBF+isfMf77HCEGsZANw0hSrO2FGg14Sl26xLAIohdaW8O7gEaag8JdVAZ3OVLd5Df1NkZBEr753Xb8WwaXsJjE7qxwINL1KdqA4+EiYW4edb7+a9bbBeOPtb67ZxmFqgyTNS/4obxahezNkjk00ytswsENg//Ee6dWBJZyLH+QGsaU2jO/W4WvRyZhmKKPdipOhiz4Rlrd2XYgsfHsfWf5v4GOTL+13ZB24dW1/m39n2woJ+v686fXbNW85XP/r");
-        byte[] proof = Base64.getDecoder().decode("lvQLU/KqgFhsLkt/5C/scqs7nWR+eYtyPdWiLVBux9GblT4AhHYMdCgwQfSJcudvsgV6fXoK+DUSRgJ++Nqt+Wvb7GlYlHpxCysQhz26TTu8Nyo7zpmVPH92+UYmbvbQCSvX2BhWtvkfHmqDVjmSIQ4RUMfeveA1KZbSf999NE4qKK8Do+8oXcmTM4LZVmh1rlyqznIdFXPN7x3pD4E0gb6/y69xtWMChv9654FMg05bAdueKt9uA4BEcAbpkdHF");
-        byte[] inputs = Base64.getDecoder().decode("LcMT3OOlkHLzJBKCKjjzzVMg+r+FVgd52LlhZPB4RFg=");
+        assertTrue("Result should be true", Groth16.verify(VK, PROOF, INPUTS));
+        assertFalse("Result should be false", Groth16.verify(VK, PROOF, INPUTS2));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedInputs() {
+        Groth16.verify(VK, PROOF, new byte[0]);
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testNullVk() {
+        Groth16.verify(null, PROOF, INPUTS);
+    }
+
+    @Test
+    public void testBatch() {
+        assertEquals(-1, Groth16.verifyBatch(VK, new byte[][]{PROOF, PROOF, PROOF}, new byte[][]{INPUTS, INPUTS, INPUTS}));
+        assertEquals(1, Groth16.verifyBatch(VK, new byte[][]{PROOF, PROOF, PROOF}, new byte[][]{INPUTS, INPUTS2, INPUTS}));
+        assertEquals(-1, Groth16.verifyBatch(VK, new byte[][]{}, new byte[][]{}));
+    }
 
-        byte[] inputs2 = Base64.getDecoder().decode("cmzVCcRVnckw3QUPhmG4Bkppeg4K50oDQwQ9EH+Fq1s=");
-        byte[] inputs3 = {};
+    @Test
+    public void testDetailed() {
+        assertTrue(Groth16.verifyDetailed(VK, PROOF, INPUTS).isValid());
+
+        VerificationResult mismatch = Groth16.verifyDetailed(VK, PROOF, INPUTS2);
+        assertEquals(VerificationResult.Reason.PAIRING_MISMATCH, mismatch.getReason());
+        assertNull(mismatch.getElement());
+
+        VerificationResult notInField = Groth16.verifyDetailed(VK, PROOF, new byte[]{
+                -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1});
+        assertEquals(VerificationResult.Reason.INPUT_NOT_IN_FIELD, notInField.getReason());
+        assertEquals("input[0]", notInField.getElement());
+
+        assertEquals(VerificationResult.Reason.WRONG_BUFFER_LENGTH, Groth16.verifyDetailed(VK, PROOF, new byte[0]).getReason());
+    }
+
+    @Test
+    public void testHandle() {
+        long handle = Groth16.prepareVerifyingKey(VK);
+        assertNotEquals(0, handle);
+
+        try {
+            assertTrue(Groth16.verifyWithHandle(handle, PROOF, INPUTS));
+            assertFalse(Groth16.verifyWithHandle(handle, PROOF, INPUTS2));
+        } finally {
+            Groth16.release(handle);
+        }
+        Groth16.release(handle);
+    }
 
-        assertTrue("Result should be true", Groth16.verify(vk, proof, inputs));
-        assertFalse("Result should be false", Groth16.verify(vk, proof, inputs2));
-        assertFalse("Result should be false", Groth16.verify(vk, proof, inputs3));
+    @Test(expected = IllegalArgumentException.class)
+    public void testReleasedHandle() {
+        long handle = Groth16.prepareVerifyingKey(VK);
+        Groth16.release(handle);
+        Groth16.verifyWithHandle(handle, PROOF, INPUTS);
     }
 }
@@ -0,0 +1,86 @@
+package com.wavesplatform.zwaves.bls12;
+
+import org.junit.AfterClass;
+import org.junit.BeforeClass;
+import org.junit.Test;
+
+import java.io.File;
+import java.io.IOException;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+
+import static org.junit.Assert.*;
+
+public class KzgTest {
+    // Setup of 4 points with tau = 0x7a7761766573
+    private static final String SETUP = "4\n2\n" +
+            "98309eccb1f36d6efe46b0c31e2ea9f5d170aec3af0925623b84b15b6f8937a6d01c4a17415cf631295689569299297e\n" +
+            "b8066806c0097f19feb66e9effea9fe1c406ff76972eb1ed9b7cfa1e3a9471201652456c21545242be415825ea1577f0\n" +
+            "8ae3c32a4dbbdff799f7fc826a189a6c242db9195772b846d16d86507b9cad69f2b33e85f4f50da7dc1cdae9975eba81\n" +
+            "80ff19400ea8d0278822c47e7af8a8c930352ff574f320b87ff9caa216804ebcb808dbdfdc76212277f73060267ab415\n" +
+            "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e" +
+            "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8\n" +
+            "950b2bf4e0171f2a4c89575f0d9efd828ab3710442acba6b5e6c004943493d10cfc56b9cf0ddea596d5b72e3d1791c6e" +
+            "16c97a0e54eeceb14d953555089c163a15835348882c5af276172e752a85b0ab6311d923f1eb42d0b7ade68861c7c928\n";
+
+    private static final String C1 = "a56d59125898383324a366ffac70e616cba41436c82e9812c7075fe5806d9bc3cf7aa82e093bad59e892eacb9804345d";
+    private static final String Z1 = "0000000000000000000000000000000000000000000000000000000000001234";
+    private static final String Y1 = "39f6d3a313fa196e25bd8b6d00ed427b776048ef0f6166294679fff3b7840701";
+    private static final String PROOF1 = "92b3c66800a0b373de7a3dadbc4cd7805526619d7799fd8b6c685891417484ea7951b82cb37f5f34f46ba760307e39ac";
+
+    private static final String C3 = "acebd0993cccda239cf55fbbfa250dd176a745eb9e8cba1a5bba146c0f00f8f4e2fdabab40e1a0c02296289c44e2cce6";
+    private static final String Z3 = "0000000000000000000000000000000000000000000000000000000000005678";
+    private static final String Y3 = "39f6d0f08598416788b4bb8ad81b7c06a538931d57c4754a3c3bfb10b8aff9af";
+    private static final String PROOF3 = "a73b53b78765d0ddf6d917715606ed709ee50a118cfbfd8c550ff8c011f2dcb67b3548f2fa6041209af77483e9fe543e";
+
+    private static long setup;
+
+    private static byte[] hex(String s) {
+        byte[] res = new byte[s.length() / 2];
+        for (int i = 0; i < res.length; i++) {
+            res[i] = (byte) Integer.parseInt(s.substring(2 * i, 2 * i + 2), 16);
+        }
+        return res;
+    }
+
+    @BeforeClass
+    public static void loadSetup() throws IOException {
+        File file = File.createTempFile("trusted_setup", ".txt");
+        file.deleteOnExit();
+        Files.write(file.toPath(), SETUP.getBytes(StandardCharsets.US_ASCII));
+
+        setup = Kzg.loadTrustedSetup(file.getPath());
+    }
+
+    @AfterClass
+    public static void releaseSetup() {
+        Kzg.release(setup);
+    }
+
+    @Test
+    public void testVerifyProof() {
+        assertTrue(Kzg.verifyProof(setup, hex(C1), hex(Z1), hex(Y1), hex(PROOF1)));
+        assertTrue(Kzg.verifyProof(setup, hex(C3), hex(Z3), hex(Y3), hex(PROOF3)));
+        assertFalse(Kzg.verifyProof(setup, hex(C1), hex(Z1), hex(Y3), hex(PROOF1)));
+    }
+
+    @Test
+    public void testVerifyProofBatch() {
+        byte[][] commitments = {hex(C1), hex(C3)};
+        byte[][] zs = {hex(Z1), hex(Z3)};
+
+        assertTrue(Kzg.verifyProofBatch(setup, commitments, zs, new byte[][]{hex(Y1), hex(Y3)}, new byte[][]{hex(PROOF1), hex(PROOF3)}));
+        assertFalse(Kzg.verifyProofBatch(setup, commitments, zs, new byte[][]{hex(Y1), hex(Y3)}, new byte[][]{hex(PROOF3), hex(PROOF1)}));
+        assertTrue(Kzg.verifyProofBatch(setup, new byte[0][], new byte[0][], new byte[0][], new byte[0][]));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedCommitment() {
+        Kzg.verifyProof(setup, new byte[47], hex(Z1), hex(Y1), hex(PROOF1));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testUnknownHandle() {
+        Kzg.verifyProof(0, hex(C1), hex(Z1), hex(Y1), hex(PROOF1));
+    }
+}
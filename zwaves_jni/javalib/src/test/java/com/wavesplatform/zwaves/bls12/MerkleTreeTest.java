@@ -0,0 +1,67 @@
+package com.wavesplatform.zwaves.bls12;
+
+import org.junit.Test;
+
+import static org.junit.Assert.*;
+
+public class MerkleTreeTest {
+    private static byte[] fr(int n) {
+        byte[] res = new byte[32];
+        res[31] = (byte) n;
+        return res;
+    }
+
+    @Test
+    public void testAppend() {
+        long full = MerkleTree.create(8, false);
+        long frontier = MerkleTree.create(8, true);
+        try {
+            byte[][] leaves = {fr(1), fr(2), fr(3), fr(4), fr(5)};
+
+            assertEquals(0, MerkleTree.append(full, leaves));
+            assertEquals(0, MerkleTree.append(frontier, leaves));
+            assertEquals(5, MerkleTree.append(full, new byte[][]{fr(6)}));
+            assertEquals(5, MerkleTree.append(frontier, new byte[][]{fr(6)}));
+
+            assertEquals(6, MerkleTree.size(full));
+            assertArrayEquals(MerkleTree.root(full), MerkleTree.root(frontier));
+            assertArrayEquals(MerkleTree.root(full), Shielded.merkleRoot(MerkleTree.proof(full, 2), 2, fr(3)));
+            assertArrayEquals(MerkleTree.proof(full, 6), MerkleTree.proof(frontier, 6));
+        } finally {
+            MerkleTree.release(full);
+            MerkleTree.release(frontier);
+        }
+    }
+
+    @Test
+    public void testFromFrontier() {
+        long tree = MerkleTree.create(8, true);
+        MerkleTree.append(tree, new byte[][]{fr(1), fr(2), fr(3)});
+
+        long resumed = MerkleTree.fromFrontier(8, 3, MerkleTree.proof(tree, 3));
+        try {
+            assertArrayEquals(MerkleTree.root(tree), MerkleTree.root(resumed));
+        } finally {
+            MerkleTree.release(tree);
+            MerkleTree.release(resumed);
+        }
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testFrontierProof() {
+        long tree = MerkleTree.create(8, true);
+        try {
+            MerkleTree.append(tree, new byte[][]{fr(1), fr(2)});
+            MerkleTree.proof(tree, 0);
+        } finally {
+            MerkleTree.release(tree);
+        }
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testReleasedHandle() {
+        long tree = MerkleTree.create(8, false);
+        MerkleTree.release(tree);
+        MerkleTree.root(tree);
+    }
+}
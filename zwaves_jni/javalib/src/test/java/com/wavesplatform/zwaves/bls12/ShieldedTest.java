@@ -0,0 +1,81 @@
+package com.wavesplatform.zwaves.bls12;
+
+import org.junit.Test;
+
+import java.util.Arrays;
+
+import static org.junit.Assert.*;
+
+public class ShieldedTest {
+    private static byte[] fr(int n) {
+        byte[] res = new byte[32];
+        res[31] = (byte) n;
+        return res;
+    }
+
+    private static byte[][] defaults(int depth) {
+        byte[][] res = new byte[depth][];
+        res[0] = fr(0);
+        for (int i = 1; i < depth; i++) {
+            res[i] = Shielded.compress(res[i - 1], res[i - 1], i - 1);
+        }
+        return res;
+    }
+
+    @Test
+    public void testMerkleRoot() {
+        byte[][] sibling = {fr(1), fr(2), fr(3)};
+
+        byte[] level0 = Shielded.compress(sibling[0], fr(7), 0);
+        byte[] level1 = Shielded.compress(level0, sibling[1], 1);
+        byte[] level2 = Shielded.compress(sibling[2], level1, 2);
+
+        assertArrayEquals(level2, Shielded.merkleRoot(sibling, 5, fr(7)));
+    }
+
+    @Test
+    public void testUpdateMerkleRootAndProof() {
+        byte[][] defaults = defaults(8);
+        byte[] emptyRoot = Shielded.merkleRoot(defaults, 0, fr(0));
+        byte[][] leaves = {fr(1), fr(2), fr(3)};
+
+        byte[][] res = Shielded.updateMerkleRootAndProof(emptyRoot, defaults, 0, leaves);
+        assertEquals(9, res.length);
+
+        byte[][] proof = Arrays.copyOfRange(res, 1, res.length);
+        assertArrayEquals(res[0], Shielded.merkleRoot(proof, 3, fr(0)));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testUpdateWrongRoot() {
+        Shielded.updateMerkleRootAndProof(fr(1), defaults(8), 0, new byte[][]{fr(1)});
+    }
+
+    @Test
+    public void testKeys() {
+        byte[] pkA = Shielded.pubkey(fr(11));
+        byte[] pkB = Shielded.pubkey(fr(12));
+        assertArrayEquals(Shielded.edh(pkA, fr(12)), Shielded.edh(pkB, fr(11)));
+
+        byte[] hash = Shielded.noteHash(fr(1), fr(2), fr(3), fr(4), pkA);
+        assertEquals(32, hash.length);
+        assertFalse(Arrays.equals(Shielded.nullifier(hash, fr(11)), Shielded.nullifier(hash, fr(12))));
+    }
+
+    @Test
+    public void testNoteEncryption() {
+        byte[] pkA = Shielded.pubkey(fr(11));
+        byte[] data = Shielded.encryptNote(fr(1), fr(2), fr(3), fr(4), pkA);
+
+        byte[][] note = Shielded.tryDecryptNote(data, fr(11));
+        assertArrayEquals(new byte[][]{fr(1), fr(2), fr(3), fr(4), pkA}, note);
+        assertNull(Shielded.tryDecryptNote(data, fr(12)));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testWrongFieldElement() {
+        byte[] e = new byte[32];
+        Arrays.fill(e, (byte) 0xff);
+        Shielded.pubkey(e);
+    }
+}
@@ -1,5 +1,6 @@
 package com.wavesplatform.zwaves.bn256;
 
+import com.wavesplatform.zwaves.VerificationResult;
 import org.junit.Test;
 
 import java.util.Base64;
@@ -7,17 +8,79 @@ import java.util.Base64;
 import static org.junit.Assert.*;
 
 public class Groth16Test {
+    private static final byte[] VK = Base64.getDecoder().decode("LDCJzjgi5HtcHEXHfU8TZz+ZUHD2ZwsQ7JIEvzdMPYKYs9SoGkKUmg1yya4TE0Ms7x+KOJ4Ze/CPfKp2s5jbniFNM71N/YlHVbNkytLtQi1DzReSh9SNBsvskdY5mavQJe+67PuPVEYnx+lJ97qIG8243njZbGWPqUJ2Vqj49NAunhqX+eIkK3zAB3IPWls3gruzX2t9wrmyE9cVVvf1kgWx63PsQV37qdH0KcFRpCH89k4TPS6fLmqdFxX3YGHCGFTpr6tLogvjbUFJPT98kJ/xck0C0B/s8PTVKdao4VQHT4DBIO8+GB3CQVh6VV4EcMLtDWWNxF4yloAlKcFT0Q4AzJSimpFqd/SwSz9Pb7uk5srte3nwphVamC+fHlJt");
+    private static final byte[] PROOF = Base64.getDecoder().decode("GQPBoHuCPcIosF+WZKE5jZV13Ib4EdjLnABncpSHcMKBZl0LhllnPxcuzExIQwhxcfXvFFAjlnDGpKauQ9OQsjBKUBsdBZnGiV2Sg4TSdyHuLo2AbRRqJN0IV3iH3On8I4ngnL30ZAxVyGQH2EK58aUZGxMbbXGR9pQdh99QaiE=");
+    private static final byte[] INPUTS = Base64.getDecoder().decode("IfZhAypdtgvecKDWzVyRuvXatmFf2ZYcMWVkCJ0/MQo=");
+    private static final byte[] INPUTS2 = Base64.getDecoder().decode("IfZhAypdtgvecKDWzVyRuvXatmFf2ZYcMWVkCJ0/MQs=");
+
     @Test
     public void test() {
-        byte[] vk = Base64.getDecoder().decode("LDCJzjgi5HtcHEXHfU8TZz+ZUHD2ZwsQ7JIEvzdMPYKYs9SoGkKUmg1yya4TE0Ms7x+KOJ4Ze/CPfKp2s5jbniFNM71N/YlHVbNkytLtQi1DzReSh9SNBsvskdY5mavQJe+67PuPVEYnx+lJ97qIG8243njZbGWPqUJ2Vqj49NAunhqX+eIkK3zAB3IPWls3gruzX2t9wrmyE9cVVvf1kgWx63PsQV37qdH0KcFRpCH89k4TPS6fLmqdFxX3YGHCGFTpr6tLogvjbUFJPT98kJ/xck0C0B/s8PTVKdao4VQHT4DBIO8+GB3CQVh6VV4EcMLtDWWNxF4yloAlKcFT0Q4AzJSimpFqd/SwSz9Pb7uk5srte3nwphVamC+fHlJt");
-        byte[] proof = Base64.getDecoder().decode("GQPBoHuCPcIosF+WZKE5jZV13Ib4EdjLnABncpSHcMKBZl0LhllnPxcuzExIQwhxcfXvFFAjlnDGpKauQ9OQsjBKUBsdBZnGiV2Sg4TSdyHuLo2AbRRqJN0IV3iH3On8I4ngnL30ZAxVyGQH2EK58aUZGxMbbXGR9pQdh99QaiE=");
-        byte[] inputs = Base64.getDecoder().decode("IfZhAypdtgvecKDWzVyRuvXatmFf2ZYcMWVkCJ0/MQo=");
+        assertTrue("Result should be true", Groth16.verify(VK, PROOF, INPUTS));
+        assertFalse("Result should be false", Groth16.verify(VK, PROOF, INPUTS2));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedInputs() {
+        Groth16.verify(VK, PROOF, new byte[0]);
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testNullVk() {
+        Groth16.verify(null, PROOF, INPUTS);
+    }
+
+    @Test
+    public void testBatch() {
+        assertEquals(-1, Groth16.verifyBatch(VK, new byte[][]{PROOF, PROOF, PROOF}, new byte[][]{INPUTS, INPUTS, INPUTS}));
+        assertEquals(1, Groth16.verifyBatch(VK, new byte[][]{PROOF, PROOF, PROOF}, new byte[][]{INPUTS, INPUTS2, INPUTS}));
+        assertEquals(-1, Groth16.verifyBatch(VK, new byte[][]{}, new byte[][]{}));
+    }
+
+    @Test
+    public void testDetailed() {
+        assertTrue(Groth16.verifyDetailed(VK, PROOF, INPUTS).isValid());
+
+        VerificationResult mismatch = Groth16.verifyDetailed(VK, PROOF, INPUTS2);
+        assertEquals(VerificationResult.Reason.PAIRING_MISMATCH, mismatch.getReason());
+        assertNull(mismatch.getElement());
 
-        byte[] inputs2 = Base64.getDecoder().decode("cmzVCcRVnckw3QUPhmG4Bkppeg4K50oDQwQ9EH+Fq1s=");
-        byte[] inputs3 = {};
+        VerificationResult notInField = Groth16.verifyDetailed(VK, PROOF, new byte[]{
+                -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1});
+        assertEquals(VerificationResult.Reason.INPUT_NOT_IN_FIELD, notInField.getReason());
+        assertEquals("input[0]", notInField.getElement());
+
+        assertEquals(VerificationResult.Reason.WRONG_BUFFER_LENGTH, Groth16.verifyDetailed(VK, PROOF, new byte[0]).getReason());
+    }
+
+    @Test
+    public void testHandle() {
+        long handle = Groth16.prepareVerifyingKey(VK);
+        assertNotEquals(0, handle);
+
+        try {
+            assertTrue(Groth16.verifyWithHandle(handle, PROOF, INPUTS));
+            assertFalse(Groth16.verifyWithHandle(handle, PROOF, INPUTS2));
+        } finally {
+            Groth16.release(handle);
+        }
+        Groth16.release(handle);
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testReleasedHandle() {
+        long handle = Groth16.prepareVerifyingKey(VK);
+        Groth16.release(handle);
+        Groth16.verifyWithHandle(handle, PROOF, INPUTS);
+    }
+
+    @Test
+    public void testValidateVerifyingKey() {
+        Groth16.validateVerifyingKey(VK);
+    }
 
-        assertTrue("Result should be true", Groth16.verify(vk, proof, inputs));
-        assertFalse("Result should be false", Groth16.verify(vk, proof, inputs2));
-        assertFalse("Result should be false", Groth16.verify(vk, proof, inputs3));
+    @Test(expected = IllegalArgumentException.class)
+    public void testValidateVerifyingKeyTrailingBytes() {
+        Groth16.validateVerifyingKey(java.util.Arrays.copyOf(VK, VK.length + 5));
     }
 }
@@ -0,0 +1,60 @@
+package com.wavesplatform.zwaves.bn256;
+
+import org.junit.Test;
+
+import static org.junit.Assert.*;
+
+public class OpsTest {
+    private static final String G1 =
+            "0000000000000000000000000000000000000000000000000000000000000001" +
+            "0000000000000000000000000000000000000000000000000000000000000002";
+    private static final String G1_NEG =
+            "0000000000000000000000000000000000000000000000000000000000000001" +
+            "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+    private static final String G1_DOUBLE =
+            "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3" +
+            "15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+    private static final String G2 =
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2" +
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed" +
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b" +
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+    private static byte[] hex(String s) {
+        byte[] res = new byte[s.length() / 2];
+        for (int i = 0; i < res.length; i++) {
+            res[i] = (byte) Integer.parseInt(s.substring(2 * i, 2 * i + 2), 16);
+        }
+        return res;
+    }
+
+    @Test
+    public void testG1Add() {
+        assertArrayEquals(hex(G1_DOUBLE), Ops.g1Add(hex(G1 + G1)));
+        assertArrayEquals(new byte[64], Ops.g1Add(hex(G1 + G1_NEG)));
+        assertArrayEquals(hex(G1), Ops.g1Add(hex(G1)));
+    }
+
+    @Test
+    public void testG1Mul() {
+        assertArrayEquals(hex(G1_DOUBLE), Ops.g1Mul(hex(G1 + "0000000000000000000000000000000000000000000000000000000000000002")));
+        assertArrayEquals(new byte[64], Ops.g1Mul(hex(G1 + "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001")));
+    }
+
+    @Test
+    public void testPairingCheck() {
+        assertTrue(Ops.pairingCheck(new byte[0]));
+        assertTrue(Ops.pairingCheck(hex(G1 + G2 + G1_NEG + G2)));
+        assertFalse(Ops.pairingCheck(hex(G1 + G2 + G1 + G2)));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testNotOnCurve() {
+        Ops.g1Add(hex(G1.substring(0, 126) + "03"));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testPairingWrongLength() {
+        Ops.pairingCheck(new byte[191]);
+    }
+}
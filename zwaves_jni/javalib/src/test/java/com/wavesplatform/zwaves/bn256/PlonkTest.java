@@ -0,0 +1,30 @@
+package com.wavesplatform.zwaves.bn256;
+
+import org.junit.Test;
+
+import java.util.Base64;
+
+import static org.junit.Assert.*;
+
+public class PlonkTest {
+    private static final byte[] VK = Base64.getDecoder().decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMrM33hyMFPIuybni+Wr+82UmJzZvgXCgqUja1Kwb1egA7KqkxwufmyVDnKKooHOtwMG/+P26vBEWmunAdxjrKgHYA9WbYqYzYGeC3u24TEET5jjXKACffv+ZX47G+jBbAuvPggQFXkv1HD1K3gFrE+VonFvoY9b6cMm40ykQq//wW4tXS52c0CHGvAfjXy80P/WO1PR6FzKwn7nQVlGmD2InjIKetFrPVHpC1K+G/XKZABhoZdFSWGeTeiNpIO2nEIydOAeycQG6sxMB1bO6pvMXFIm3NSSzaVBGOaxweeXibRU53mlSTgerV2STOEkMfJKoqy2GUtjgE34rLAE/lRBb/DSMnnPY2jKfrkasvgjqSKXCAkm7BOcEJ714BNhVgs7a3D1/x50Jsx02O/lol89iL6oRX8IwN/4Q3gYAJVPQPUEhBcjsiV8rxslZu9iRnL2e0LRwyAocTc79mKlUJFC8mgrxdA+4S6lAoiT3JjMNMv7y0osqAu2aoUNg0nhF0cvrjPb2wZHTNMXny35bo7AEIe0kK1ijAgVmTEhlnoki3HXum4S6IcrLvgc+TSW398p508ho5cO1czL7xAy+gjHMBwCw/xm4QvxfdAI5JeTi05skOICyQloz8Pom/WgsguAxQZXEnGP0sM6H3kYWknIeBEsiWSjDp9Nn3RSx4SwCDxYGWMl48MZeeO1mymJwYu++SlDNftq+XK6UrzhmSYIGVbJlhdWAmbdUuZFRIn/D6EZjdOUOR4H7/tH+OiE9UMz2V45GEdElFNOSTai2F88nfMDIYbdKV0dVHOtxinAATz0lQw3hB/1U4wVKhD0yfozPE2Fjx++XkDdb6TmYoTENGk6LRyiYMJQknN79JQD9CxuP0gB4HldwtK10ythaI=");
+    private static final byte[] PROOF = Base64.getDecoder().decode("GEdu+us7F4jm4+HmmObUR1jY3iUSbF3/w4g/bPgKRQEtosmD8Bu5MjyAbIj4tprq7rCkP4l6tZlNTt57RhTawQZbSLfcTGHvYtlo4JNuSvCsapMG0x/lQDgbWGVw5az7HtTTQd9QPm/ohhtKEvlq6IzuH3X+uaqVT5722MbGZMcNDyAwWX5bPn+Nm6/M4bTVTTWSUEM7ye59Rkrtrma9ABbxkpJLjIYUZxyYAMiSV+ZkjwxdKdyuBRddy2P9rShqKERVPTM8kI9R14F4FRZ2hLR2CCHBJmu8MtK6QuGqO5YBs/6RAt+yM4pmoxtN7lEHsHNQsYqENjjl5zjIJCaKDy4p2MbpxLFeA18dsXw5eUpR8+NzEoT3gYRrKHg1PKtSEmIIvDtV8Zzxt1wnk84xMpb/GABc5of/je3yrzRsMGQKWwEC5lvMVPfDTD5Eu1e/jIBFjOHKVT2Xf3DNexojaiqsqwAN4nRm/FRuPVW1IYp9rkPjjCiA88U05bHFQfLRACNyJqKi8H3siRY2YR6Sc8A+5Mp+Pb668RJVyaE7if0WO0ri6IwGZEyNh5N1wflbxV65lnec2nNJYCZvT+sR4xZyd+jeFOiq/z2YMw5ag5O8xTBLu3efKlRFVIMMUEadD2Raw8uzldi7ICDLII7KwOwe2IykZJ6mJPG3Bequ7S4X7dU8MWY8rFYgyYhjIlKwoxHosrPdyCS9GpMH48dagCnOeM8eCK5NDd3mywAxe1JJbTfQf5U4txz5ybo8PNBZFC0QrDyC/IDrHU/5ERe4vC58ZykFbHNLzRrQ/XqQXEYE/HJ/xKIvt/2/yg+v/Fl/LkIZAh4MfvfSW2eiGSmw6hoWfYPPH+yZaXWijX+GpfI1Y7pTRsBA81MDd0V1BKppGEQBgA7JMKWHihXRh/FQ3iE9vweskz/alZBHWASbzKEtJ9Eem+FWK9h8yDB0p6AuqZZc+rGYCWymhExQ7sMQGh4IN1tyQObmaf4ZFmC/qYpfw4YQSq0CfMzzQCNjyF+2");
+    private static final byte[] INPUTS = Base64.getDecoder().decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACM=");
+    private static final byte[] INPUTS2 = Base64.getDecoder().decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACQ=");
+
+    @Test
+    public void test() {
+        assertTrue("Result should be true", Plonk.verify(VK, PROOF, INPUTS));
+        assertFalse("Result should be false", Plonk.verify(VK, PROOF, INPUTS2));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedInputs() {
+        Plonk.verify(VK, PROOF, new byte[0]);
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void testMalformedProof() {
+        Plonk.verify(VK, new byte[767], INPUTS);
+    }
+}
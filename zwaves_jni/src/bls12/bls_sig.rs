@@ -0,0 +1,522 @@
+//! BLS signatures over BLS12-381 in the minimal-pubkey-size variant of draft-irtf-cfrg-bls-signature:
+//! public keys are 48 bytes compressed G1 points and signatures are 96 bytes compressed G2 points.
+//!
+//! Messages are hashed to G2 with `BLS12381G2_XMD:SHA-256_SSWU_RO_` from draft-irtf-cfrg-hash-to-curve.
+//! The ciphersuite is the proof of possession one, as used by Ethereum 2, since it is the only one
+//! defining `FastAggregateVerify`. Proofs of possession must be checked when the keys are registered.
+
+use std::io;
+
+use lazy_static::lazy_static;
+use pairing::{
+    bls12_381::{Bls12, Fq, Fq12, Fq2, FqRepr, G1Affine, G2Affine, G2Uncompressed, G2},
+    BitIterator, CurveAffine, CurveProjective, EncodedPoint, Engine, Field, PrimeField, PrimeFieldRepr, SqrtField,
+};
+use sha2::{Digest, Sha256};
+
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+pub const PUBLIC_KEY_LEN: usize = 48;
+pub const SIGNATURE_LEN: usize = 96;
+
+/// Effective cofactor of G2 from the hash-to-curve draft, little-endian limbs.
+const H_EFF: [u64; 10] = [
+    0xe8020005aaa95551,
+    0x59894c0adebbf6b4,
+    0xe954cbc06689f6a3,
+    0x2ec0ec69d7477c1a,
+    0x6d82bf015d1212b0,
+    0x329c2f178731db95,
+    0x9986ff031508ffe1,
+    0x88e2a8e9145ad768,
+    0x584c6a0ea91b3528,
+    0x0bc69f08f2ee75b3,
+];
+
+// Coefficients of the 3-isogeny map from E' to E, constant terms first, as `(c0, c1)` pairs
+const ISO3_XNUM: [(&str, &str); 4] = [
+    (
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+    ),
+    (
+        "0",
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a",
+    ),
+    (
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e",
+        "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d",
+    ),
+    (
+        "171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1",
+        "0",
+    ),
+];
+const ISO3_XDEN: [(&str, &str); 3] = [
+    (
+        "0",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63",
+    ),
+    (
+        "c",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f",
+    ),
+    ("1", "0"),
+];
+const ISO3_YNUM: [(&str, &str); 4] = [
+    (
+        "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+        "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+    ),
+    (
+        "0",
+        "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be",
+    ),
+    (
+        "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c",
+        "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f",
+    ),
+    (
+        "124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10",
+        "0",
+    ),
+];
+const ISO3_YDEN: [(&str, &str); 4] = [
+    (
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+    ),
+    (
+        "0",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3",
+    ),
+    (
+        "12",
+        "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99",
+    ),
+    ("1", "0"),
+];
+
+struct Constants {
+    /// `A'`, `B'` of the curve E' isogenous to G2 and `Z` of the simplified SWU map
+    a: Fq2,
+    b: Fq2,
+    z: Fq2,
+    xnum: Vec<Fq2>,
+    xden: Vec<Fq2>,
+    ynum: Vec<Fq2>,
+    yden: Vec<Fq2>,
+}
+
+lazy_static! {
+    static ref CONSTANTS: Constants = Constants {
+        a: fq2(&("0", "f0")),
+        b: fq2(&("3f4", "3f4")),
+        z: {
+            let mut z = fq2(&("2", "1"));
+            z.negate();
+            z
+        },
+        xnum: ISO3_XNUM.iter().map(fq2).collect(),
+        xden: ISO3_XDEN.iter().map(fq2).collect(),
+        ynum: ISO3_YNUM.iter().map(fq2).collect(),
+        yden: ISO3_YDEN.iter().map(fq2).collect(),
+    };
+}
+
+fn fq_from_hex(s: &str) -> Fq {
+    let s = format!("{:0>96}", s);
+    let bytes = (0..96)
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut repr = FqRepr::default();
+    repr.read_be(&bytes[..]).unwrap();
+    Fq::from_repr(repr).unwrap()
+}
+
+fn fq2(c: &(&str, &str)) -> Fq2 {
+    Fq2 {
+        c0: fq_from_hex(c.0),
+        c1: fq_from_hex(c.1),
+    }
+}
+
+/// Verifies a signature of `msg` by the public key `pk`.
+///
+/// Malformed keys and signatures, including keys at infinity, are errors.
+pub fn verify(pk: &[u8], msg: &[u8], sig: &[u8]) -> io::Result<bool> {
+    let pk = read_public_key(pk)?;
+    let sig = read_signature(sig)?;
+
+    Ok(pairing_check(&[(pk, hash_to_g2(msg, DST))], &sig))
+}
+
+/// Verifies an aggregate signature of `msgs[i]` by `pks[i]` for all `i`.
+///
+/// The messages are expected to be distinct, which is not checked here. Empty lists are not valid.
+pub fn aggregate_verify(pks: &[Vec<u8>], msgs: &[Vec<u8>], sig: &[u8]) -> io::Result<bool> {
+    if pks.len() != msgs.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong messages count"));
+    }
+
+    let sig = read_signature(sig)?;
+    let pairs = pks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(pk, msg)| Ok((read_public_key(pk)?, hash_to_g2(msg, DST))))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(!pairs.is_empty() && pairing_check(&pairs, &sig))
+}
+
+/// Verifies an aggregate signature of one `msg` by all `pks`. Empty lists are not valid.
+pub fn fast_aggregate_verify(pks: &[Vec<u8>], msg: &[u8], sig: &[u8]) -> io::Result<bool> {
+    let sig = read_signature(sig)?;
+
+    let mut aggregate = G1Affine::zero().into_projective();
+    for pk in pks {
+        aggregate.add_assign_mixed(&read_public_key(pk)?);
+    }
+
+    Ok(!pks.is_empty() && pairing_check(&[(aggregate.into_affine(), hash_to_g2(msg, DST))], &sig))
+}
+
+fn read_public_key(data: &[u8]) -> io::Result<G1Affine> {
+    if data.len() != PUBLIC_KEY_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong public key length"));
+    }
+
+    let mut repr = <G1Affine as CurveAffine>::Compressed::empty();
+    repr.as_mut().copy_from_slice(data);
+
+    let pk = repr
+        .into_affine()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if pk.is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "public key at infinity"));
+    }
+
+    Ok(pk)
+}
+
+fn read_signature(data: &[u8]) -> io::Result<G2Affine> {
+    if data.len() != SIGNATURE_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong signature length"));
+    }
+
+    let mut repr = <G2Affine as CurveAffine>::Compressed::empty();
+    repr.as_mut().copy_from_slice(data);
+
+    repr.into_affine()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Checks `e(pk_1, h_1) * ... * e(pk_n, h_n) == e(g1, sig)`.
+fn pairing_check(pairs: &[(G1Affine, G2)], sig: &G2Affine) -> bool {
+    let mut neg_g1 = G1Affine::one();
+    neg_g1.negate();
+
+    let mut prepared = pairs
+        .iter()
+        .map(|(pk, h)| (pk.prepare(), h.into_affine().prepare()))
+        .collect::<Vec<_>>();
+    prepared.push((neg_g1.prepare(), sig.prepare()));
+
+    let refs = prepared.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>();
+
+    Bls12::final_exponentiation(&Bls12::miller_loop(&refs)).unwrap() == Fq12::one()
+}
+
+/// `hash_to_curve` of the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite.
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2 {
+    let u = hash_to_field(msg, dst);
+
+    let mut q = map_to_curve(&u[0]);
+    q.add_assign(&map_to_curve(&u[1]));
+
+    clear_cofactor(&q)
+}
+
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    assert!(dst.len() <= 255 && len <= 255 * 32);
+
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    let b_0 = Sha256::new()
+        .chain(&[0u8; 64][..])
+        .chain(msg)
+        .chain(&[(len >> 8) as u8, len as u8, 0])
+        .chain(&dst_prime)
+        .finalize();
+
+    let mut b_i = Sha256::new().chain(&b_0).chain(&[1u8]).chain(&dst_prime).finalize();
+    let mut res = b_i.to_vec();
+
+    for i in 2..=((len + 31) / 32) as u8 {
+        let xored = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect::<Vec<_>>();
+        b_i = Sha256::new().chain(&xored).chain(&[i]).chain(&dst_prime).finalize();
+        res.extend_from_slice(&b_i);
+    }
+
+    res.truncate(len);
+    res
+}
+
+fn hash_to_field(msg: &[u8], dst: &[u8]) -> [Fq2; 2] {
+    let okm = expand_message_xmd(msg, dst, 256);
+
+    [
+        Fq2 {
+            c0: fq_from_okm(&okm[0..64]),
+            c1: fq_from_okm(&okm[64..128]),
+        },
+        Fq2 {
+            c0: fq_from_okm(&okm[128..192]),
+            c1: fq_from_okm(&okm[192..256]),
+        },
+    ]
+}
+
+/// Reduces a 64 bytes big-endian number modulo the field characteristic as `hi * 2^256 + lo`.
+fn fq_from_okm(data: &[u8]) -> Fq {
+    let read_256 = |data: &[u8]| {
+        let mut bytes = [0; 48];
+        bytes[16..].copy_from_slice(data);
+
+        let mut repr = FqRepr::default();
+        repr.read_be(&bytes[..]).unwrap();
+        Fq::from_repr(repr).unwrap()
+    };
+
+    let mut res = read_256(&data[..32]);
+    res.mul_assign(&Fq::from_repr(FqRepr([0, 0, 0, 0, 1, 0])).unwrap());
+    res.add_assign(&read_256(&data[32..]));
+    res
+}
+
+fn sgn0(a: &Fq2) -> bool {
+    a.c0.into_repr().is_odd() || (a.c0.is_zero() && a.c1.into_repr().is_odd())
+}
+
+/// `x^3 + A' * x + B'`
+fn curve_rhs(x: &Fq2) -> Fq2 {
+    let c = &*CONSTANTS;
+
+    let mut res = *x;
+    res.square();
+    res.add_assign(&c.a);
+    res.mul_assign(x);
+    res.add_assign(&c.b);
+    res
+}
+
+fn eval_poly(coeffs: &[Fq2], x: &Fq2) -> Fq2 {
+    coeffs.iter().rev().fold(Fq2::zero(), |mut acc, c| {
+        acc.mul_assign(x);
+        acc.add_assign(c);
+        acc
+    })
+}
+
+/// Simplified SWU map to E' followed by the 3-isogeny to the curve of G2.
+fn map_to_curve(u: &Fq2) -> G2 {
+    let c = &*CONSTANTS;
+
+    let mut z_u2 = *u;
+    z_u2.square();
+    z_u2.mul_assign(&c.z);
+
+    let mut tv1 = z_u2;
+    tv1.square();
+    tv1.add_assign(&z_u2);
+
+    let x1 = match tv1.inverse() {
+        Some(mut x1) => {
+            // -B' / A' * (1 + 1 / tv1)
+            x1.add_assign(&Fq2::one());
+            x1.mul_assign(&c.b);
+            x1.mul_assign(&c.a.inverse().unwrap());
+            x1.negate();
+            x1
+        }
+        None => {
+            // B' / (Z * A')
+            let mut x1 = c.z;
+            x1.mul_assign(&c.a);
+            let mut x1 = x1.inverse().unwrap();
+            x1.mul_assign(&c.b);
+            x1
+        }
+    };
+
+    let (x, mut y) = match curve_rhs(&x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let mut x2 = z_u2;
+            x2.mul_assign(&x1);
+            (x2, curve_rhs(&x2).sqrt().expect("g(x1) or g(x2) is a square"))
+        }
+    };
+
+    if sgn0(u) != sgn0(&y) {
+        y.negate();
+    }
+
+    iso_map(&x, &y)
+}
+
+fn iso_map(x: &Fq2, y: &Fq2) -> G2 {
+    let c = &*CONSTANTS;
+
+    let (x_den, y_den) = match (eval_poly(&c.xden, x).inverse(), eval_poly(&c.yden, x).inverse()) {
+        (Some(x_den), Some(y_den)) => (x_den, y_den),
+        // Exceptional points of the isogeny are mapped to infinity
+        _ => return G2::zero(),
+    };
+
+    let mut iso_x = eval_poly(&c.xnum, x);
+    iso_x.mul_assign(&x_den);
+
+    let mut iso_y = eval_poly(&c.ynum, x);
+    iso_y.mul_assign(&y_den);
+    iso_y.mul_assign(y);
+
+    let mut encoded = G2Uncompressed::empty();
+    for (chunk, coordinate) in encoded
+        .as_mut()
+        .chunks_mut(48)
+        .zip([iso_x.c1, iso_x.c0, iso_y.c1, iso_y.c0].iter())
+    {
+        coordinate.into_repr().write_be(chunk).unwrap();
+    }
+
+    encoded
+        .into_affine_unchecked()
+        .expect("isogeny maps to the curve")
+        .into_projective()
+}
+
+/// Multiplication by `H_EFF`, which maps the curve to G2.
+fn clear_cofactor(p: &G2) -> G2 {
+    let mut res = G2::zero();
+
+    for bit in BitIterator::new(H_EFF) {
+        res.double();
+        if bit {
+            res.add_assign(p);
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const PK1: &str =
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a";
+    const PK2: &str =
+        "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81";
+    const PK3: &str =
+        "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f";
+
+    const MSG0: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+    const MSG1: &str = "5656565656565656565656565656565656565656565656565656565656565656";
+    const MSG2: &str = "abababababababababababababababababababababababababababababababab";
+
+    const SIG1_MSG0: &str = "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55";
+    const SIG1_MSG1: &str = "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb";
+    const SIG2_MSG1: &str = "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff974ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe";
+    const SIG3_MSG2: &str = "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9";
+
+    // Aggregates of the signatures of MSG0, MSG1, MSG2 by the keys 1, 2, 3, of MSG2 by all keys
+    // and of MSG1 by the keys 1 and 2
+    const AGG_SIG: &str = "9104e74b9dfd3ad502f25d6a5ef57db0ed7d9a0e00f3500586d8ce44231212542fcfaf87840539b398bf07626705cf1105d246ca1062c6c2e1a53029a0f790ed5e3cb1f52f8234dc5144c45fc847c0cd37a92d68e7c5ba7c648a8a339f171244";
+    const FAST_AGG_SIG_MSG2: &str = "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930";
+    const FAST_AGG_SIG_MSG1: &str = "912c3615f69575407db9392eb21fee18fff797eeb2fbe1816366ca2a08ae574d8824dbfafb4c9eaa1cf61b63c6f9b69911f269b664c42947dd1b53ef1081926c1e82bb2a465f927124b08391a5249036146d6f3f1e17ff5f162f779746d830d1";
+
+    const INFINITY_G1: &str =
+        "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    // Vectors of draft-irtf-cfrg-hash-to-curve, appendix J.10.1
+    #[test_case(
+        "",
+        "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d60503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92";
+        "empty"
+    )]
+    #[test_case(
+        "abc",
+        "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e600aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd161787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48";
+        "abc"
+    )]
+    fn hash_to_g2_test(msg: &str, expected: &str) {
+        let p = hash_to_g2(msg.as_bytes(), b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_");
+        assert_eq!(p.into_affine().into_uncompressed().as_ref(), &hex(expected)[..]);
+    }
+
+    #[test_case(PK1, MSG0, SIG1_MSG0 => true; "valid")]
+    #[test_case(PK1, MSG1, SIG1_MSG1 => true; "valid other message")]
+    #[test_case(PK3, MSG2, SIG3_MSG2 => true; "valid other key")]
+    #[test_case(PK1, MSG1, SIG1_MSG0 => false; "wrong message")]
+    #[test_case(PK2, MSG0, SIG1_MSG0 => false; "wrong key")]
+    fn verify_test(pk: &str, msg: &str, sig: &str) -> bool {
+        verify(&hex(pk), &hex(msg), &hex(sig)).unwrap()
+    }
+
+    #[test]
+    fn verify_malformed_test() {
+        let mut not_on_curve = hex(SIG1_MSG0);
+        not_on_curve[95] ^= 1;
+
+        assert!(verify(&hex(INFINITY_G1), &hex(MSG0), &hex(SIG1_MSG0)).is_err());
+        assert!(verify(&hex(PK1)[..47], &hex(MSG0), &hex(SIG1_MSG0)).is_err());
+        assert!(verify(&hex(PK1), &hex(MSG0), &hex(SIG1_MSG0)[..95]).is_err());
+        assert!(verify(&hex(PK1), &hex(MSG0), &not_on_curve).is_err());
+    }
+
+    #[test_case(&[PK1, PK2, PK3], &[MSG0, MSG1, MSG2], AGG_SIG => true; "valid")]
+    #[test_case(&[PK1], &[MSG0], SIG1_MSG0 => true; "single")]
+    #[test_case(&[PK1, PK2, PK3], &[MSG0, MSG2, MSG1], AGG_SIG => false; "swapped messages")]
+    #[test_case(&[PK1, PK2], &[MSG0, MSG1], AGG_SIG => false; "missing signer")]
+    #[test_case(&[], &[], AGG_SIG => false; "empty")]
+    fn aggregate_verify_test(pks: &[&str], msgs: &[&str], sig: &str) -> bool {
+        let pks = pks.iter().map(|pk| hex(pk)).collect::<Vec<_>>();
+        let msgs = msgs.iter().map(|msg| hex(msg)).collect::<Vec<_>>();
+
+        aggregate_verify(&pks, &msgs, &hex(sig)).unwrap()
+    }
+
+    #[test_case(&[PK1, PK2, PK3], MSG2, FAST_AGG_SIG_MSG2 => true; "three keys")]
+    #[test_case(&[PK1, PK2], MSG1, FAST_AGG_SIG_MSG1 => true; "two keys")]
+    #[test_case(&[PK2, PK1], MSG1, FAST_AGG_SIG_MSG1 => true; "keys order")]
+    #[test_case(&[PK1], MSG1, SIG1_MSG1 => true; "single")]
+    #[test_case(&[PK1, PK2], MSG2, FAST_AGG_SIG_MSG2 => false; "missing key")]
+    #[test_case(&[PK1, PK2, PK3], MSG1, FAST_AGG_SIG_MSG2 => false; "wrong message")]
+    #[test_case(&[], MSG2, FAST_AGG_SIG_MSG2 => false; "empty")]
+    fn fast_aggregate_verify_test(pks: &[&str], msg: &str, sig: &str) -> bool {
+        let pks = pks.iter().map(|pk| hex(pk)).collect::<Vec<_>>();
+
+        fast_aggregate_verify(&pks, &hex(msg), &hex(sig)).unwrap()
+    }
+
+    #[test]
+    fn aggregate_verify_malformed_test() {
+        let pks = vec![hex(PK1), hex(INFINITY_G1)];
+
+        assert!(aggregate_verify(&pks, &[hex(MSG0), hex(MSG1)], &hex(AGG_SIG)).is_err());
+        assert!(aggregate_verify(&pks[..1], &[hex(MSG0), hex(MSG1)], &hex(AGG_SIG)).is_err());
+        assert!(fast_aggregate_verify(&pks, &hex(MSG1), &hex(FAST_AGG_SIG_MSG1)).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! KZG polynomial commitment openings over BLS12-381 with the semantics of `verify_kzg_proof`
+//! and `verify_kzg_proof_batch` from EIP-4844.
+//!
+//! Commitments and proofs are 48 bytes compressed G1 points, which must be in the subgroup
+//! and may be the point at infinity. Field elements are 32 bytes big-endian and must be canonical.
+
+use std::{fs, io, path::Path};
+
+use pairing::{
+    bls12_381::{Bls12, Fq12, Fr, FrRepr, G1Affine, G2Affine},
+    CurveAffine, CurveProjective, EncodedPoint, Engine, Field, PrimeField, PrimeFieldRepr,
+};
+use sha2::{Digest, Sha256};
+
+use zwaves_primitives::serialization::read_fr_repr_be;
+
+pub const G1_POINT_LEN: usize = 48;
+pub const G2_POINT_LEN: usize = 96;
+pub const FIELD_ELEMENT_LEN: usize = 32;
+
+const BATCH_DOMAIN: &[u8] = b"RCKZGBATCH___V1_";
+
+/// Points of the trusted setup: the G1 points in Lagrange form and the G2 points in monomial form,
+/// `[tau^i]G2`. Only `[tau]G2` is needed to verify openings.
+pub struct TrustedSetup {
+    pub g1_lagrange: Vec<G1Affine>,
+    pub g2_monomial: Vec<G2Affine>,
+}
+
+impl TrustedSetup {
+    /// Reads the `trusted_setup.txt` format of the EIP-4844 libraries: the numbers of G1 and G2 points,
+    /// then all the hex encoded compressed points, one value per line.
+    pub fn read(data: &str) -> io::Result<Self> {
+        let mut lines = data.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        let g1_len = parse_len(next_line(&mut lines)?)?;
+        let g2_len = parse_len(next_line(&mut lines)?)?;
+
+        if g1_len == 0 || g2_len < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong trusted setup size"));
+        }
+
+        let g1_lagrange = (0..g1_len)
+            .map(|_| read_point::<G1Affine>(&decode_hex(next_line(&mut lines)?)?))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let g2_monomial = (0..g2_len)
+            .map(|_| read_point::<G2Affine>(&decode_hex(next_line(&mut lines)?)?))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if lines.next().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "trailing trusted setup data",
+            ));
+        }
+
+        if g2_monomial[0] != G2Affine::one() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "wrong trusted setup generator",
+            ));
+        }
+
+        Ok(TrustedSetup {
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(&fs::read_to_string(path)?)
+    }
+}
+
+fn next_line<'a>(lines: &mut impl Iterator<Item = &'a str>) -> io::Result<&'a str> {
+    lines
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unexpected end of trusted setup"))
+}
+
+fn parse_len(line: &str) -> io::Result<usize> {
+    line.parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "wrong trusted setup size"))
+}
+
+fn decode_hex(s: &str) -> io::Result<Vec<u8>> {
+    let s = s.trim_start_matches("0x");
+
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong hex string"));
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "wrong hex string"))
+        })
+        .collect()
+}
+
+/// Reads a compressed point, checking that it is on the curve and in the subgroup.
+fn read_point<G: CurveAffine>(data: &[u8]) -> io::Result<G> {
+    let mut repr = G::Compressed::empty();
+
+    if data.len() != repr.as_ref().len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong point length"));
+    }
+
+    repr.as_mut().copy_from_slice(data);
+    repr.into_affine()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_field_element(data: &[u8]) -> io::Result<Fr> {
+    if data.len() != FIELD_ELEMENT_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong field element length"));
+    }
+
+    Fr::from_repr(read_fr_repr_be::<Fr>(data)?).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "not in field"))
+}
+
+/// Checks `e(a1, b1) == e(a2, b2)`.
+fn pairings_equal(a1: &G1Affine, b1: &G2Affine, a2: &G1Affine, b2: &G2Affine) -> bool {
+    let mut neg_a2 = *a2;
+    neg_a2.negate();
+
+    Bls12::final_exponentiation(&Bls12::miller_loop(&[
+        (&a1.prepare(), &b1.prepare()),
+        (&neg_a2.prepare(), &b2.prepare()),
+    ]))
+    .unwrap()
+        == Fq12::one()
+}
+
+/// Verifies that `proof` opens `commitment` to `y` at `z`, that is `p(z) == y` for the committed `p`.
+pub fn verify_kzg_proof(setup: &TrustedSetup, commitment: &[u8], z: &[u8], y: &[u8], proof: &[u8]) -> io::Result<bool> {
+    let commitment = read_point::<G1Affine>(commitment)?;
+    let z = read_field_element(z)?;
+    let y = read_field_element(y)?;
+    let proof = read_point::<G1Affine>(proof)?;
+
+    // e(C - [y]G1, G2) == e(proof, [tau]G2 - [z]G2)
+    let mut c_minus_y = commitment.into_projective();
+    c_minus_y.sub_assign(&G1Affine::one().mul(y.into_repr()));
+
+    let mut tau_minus_z = setup.g2_monomial[1].into_projective();
+    tau_minus_z.sub_assign(&G2Affine::one().mul(z.into_repr()));
+
+    Ok(pairings_equal(
+        &c_minus_y.into_affine(),
+        &G2Affine::one(),
+        &proof,
+        &tau_minus_z.into_affine(),
+    ))
+}
+
+/// Verifies all `(commitments[i], zs[i], ys[i], proofs[i])` openings with one pairing check,
+/// combined by the powers of a Fiat-Shamir challenge. An empty batch is valid.
+pub fn verify_kzg_proof_batch(
+    setup: &TrustedSetup,
+    commitments: &[Vec<u8>],
+    zs: &[Vec<u8>],
+    ys: &[Vec<u8>],
+    proofs: &[Vec<u8>],
+) -> io::Result<bool> {
+    let n = commitments.len();
+
+    if zs.len() != n || ys.len() != n || proofs.len() != n {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong batch length"));
+    }
+
+    let mut hasher = Sha256::new()
+        .chain(BATCH_DOMAIN)
+        .chain(&(setup.g1_lagrange.len() as u64).to_be_bytes())
+        .chain(&(n as u64).to_be_bytes());
+
+    let mut openings = Vec::with_capacity(n);
+
+    for i in 0..n {
+        openings.push((
+            read_point::<G1Affine>(&commitments[i])?,
+            read_field_element(&zs[i])?,
+            read_field_element(&ys[i])?,
+            read_point::<G1Affine>(&proofs[i])?,
+        ));
+
+        hasher = hasher
+            .chain(&commitments[i])
+            .chain(&zs[i])
+            .chain(&ys[i])
+            .chain(&proofs[i]);
+    }
+
+    let r = hash_to_field(&hasher.finalize());
+
+    // e(sum r^i proof_i, [tau]G2) == e(sum r^i (C_i - [y_i]G1 + [z_i]proof_i), G2)
+    let mut proof_lincomb = G1Affine::zero().into_projective();
+    let mut rhs_lincomb = G1Affine::zero().into_projective();
+    let mut y_lincomb = Fr::zero();
+    let mut r_power = Fr::one();
+
+    for (commitment, z, y, proof) in openings {
+        proof_lincomb.add_assign(&proof.mul(r_power.into_repr()));
+
+        let mut r_z = r_power;
+        r_z.mul_assign(&z);
+        rhs_lincomb.add_assign(&proof.mul(r_z.into_repr()));
+        rhs_lincomb.add_assign(&commitment.mul(r_power.into_repr()));
+
+        let mut r_y = r_power;
+        r_y.mul_assign(&y);
+        y_lincomb.add_assign(&r_y);
+
+        r_power.mul_assign(&r);
+    }
+
+    rhs_lincomb.sub_assign(&G1Affine::one().mul(y_lincomb.into_repr()));
+
+    Ok(pairings_equal(
+        &proof_lincomb.into_affine(),
+        &setup.g2_monomial[1],
+        &rhs_lincomb.into_affine(),
+        &G2Affine::one(),
+    ))
+}
+
+/// A 32 bytes big-endian hash reduced modulo the group order.
+fn hash_to_field(hash: &[u8]) -> Fr {
+    // The hash is less than 3r, so a few subtractions reduce it
+    let mut repr = FrRepr::default();
+    repr.read_be(hash).unwrap();
+    while repr >= Fr::char() {
+        repr.sub_noborrow(&Fr::char());
+    }
+    Fr::from_repr(repr).unwrap()
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn hex(s: &str) -> Vec<u8> {
+        decode_hex(s).unwrap()
+    }
+
+    // Setup of 4 points with tau = 0x7a7761766573, the openings are of the polynomials with evaluations
+    // [1, 2, 3, 4], [5, 6, 7, 8] and [9, 10, 11, 12] on the 4th roots of unity
+    const SETUP: &str = concat!(
+        "4\n",
+        "2\n",
+        "98309eccb1f36d6efe46b0c31e2ea9f5d170aec3af0925623b84b15b6f8937a6d01c4a17415cf631295689569299297e\n",
+        "b8066806c0097f19feb66e9effea9fe1c406ff76972eb1ed9b7cfa1e3a9471201652456c21545242be415825ea1577f0\n",
+        "8ae3c32a4dbbdff799f7fc826a189a6c242db9195772b846d16d86507b9cad69f2b33e85f4f50da7dc1cdae9975eba81\n",
+        "80ff19400ea8d0278822c47e7af8a8c930352ff574f320b87ff9caa216804ebcb808dbdfdc76212277f73060267ab415\n",
+        "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8\n",
+        "950b2bf4e0171f2a4c89575f0d9efd828ab3710442acba6b5e6c004943493d10cfc56b9cf0ddea596d5b72e3d1791c6e",
+        "16c97a0e54eeceb14d953555089c163a15835348882c5af276172e752a85b0ab6311d923f1eb42d0b7ade68861c7c928\n",
+    );
+
+    const C1: &str = "a56d59125898383324a366ffac70e616cba41436c82e9812c7075fe5806d9bc3cf7aa82e093bad59e892eacb9804345d";
+    const Z1: &str = "0000000000000000000000000000000000000000000000000000000000001234";
+    const Y1: &str = "39f6d3a313fa196e25bd8b6d00ed427b776048ef0f6166294679fff3b7840701";
+    const PROOF1: &str =
+        "92b3c66800a0b373de7a3dadbc4cd7805526619d7799fd8b6c685891417484ea7951b82cb37f5f34f46ba760307e39ac";
+
+    // Opening at the second root of unity, to the second evaluation
+    const C2: &str = "b9386e3316ff65ba5d9634aa2ac540f40d28c34f005193cd730ee2584cff8aa988144057cb5e25374eea5e6be6492863";
+    const Z2: &str = "00000000000000008d51ccce760304d0ec030002760300000001000000000000";
+    const Y2: &str = "0000000000000000000000000000000000000000000000000000000000000006";
+    const PROOF2: &str =
+        "8a82914db9f0cb9570cdd9b8411b81cd9c6d8c4bf83427511957aa45393ea13e33ed5b689620981d549959c89779fb84";
+
+    const C3: &str = "acebd0993cccda239cf55fbbfa250dd176a745eb9e8cba1a5bba146c0f00f8f4e2fdabab40e1a0c02296289c44e2cce6";
+    const Z3: &str = "0000000000000000000000000000000000000000000000000000000000005678";
+    const Y3: &str = "39f6d0f08598416788b4bb8ad81b7c06a538931d57c4754a3c3bfb10b8aff9af";
+    const PROOF3: &str =
+        "a73b53b78765d0ddf6d917715606ed709ee50a118cfbfd8c550ff8c011f2dcb67b3548f2fa6041209af77483e9fe543e";
+
+    const INFINITY: &str =
+        "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const ZERO: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+    const GROUP_ORDER: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    // (0, 2) is on the curve, but not in the subgroup
+    const NOT_IN_SUBGROUP: &str =
+        "800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    #[test_case(C1, Z1, Y1, PROOF1 => true; "valid")]
+    #[test_case(C2, Z2, Y2, PROOF2 => true; "valid in domain")]
+    #[test_case(C3, Z3, Y3, PROOF3 => true; "valid other")]
+    #[test_case(INFINITY, Z1, ZERO, INFINITY => true; "zero polynomial")]
+    #[test_case(C1, Z1, Y3, PROOF1 => false; "wrong value")]
+    #[test_case(C1, Z3, Y1, PROOF1 => false; "wrong point")]
+    #[test_case(C1, Z1, Y1, PROOF3 => false; "wrong proof")]
+    #[test_case(C3, Z1, Y1, PROOF1 => false; "wrong commitment")]
+    fn verify_kzg_proof_test(commitment: &str, z: &str, y: &str, proof: &str) -> bool {
+        let setup = TrustedSetup::read(SETUP).unwrap();
+        verify_kzg_proof(&setup, &hex(commitment), &hex(z), &hex(y), &hex(proof)).unwrap()
+    }
+
+    #[test_case(NOT_IN_SUBGROUP, Z1, Y1, PROOF1; "commitment not in subgroup")]
+    #[test_case(C1, Z1, Y1, NOT_IN_SUBGROUP; "proof not in subgroup")]
+    #[test_case(C1, GROUP_ORDER, Y1, PROOF1; "point not in field")]
+    #[test_case(C1, Z1, GROUP_ORDER, PROOF1; "value not in field")]
+    #[test_case(&C1[2..], Z1, Y1, PROOF1; "short commitment")]
+    #[test_case(C1, &Z1[2..], Y1, PROOF1; "short point")]
+    fn verify_kzg_proof_malformed_test(commitment: &str, z: &str, y: &str, proof: &str) {
+        let setup = TrustedSetup::read(SETUP).unwrap();
+        assert!(verify_kzg_proof(&setup, &hex(commitment), &hex(z), &hex(y), &hex(proof)).is_err());
+    }
+
+    #[test_case(&[(C1, Z1, Y1, PROOF1), (C2, Z2, Y2, PROOF2), (C3, Z3, Y3, PROOF3)] => true; "valid")]
+    #[test_case(&[(C1, Z1, Y1, PROOF1)] => true; "single")]
+    #[test_case(&[] => true; "empty")]
+    #[test_case(&[(C1, Z1, Y1, PROOF1), (C2, Z2, Y2, PROOF2), (C3, Z3, Y1, PROOF3)] => false; "wrong value")]
+    #[test_case(&[(C1, Z1, Y1, PROOF2), (C2, Z2, Y2, PROOF1)] => false; "swapped proofs")]
+    fn verify_kzg_proof_batch_test(openings: &[(&str, &str, &str, &str)]) -> bool {
+        let setup = TrustedSetup::read(SETUP).unwrap();
+
+        let commitments = openings.iter().map(|o| hex(o.0)).collect::<Vec<_>>();
+        let zs = openings.iter().map(|o| hex(o.1)).collect::<Vec<_>>();
+        let ys = openings.iter().map(|o| hex(o.2)).collect::<Vec<_>>();
+        let proofs = openings.iter().map(|o| hex(o.3)).collect::<Vec<_>>();
+
+        verify_kzg_proof_batch(&setup, &commitments, &zs, &ys, &proofs).unwrap()
+    }
+
+    #[test]
+    fn verify_kzg_proof_batch_malformed_test() {
+        let setup = TrustedSetup::read(SETUP).unwrap();
+        let (c, z, y, proof) = (vec![hex(C1)], vec![hex(Z1)], vec![hex(Y1)], vec![hex(PROOF1)]);
+
+        assert!(verify_kzg_proof_batch(&setup, &c, &z, &y, &[]).is_err());
+        assert!(verify_kzg_proof_batch(&setup, &c, &z, &[hex(GROUP_ORDER)], &proof).is_err());
+        assert!(verify_kzg_proof_batch(&setup, &[hex(NOT_IN_SUBGROUP)], &z, &y, &proof).is_err());
+    }
+
+    #[test]
+    fn trusted_setup_test() {
+        let setup = TrustedSetup::read(SETUP).unwrap();
+        assert_eq!((setup.g1_lagrange.len(), setup.g2_monomial.len()), (4, 2));
+
+        let lines = SETUP.lines().collect::<Vec<_>>();
+
+        // Truncated, with an extra point, with the G2 points swapped, with a wrong size
+        // and with the first G1 point changed to one out of the subgroup
+        assert!(TrustedSetup::read(&lines[..7].join("\n")).is_err());
+        assert!(TrustedSetup::read(&[SETUP, lines[2]].join("\n")).is_err());
+        assert!(TrustedSetup::read(&[&lines[..6], &[lines[7], lines[6]][..]].concat().join("\n")).is_err());
+        assert!(TrustedSetup::read(&SETUP.replacen("4\n", "5\n", 1)).is_err());
+        assert!(TrustedSetup::read(&SETUP.replacen("98", "80", 1)).is_err());
+    }
+}
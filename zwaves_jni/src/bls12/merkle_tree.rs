@@ -0,0 +1,99 @@
+//! `IncrementalMerkleTree` of `zwaves_primitives` over 32 bytes big-endian field elements.
+
+use std::io;
+
+use pairing::bls12_381::Bls12;
+
+use zwaves_primitives::merkle_tree::IncrementalMerkleTree;
+
+use super::shielded::{fr_to_bytes, read_fr, JUBJUB_PARAMS};
+
+pub struct MerkleTree(IncrementalMerkleTree<Bls12>);
+
+impl MerkleTree {
+    /// Empty tree, a `frontier` tree keeps only the proof of the next leaf.
+    pub fn new(depth: usize, frontier: bool) -> io::Result<Self> {
+        Ok(MerkleTree(if frontier {
+            IncrementalMerkleTree::new_frontier(depth, &JUBJUB_PARAMS)?
+        } else {
+            IncrementalMerkleTree::new(depth, &JUBJUB_PARAMS)?
+        }))
+    }
+
+    /// Frontier tree with `size` leaves, `sibling` is the proof of the leaf at the index `size`.
+    pub fn from_frontier(depth: usize, size: u64, sibling: &[Vec<u8>]) -> io::Result<Self> {
+        let sibling = sibling.iter().map(|e| read_fr(e)).collect::<io::Result<Vec<_>>>()?;
+
+        Ok(MerkleTree(IncrementalMerkleTree::from_frontier(
+            depth,
+            size,
+            &sibling,
+            &JUBJUB_PARAMS,
+        )?))
+    }
+
+    /// Appends the leaves and returns the index of the first one.
+    pub fn append(&mut self, leaves: &[Vec<u8>]) -> io::Result<u64> {
+        let leaves = leaves.iter().map(|e| read_fr(e)).collect::<io::Result<Vec<_>>>()?;
+
+        self.0.append_many(&leaves, &JUBJUB_PARAMS)
+    }
+
+    pub fn root(&self) -> Vec<u8> {
+        fr_to_bytes(&self.0.root())
+    }
+
+    pub fn proof(&self, index: u64) -> io::Result<Vec<Vec<u8>>> {
+        Ok(self.0.proof(index)?.iter().map(fr_to_bytes).collect())
+    }
+
+    pub fn size(&self) -> u64 {
+        self.0.size()
+    }
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use crate::bls12::shielded::merkle_root;
+    use zwaves_primitives::merkle_tree::MAX_DEPTH;
+
+    fn fr(n: u8) -> Vec<u8> {
+        let mut res = vec![0u8; 32];
+        res[31] = n;
+        res
+    }
+
+    #[test]
+    fn merkle_tree_test() {
+        let mut full = MerkleTree::new(8, false).unwrap();
+        let mut frontier = MerkleTree::new(8, true).unwrap();
+        let leaves = (1..12).map(fr).collect::<Vec<_>>();
+
+        assert_eq!(full.append(&leaves[..4]).unwrap(), 0);
+        assert_eq!(frontier.append(&leaves[..4]).unwrap(), 0);
+
+        let mut resumed = MerkleTree::from_frontier(8, 4, &frontier.proof(4).unwrap()).unwrap();
+        assert_eq!(full.append(&leaves[4..]).unwrap(), 4);
+        assert_eq!(resumed.append(&leaves[4..]).unwrap(), 4);
+
+        assert_eq!(full.size(), 11);
+        assert_eq!(full.root(), resumed.root());
+        assert_eq!(
+            merkle_root(&full.proof(6).unwrap(), 6, &leaves[6]).unwrap(),
+            full.root()
+        );
+        assert!(resumed.proof(6).is_err());
+    }
+
+    #[test]
+    fn wrong_input_test() {
+        assert!(MerkleTree::new(MAX_DEPTH + 1, false).is_err());
+        assert!(MerkleTree::from_frontier(2, 1, &[fr(1)]).is_err());
+
+        let mut tree = MerkleTree::new(1, false).unwrap();
+        assert!(tree.append(&[vec![0u8; 31]]).is_err());
+        assert!(tree.append(&[fr(1), fr(2), fr(3)]).is_err());
+        assert!(tree.proof(2).is_err());
+    }
+}
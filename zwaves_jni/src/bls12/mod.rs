@@ -1,13 +1,27 @@
 use std::io;
 
 use bellman::groth16::Proof;
-use pairing::bls12_381::{Bls12, Fr};
+use pairing::{
+    bls12_381::{Bls12, Fr, G1Affine, G2Affine},
+    CurveAffine, EncodedPoint, GroupDecodingError, PrimeField,
+};
+use rand::os::OsRng;
 
 use zwaves_primitives::{
-    serialization::read_fr_vec,
-    verifier::{verify_proof, TruncatedVerifyingKey},
+    serialization::{read_fr_repr_be, read_fr_vec},
+    verifier::{
+        prepare_verifying_key, verify_prepared_proof, verify_proof, verify_proofs_batch, PreparedVerifyingKey,
+        TruncatedVerifyingKey,
+    },
 };
 
+use crate::diagnostics::{Element, VerificationFailure};
+
+pub mod bls_sig;
+pub mod kzg;
+pub mod merkle_tree;
+pub mod shielded;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -39,6 +53,137 @@ pub fn groth16_verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> io::Result<u8>
         .unwrap_or(0))
 }
 
+/// Verifies all `(proofs[i], inputs[i])` pairs against one verifying key.
+///
+/// Returns `None` if every proof is valid, otherwise the index of the first invalid one.
+/// Malformed proofs and inputs are reported as invalid, a malformed vk is an error.
+pub fn groth16_verify_batch(vk: &[u8], proofs: &[Vec<u8>], inputs: &[Vec<u8>]) -> io::Result<Option<usize>> {
+    if proofs.len() != inputs.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong batch length"));
+    }
+
+    if (vk.len() % 48 != 0) || (vk.len() < 8 * 48) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = TruncatedVerifyingKey::<Bls12>::read(vk)?;
+    let inputs_len = vk.ic.len() - 1;
+
+    let mut batch = Vec::with_capacity(proofs.len());
+
+    for (i, (proof, inputs)) in proofs.iter().zip(inputs.iter()).enumerate() {
+        if (proof.len() != 192) || (inputs.len() != inputs_len * 32) {
+            return Ok(Some(i));
+        }
+
+        let proof = match Proof::<Bls12>::read(proof.as_slice()) {
+            Ok(proof) => proof,
+            _ => return Ok(Some(i)),
+        };
+
+        let inputs = match read_fr_vec::<Fr>(inputs) {
+            Ok(inputs) => inputs,
+            _ => return Ok(Some(i)),
+        };
+
+        batch.push((proof, inputs));
+    }
+
+    let mut rng = OsRng::new()?;
+
+    if verify_proofs_batch(&vk, &batch, &mut rng).unwrap_or(false) {
+        return Ok(None);
+    }
+
+    // The batch is invalid, look for the proof which broke it
+    Ok(batch
+        .iter()
+        .position(|(proof, inputs)| !verify_proof(&vk, proof, inputs.as_slice()).unwrap_or(false)))
+}
+
+/// Reads the vk and precomputes its part of the pairing check for `groth16_verify_prepared`.
+pub fn prepare_vk(vk: &[u8]) -> io::Result<PreparedVerifyingKey<Bls12>> {
+    if (vk.len() % 48 != 0) || (vk.len() < 8 * 48) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = TruncatedVerifyingKey::<Bls12>::read(vk)?;
+    Ok(prepare_verifying_key(&vk))
+}
+
+pub fn groth16_verify_prepared(pvk: &PreparedVerifyingKey<Bls12>, proof: &[u8], inputs: &[u8]) -> io::Result<u8> {
+    if (inputs.len() % 32 != 0) || ((inputs.len() / 32 + 1) != pvk.ic.len()) || (proof.len() != 192) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let proof = Proof::<Bls12>::read(proof)?;
+    let inputs = read_fr_vec::<Fr>(inputs)?;
+
+    Ok(verify_prepared_proof(pvk, &proof, inputs.as_slice())
+        .map(|r| r as u8)
+        .unwrap_or(0))
+}
+
+/// Same check as `groth16_verify`, but reports why and on which element the verification failed.
+pub fn verify_detailed(vk: &[u8], proof: &[u8], inputs: &[u8]) -> Result<(), VerificationFailure> {
+    if (vk.len() % 48 != 0) || (inputs.len() % 32 != 0) {
+        return Err(VerificationFailure::WrongBufferLength);
+    }
+
+    let inputs_len = inputs.len() / 32;
+
+    if ((vk.len() / 48) != (inputs_len + 8)) || (proof.len() != 192) {
+        return Err(VerificationFailure::WrongBufferLength);
+    }
+
+    let vk = TruncatedVerifyingKey::<Bls12> {
+        alpha_g1: decode_point::<G1Affine>(&vk[0..48], Element::Alpha)?,
+        beta_g2: decode_point::<G2Affine>(&vk[48..144], Element::Beta)?,
+        gamma_g2: decode_point::<G2Affine>(&vk[144..240], Element::Gamma)?,
+        delta_g2: decode_point::<G2Affine>(&vk[240..336], Element::Delta)?,
+        ic: vk[336..]
+            .chunks(48)
+            .enumerate()
+            .map(|(i, data)| decode_point::<G1Affine>(data, Element::Ic(i)))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let proof = Proof::<Bls12> {
+        a: decode_point::<G1Affine>(&proof[0..48], Element::A)?,
+        b: decode_point::<G2Affine>(&proof[48..144], Element::B)?,
+        c: decode_point::<G1Affine>(&proof[144..192], Element::C)?,
+    };
+
+    let inputs = inputs
+        .chunks(32)
+        .enumerate()
+        .map(|(i, data)| {
+            read_fr_repr_be::<Fr>(data)
+                .ok()
+                .and_then(|repr| Fr::from_repr(repr).ok())
+                .ok_or(VerificationFailure::InputNotInField(i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match verify_proof(&vk, &proof, inputs.as_slice()) {
+        Ok(true) => Ok(()),
+        _ => Err(VerificationFailure::PairingMismatch),
+    }
+}
+
+fn decode_point<G: CurveAffine>(data: &[u8], element: Element) -> Result<G, VerificationFailure> {
+    let mut repr = G::Compressed::empty();
+    repr.as_mut().copy_from_slice(data);
+
+    match repr.into_affine() {
+        Ok(p) if p.is_zero() => Err(VerificationFailure::PointAtInfinity(element)),
+        Ok(p) => Ok(p),
+        Err(GroupDecodingError::NotOnCurve) => Err(VerificationFailure::NotOnCurve(element)),
+        Err(GroupDecodingError::NotInSubgroup) => Err(VerificationFailure::NotInSubgroup(element)),
+        Err(_) => Err(VerificationFailure::InvalidEncoding(element)),
+    }
+}
+
 #[cfg(test)]
 mod local_tests {
     use super::*;
@@ -119,4 +264,47 @@ mod local_tests {
 
         return groth16_verify(&vk, &proof, &inputs).unwrap_or(0) != 0;
     }
+
+    #[test]
+    fn groth16_verify_prepared_test() {
+        let vk = decode("hwk883gUlTKCyXYA6XWZa8H9/xKIYZaJ0xEs0M5hQOMxiGpxocuX/8maSDmeCk3bo5ViaDBdO7ZBxAhLSe5k/5TFQyF5Lv7KN2tLKnwgoWMqB16OL8WdbePIwTCuPtJNAFKoTZylLDbSf02kckMcZQDPF9iGh+JC99Pio74vDpwTEjUx5tQ99gNQwxULtztsqDRsPnEvKvLmsxHt8LQVBkEBm2PBJFY+OXf1MNW021viDBpR10mX4WQ6zrsGL5L0GY4cwf4tlbh+Obit+LnN/SQTnREf8fPpdKZ1sa/ui3pGi8lMT6io4D7Ujlwx2RdCkBF+isfMf77HCEGsZANw0hSrO2FGg14Sl26xLAIohdaW8O7gEaag8JdVAZ3OVLd5Df1NkZBEr753Xb8WwaXsJjE7qxwINL1KdqA4+EiYW4edb7+a9bbBeOPtb67ZxmFqgyTNS/4obxahezNkjk00ytswsENg//Ee6dWBJZyLH+QGsaU2jO/W4WvRyZhmKKPdipOhiz4Rlrd2XYgsfHsfWf5v4GOTL+13ZB24dW1/m39n2woJ+v686fXbNW85XP/r").unwrap();
+        let proof = decode("lvQLU/KqgFhsLkt/5C/scqs7nWR+eYtyPdWiLVBux9GblT4AhHYMdCgwQfSJcudvsgV6fXoK+DUSRgJ++Nqt+Wvb7GlYlHpxCysQhz26TTu8Nyo7zpmVPH92+UYmbvbQCSvX2BhWtvkfHmqDVjmSIQ4RUMfeveA1KZbSf999NE4qKK8Do+8oXcmTM4LZVmh1rlyqznIdFXPN7x3pD4E0gb6/y69xtWMChv9654FMg05bAdueKt9uA4BEcAbpkdHF").unwrap();
+        let inputs = decode("LcMT3OOlkHLzJBKCKjjzzVMg+r+FVgd52LlhZPB4RFg=").unwrap();
+        let bad_inputs = decode("cmzVCcRVnckw3QUPhmG4Bkppeg4K50oDQwQ9EH+Fq1s=").unwrap();
+
+        let pvk = prepare_vk(&vk).unwrap();
+
+        assert_eq!(groth16_verify_prepared(&pvk, &proof, &inputs).unwrap(), 1);
+        assert_eq!(groth16_verify_prepared(&pvk, &proof, &bad_inputs).unwrap(), 0);
+        assert!(groth16_verify_prepared(&pvk, &proof, &[]).is_err());
+        assert!(prepare_vk(&vk[..48 * 7]).is_err());
+    }
+
+    #[test]
+    fn verify_detailed_test() {
+        let vk = decode("hwk883gUlTKCyXYA6XWZa8H9/xKIYZaJ0xEs0M5hQOMxiGpxocuX/8maSDmeCk3bo5ViaDBdO7ZBxAhLSe5k/5TFQyF5Lv7KN2tLKnwgoWMqB16OL8WdbePIwTCuPtJNAFKoTZylLDbSf02kckMcZQDPF9iGh+JC99Pio74vDpwTEjUx5tQ99gNQwxULtztsqDRsPnEvKvLmsxHt8LQVBkEBm2PBJFY+OXf1MNW021viDBpR10mX4WQ6zrsGL5L0GY4cwf4tlbh+Obit+LnN/SQTnREf8fPpdKZ1sa/ui3pGi8lMT6io4D7Ujlwx2RdCkBF+isfMf77HCEGsZANw0hSrO2FGg14Sl26xLAIohdaW8O7gEaag8JdVAZ3OVLd5Df1NkZBEr753Xb8WwaXsJjE7qxwINL1KdqA4+EiYW4edb7+a9bbBeOPtb67ZxmFqgyTNS/4obxahezNkjk00ytswsENg//Ee6dWBJZyLH+QGsaU2jO/W4WvRyZhmKKPdipOhiz4Rlrd2XYgsfHsfWf5v4GOTL+13ZB24dW1/m39n2woJ+v686fXbNW85XP/r").unwrap();
+        let mut proof = decode("lvQLU/KqgFhsLkt/5C/scqs7nWR+eYtyPdWiLVBux9GblT4AhHYMdCgwQfSJcudvsgV6fXoK+DUSRgJ++Nqt+Wvb7GlYlHpxCysQhz26TTu8Nyo7zpmVPH92+UYmbvbQCSvX2BhWtvkfHmqDVjmSIQ4RUMfeveA1KZbSf999NE4qKK8Do+8oXcmTM4LZVmh1rlyqznIdFXPN7x3pD4E0gb6/y69xtWMChv9654FMg05bAdueKt9uA4BEcAbpkdHF").unwrap();
+        let inputs = decode("LcMT3OOlkHLzJBKCKjjzzVMg+r+FVgd52LlhZPB4RFg=").unwrap();
+        let bad_inputs = decode("cmzVCcRVnckw3QUPhmG4Bkppeg4K50oDQwQ9EH+Fq1s=").unwrap();
+
+        assert_eq!(verify_detailed(&vk, &proof, &inputs), Ok(()));
+        assert_eq!(
+            verify_detailed(&vk, &proof, &bad_inputs),
+            Err(VerificationFailure::PairingMismatch)
+        );
+        assert_eq!(
+            verify_detailed(&vk[..48 * 8], &proof, &inputs),
+            Err(VerificationFailure::WrongBufferLength)
+        );
+        assert_eq!(
+            verify_detailed(&vk, &proof, &[0xff; 32]),
+            Err(VerificationFailure::InputNotInField(0))
+        );
+
+        proof[..48].copy_from_slice(G1Affine::zero().into_compressed().as_ref());
+        assert_eq!(
+            verify_detailed(&vk, &proof, &inputs),
+            Err(VerificationFailure::PointAtInfinity(Element::A))
+        );
+    }
 }
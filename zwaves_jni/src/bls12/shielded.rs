@@ -0,0 +1,287 @@
+//! Shielded pool primitives of `zwaves_primitives`: Pedersen Merkle hashing, note hashes, nullifiers,
+//! keys and note encryption. Field elements are 32 bytes big-endian and must be canonical, the same encoding as
+//! in the Node binding.
+
+use std::io;
+
+use lazy_static::lazy_static;
+use pairing::{
+    bls12_381::{Bls12, Fr},
+    PrimeField, PrimeFieldRepr,
+};
+use rand::os::OsRng;
+use sapling_crypto::{jubjub::JubjubBls12, pedersen_hash::Personalization};
+
+use zwaves_primitives::{
+    note_encryption, pedersen_hasher,
+    serialization::read_fr_repr_be,
+    transactions::{self, NoteData},
+};
+
+pub const FIELD_ELEMENT_LEN: usize = 32;
+
+/// Merkle tree levels are personalized with 6 bits and the leaf index is 63 bits in `pedersen_hasher`.
+pub const MAX_MERKLE_DEPTH: usize = 63;
+
+lazy_static! {
+    pub(crate) static ref JUBJUB_PARAMS: JubjubBls12 = JubjubBls12::new();
+}
+
+/// Hash of two children at the `level` of the Merkle tree, leaves are at the level `0`.
+pub fn compress(left: &[u8], right: &[u8], level: usize) -> io::Result<Vec<u8>> {
+    if level >= MAX_MERKLE_DEPTH {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong merkle tree level"));
+    }
+
+    let left = read_fr(left)?;
+    let right = read_fr(right)?;
+
+    Ok(fr_to_bytes(&pedersen_hasher::compress::<Bls12>(
+        &left,
+        &right,
+        Personalization::MerkleTree(level),
+        &JUBJUB_PARAMS,
+    )))
+}
+
+/// Root of the tree with the `leaf` at the `index` and its siblings from the bottom up.
+pub fn merkle_root(sibling: &[Vec<u8>], index: u64, leaf: &[u8]) -> io::Result<Vec<u8>> {
+    let sibling = read_proof(sibling, index)?;
+    let leaf = read_fr(leaf)?;
+
+    Ok(fr_to_bytes(&pedersen_hasher::merkle_root::<Bls12>(
+        &sibling,
+        index,
+        &leaf,
+        &JUBJUB_PARAMS,
+    )))
+}
+
+/// Appends the `leaves` to the tree at the first empty `index`, `sibling` is the proof of that index.
+/// Returns the new root and the proof of the index after the appended leaves.
+pub fn update_merkle_root_and_proof(
+    root: &[u8],
+    sibling: &[Vec<u8>],
+    index: u64,
+    leaves: &[Vec<u8>],
+) -> io::Result<(Vec<u8>, Vec<Vec<u8>>)> {
+    let root = read_fr(root)?;
+    let sibling = read_proof(sibling, index)?;
+    let leaves = leaves.iter().map(|e| read_fr(e)).collect::<io::Result<Vec<_>>>()?;
+
+    let defaults = pedersen_hasher::merkle_defaults::<Bls12>(sibling.len(), &JUBJUB_PARAMS);
+
+    let (root, proof) = pedersen_hasher::update_merkle_root_and_proof::<Bls12>(
+        &root,
+        &sibling,
+        index,
+        &leaves,
+        &defaults,
+        &JUBJUB_PARAMS,
+    )
+    .ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "root does not match the proof or the leaves do not fit the tree",
+        )
+    })?;
+
+    Ok((fr_to_bytes(&root), proof.iter().map(fr_to_bytes).collect()))
+}
+
+pub fn note_hash(
+    asset_id: &[u8],
+    amount: &[u8],
+    native_amount: &[u8],
+    txid: &[u8],
+    owner: &[u8],
+) -> io::Result<Vec<u8>> {
+    let note = read_note(asset_id, amount, native_amount, txid, owner)?;
+
+    Ok(fr_to_bytes(&transactions::note_hash(&note, &JUBJUB_PARAMS)))
+}
+
+pub fn nullifier(note_hash: &[u8], sk: &[u8]) -> io::Result<Vec<u8>> {
+    let note_hash = read_fr(note_hash)?;
+    let sk = read_fr(sk)?;
+
+    Ok(fr_to_bytes(&transactions::nullifier::<Bls12>(
+        &note_hash,
+        &sk,
+        &JUBJUB_PARAMS,
+    )))
+}
+
+pub fn pubkey(sk: &[u8]) -> io::Result<Vec<u8>> {
+    let sk = read_fr(sk)?;
+
+    Ok(fr_to_bytes(&transactions::pubkey::<Bls12>(&sk, &JUBJUB_PARAMS)))
+}
+
+/// Diffie-Hellman shared secret of the public key `pk` and the secret key `sk`.
+pub fn edh(pk: &[u8], sk: &[u8]) -> io::Result<Vec<u8>> {
+    let pk = read_fr(pk)?;
+    let sk = read_fr(sk)?;
+
+    let res = transactions::edh::<Bls12>(&pk, &sk, &JUBJUB_PARAMS)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "pk is not an elliptic curve point"))?;
+    Ok(fr_to_bytes(&res))
+}
+
+/// Encrypts the note to its owner with a random ephemeral key. The amounts and the asset id must fit 64 bits.
+pub fn encrypt_note(
+    asset_id: &[u8],
+    amount: &[u8],
+    native_amount: &[u8],
+    txid: &[u8],
+    owner: &[u8],
+) -> io::Result<Vec<u8>> {
+    let note = read_note(asset_id, amount, native_amount, txid, owner)?;
+    let mut rng = OsRng::new()?;
+
+    note_encryption::encrypt_note(&note, &mut rng, &JUBJUB_PARAMS)
+}
+
+/// Fields of the note in the order of `note_hash` if it is encrypted to the owner of `sk`, `None` otherwise.
+pub fn try_decrypt_note(data: &[u8], sk: &[u8]) -> io::Result<Option<Vec<Vec<u8>>>> {
+    let sk = read_fr(sk)?;
+
+    Ok(
+        note_encryption::try_decrypt_note::<Bls12>(data, &sk, &JUBJUB_PARAMS).map(|note| {
+            [note.asset_id, note.amount, note.native_amount, note.txid, note.owner]
+                .iter()
+                .map(fr_to_bytes)
+                .collect()
+        }),
+    )
+}
+
+fn read_note(
+    asset_id: &[u8],
+    amount: &[u8],
+    native_amount: &[u8],
+    txid: &[u8],
+    owner: &[u8],
+) -> io::Result<NoteData<Bls12>> {
+    Ok(NoteData {
+        asset_id: read_fr(asset_id)?,
+        amount: read_fr(amount)?,
+        native_amount: read_fr(native_amount)?,
+        txid: read_fr(txid)?,
+        owner: read_fr(owner)?,
+    })
+}
+
+pub(crate) fn read_fr(data: &[u8]) -> io::Result<Fr> {
+    if data.len() != FIELD_ELEMENT_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong field element length"));
+    }
+
+    let repr = read_fr_repr_be::<Fr>(data)?;
+    Fr::from_repr(repr).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "not a field element"))
+}
+
+fn read_proof(sibling: &[Vec<u8>], index: u64) -> io::Result<Vec<Fr>> {
+    if sibling.len() > MAX_MERKLE_DEPTH || index >> sibling.len() != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong merkle proof length"));
+    }
+
+    sibling.iter().map(|e| read_fr(e)).collect()
+}
+
+pub(crate) fn fr_to_bytes(fr: &Fr) -> Vec<u8> {
+    let mut res = vec![];
+    fr.into_repr().write_be(&mut res).unwrap();
+    res
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn fr(n: u8) -> Vec<u8> {
+        let mut res = vec![0u8; 32];
+        res[31] = n;
+        res
+    }
+
+    #[test]
+    fn merkle_root_test() {
+        let sibling = vec![fr(1), fr(2), fr(3)];
+
+        // index 0b101: the leaf is the right child at the levels 0 and 2
+        let level0 = compress(&sibling[0], &fr(7), 0).unwrap();
+        let level1 = compress(&level0, &sibling[1], 1).unwrap();
+        let level2 = compress(&sibling[2], &level1, 2).unwrap();
+
+        assert_eq!(merkle_root(&sibling, 5, &fr(7)).unwrap(), level2);
+        assert_ne!(merkle_root(&sibling, 4, &fr(7)).unwrap(), level2);
+    }
+
+    #[test]
+    fn update_merkle_root_and_proof_test() {
+        let depth = 8;
+        let defaults = pedersen_hasher::merkle_defaults::<Bls12>(depth, &JUBJUB_PARAMS)
+            .iter()
+            .map(fr_to_bytes)
+            .collect::<Vec<_>>();
+        let empty_root = merkle_root(&defaults, 0, &fr(0)).unwrap();
+
+        let leaves = (1..6).map(fr).collect::<Vec<_>>();
+        let (root0, proof0) = update_merkle_root_and_proof(&empty_root, &defaults, 0, &leaves[..2]).unwrap();
+        let (root1, proof1) = update_merkle_root_and_proof(&root0, &proof0, 2, &leaves[2..]).unwrap();
+        let (root2, proof2) = update_merkle_root_and_proof(&empty_root, &defaults, 0, &leaves).unwrap();
+
+        assert_eq!(root1, root2);
+        assert_eq!(proof1, proof2);
+        assert_eq!(merkle_root(&proof2, 5, &fr(0)).unwrap(), root2);
+
+        assert!(update_merkle_root_and_proof(&root0, &defaults, 2, &leaves).is_err());
+    }
+
+    #[test]
+    fn keys_test() {
+        let (sk_a, sk_b) = (fr(11), fr(12));
+        let (pk_a, pk_b) = (pubkey(&sk_a).unwrap(), pubkey(&sk_b).unwrap());
+
+        assert_eq!(edh(&pk_a, &sk_b).unwrap(), edh(&pk_b, &sk_a).unwrap());
+
+        let hash = note_hash(&fr(1), &fr(2), &fr(3), &fr(4), &pk_a).unwrap();
+        assert_ne!(hash, note_hash(&fr(1), &fr(3), &fr(3), &fr(4), &pk_a).unwrap());
+        assert_ne!(nullifier(&hash, &sk_a).unwrap(), nullifier(&hash, &sk_b).unwrap());
+    }
+
+    #[test]
+    fn note_encryption_test() {
+        let (sk_a, sk_b) = (fr(11), fr(12));
+        let pk_a = pubkey(&sk_a).unwrap();
+        let note = vec![fr(1), fr(2), fr(3), fr(4), pk_a.clone()];
+
+        let data = encrypt_note(&fr(1), &fr(2), &fr(3), &fr(4), &pk_a).unwrap();
+        assert_eq!(data.len(), note_encryption::NOTE_CIPHERTEXT_LEN);
+        assert_eq!(try_decrypt_note(&data, &sk_a).unwrap(), Some(note));
+        assert_eq!(try_decrypt_note(&data, &sk_b).unwrap(), None);
+        assert_eq!(try_decrypt_note(&data[1..], &sk_a).unwrap(), None);
+
+        let mut amount = vec![0u8; 32];
+        amount[23] = 1;
+        assert!(encrypt_note(&fr(1), &amount, &fr(3), &fr(4), &pk_a).is_err());
+    }
+
+    #[test_case(vec![0u8; 31] ; "short")]
+    #[test_case(vec![0u8; 33] ; "long")]
+    #[test_case(vec![0xffu8; 32] ; "out of range")]
+    fn wrong_field_element_test(e: Vec<u8>) {
+        assert!(pubkey(&e).is_err());
+        assert!(nullifier(&e, &fr(1)).is_err());
+        assert!(compress(&fr(1), &e, 0).is_err());
+    }
+
+    #[test]
+    fn wrong_merkle_input_test() {
+        assert!(compress(&fr(1), &fr(2), MAX_MERKLE_DEPTH).is_err());
+        assert!(merkle_root(&[fr(1)], 2, &fr(2)).is_err());
+        assert!(merkle_root(&vec![fr(1); MAX_MERKLE_DEPTH + 1], 0, &fr(2)).is_err());
+    }
+}
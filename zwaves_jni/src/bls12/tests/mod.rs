@@ -148,4 +148,59 @@ mod tests {
         assert!(result, "Proof is correct");
         Ok(())
     }
+
+    #[test]
+    pub fn test_groth16_verify_batch() -> std::io::Result<()> {
+        let rng = &mut OsRng::new().unwrap();
+        let jubjub_params = JubjubBls12::new();
+
+        let params = {
+            let c = PedersenDemo::<Bls12> {
+                params: Box::new(JubjubBls12::new()),
+                image: None,
+                data: vec![None; 2],
+                preimage: None,
+            };
+            generate_random_parameters(c, rng).unwrap()
+        };
+
+        let mut vk = vec![];
+        truncate_verifying_key(&params.vk).write(&mut vk)?;
+
+        let mut proofs = vec![];
+        let mut inputs = vec![];
+
+        for _ in 0..4 {
+            let preimage = rng.gen();
+            let image = pedersen_hasher::hash::<Bls12>(&preimage, &jubjub_params);
+
+            let x: Fr = rng.gen();
+            let mut neg_x = x;
+            neg_x.negate();
+
+            let c = PedersenDemo::<Bls12> {
+                params: Box::new(JubjubBls12::new()),
+                image: Some(image),
+                data: vec![Some(x), Some(neg_x)],
+                preimage: Some(preimage),
+            };
+            let proof = create_random_proof(c, &params, rng).unwrap();
+
+            let mut proof_b = vec![];
+            proof.write(&mut proof_b)?;
+            proofs.push(proof_b);
+
+            let mut inputs_b = vec![0u8; 32 * 3];
+            write_fr_iter([image, x, neg_x].iter(), &mut inputs_b)?;
+            inputs.push(inputs_b);
+        }
+
+        let result = crate::bls12::groth16_verify_batch(&vk, &proofs, &inputs)?;
+        assert_eq!(result, None, "All proofs are correct");
+
+        inputs.swap(1, 2);
+        let result = crate::bls12::groth16_verify_batch(&vk, &proofs, &inputs)?;
+        assert_eq!(result, Some(1), "Proof 1 is the first incorrect one");
+        Ok(())
+    }
 }
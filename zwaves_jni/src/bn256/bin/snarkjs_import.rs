@@ -0,0 +1,69 @@
+//! Converts snarkjs JSON files into the vk, proof and inputs arguments of Ride `groth16Verify`.
+//!
+//! Usage: `snarkjs_import [--bls12] <verification_key.json> <proof.json> <public.json>`
+
+extern crate zwaves_jni;
+
+use std::{env, fs, io, process};
+
+use zwaves_jni::{bls12, bn256};
+use zwaves_primitives::snarkjs::{public_signals_to_bytes, read_proof_bls12, read_public_signals_bls12, read_vk_bls12};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let (is_bls12, files) = match args.first().map(String::as_str) {
+        Some("--bls12") => (true, &args[1..]),
+        _ => (false, &args[..]),
+    };
+
+    if files.len() != 3 {
+        eprintln!("Usage: snarkjs_import [--bls12] <verification_key.json> <proof.json> <public.json>");
+        process::exit(1);
+    }
+
+    match convert(is_bls12, &files[0], &files[1], &files[2]) {
+        Ok((vk, proof, inputs, valid)) => {
+            for (name, data) in [("vk", vk), ("proof", proof), ("inputs", inputs)].iter() {
+                println!("{}: base64'{}'", name, base64::encode(data));
+                println!("{}: base58'{}'", name, bs58::encode(data).into_string());
+            }
+            println!("valid: {}", valid);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn convert(
+    is_bls12: bool,
+    vk_path: &str,
+    proof_path: &str,
+    public_path: &str,
+) -> io::Result<(Vec<u8>, Vec<u8>, Vec<u8>, bool)> {
+    let vk_json = fs::read_to_string(vk_path)?;
+    let proof_json = fs::read_to_string(proof_path)?;
+    let public_json = fs::read_to_string(public_path)?;
+
+    let mut vk = vec![];
+    let mut proof = vec![];
+    let inputs = public_signals_to_bytes(&public_json)?;
+
+    let valid = if is_bls12 {
+        read_vk_bls12(&vk_json)?.write(&mut vk)?;
+        read_proof_bls12(&proof_json)?.write(&mut proof)?;
+        read_public_signals_bls12(&public_json)?;
+
+        bls12::groth16_verify(&vk, &proof, &inputs)?
+    } else {
+        bn256::snarkjs::read_vk(&vk_json)?.write(&mut vk)?;
+        bn256::snarkjs::read_proof(&proof_json)?.write(&mut proof)?;
+        bn256::snarkjs::read_public_signals(&public_json)?;
+
+        bn256::groth16_verify(&vk, &proof, &inputs)?
+    };
+
+    Ok((vk, proof, inputs, valid != 0))
+}
@@ -1,29 +1,49 @@
 use std::io;
 
-use pairing_ce::bn256::{Bn256, Fr};
-use serialization::read_fr_vec;
-use verifier::{verify_proof, Proof, TruncatedVerifyingKey};
+use ff::PrimeField;
+use pairing_ce::{
+    bn256::{Bn256, Fr, G1Affine, G2Affine},
+    CurveAffine, EncodedPoint, GroupDecodingError,
+};
+use rand::os::OsRng;
+use serialization::{read_fr_repr_be, read_fr_vec, Encoding};
+use verifier::{
+    prepare_verifying_key, verify_prepared_proof, verify_proof, verify_proofs_batch, PreparedVerifyingKey, Proof,
+    TruncatedVerifyingKey,
+};
 
+use crate::diagnostics::{Element, VerificationFailure};
+
+pub mod ops;
+pub mod plonk;
 pub mod serialization;
+pub mod snarkjs;
 pub mod verifier;
 
 pub fn groth16_verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> io::Result<u8> {
+    groth16_verify_encoded(vk, proof, inputs, Encoding::Compressed)
+}
+
+/// Same as `groth16_verify` for vk and proof points in the given encoding.
+pub fn groth16_verify_encoded(vk: &[u8], proof: &[u8], inputs: &[u8], encoding: Encoding) -> io::Result<u8> {
+    let g1_len = encoding.g1_len();
+
     let buff_vk_len = vk.len();
     let buff_proof_len = proof.len();
     let buff_inputs_len = inputs.len();
 
-    if (buff_vk_len % 32 != 0) || (buff_inputs_len % 32 != 0) {
+    if (buff_vk_len % g1_len != 0) || (buff_inputs_len % 32 != 0) {
         return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
     }
 
     let inputs_len = buff_inputs_len / 32;
 
-    if ((buff_vk_len / 32) != (inputs_len + 8)) || (buff_proof_len != 128) {
+    if ((buff_vk_len / g1_len) != (inputs_len + 8)) || (buff_proof_len != 4 * g1_len) {
         return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
     }
 
-    let vk = TruncatedVerifyingKey::<Bn256>::read(vk)?;
-    let proof = Proof::<Bn256>::read(proof)?;
+    let vk = TruncatedVerifyingKey::<Bn256>::read_encoded(vk, encoding)?;
+    let proof = Proof::<Bn256>::read_encoded(proof, encoding)?;
     let inputs = read_fr_vec::<Fr>(inputs)?;
 
     if (inputs.len() != inputs_len) || (vk.ic.len() != (inputs_len + 1)) {
@@ -35,11 +55,198 @@ pub fn groth16_verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> io::Result<u8>
         .unwrap_or(0))
 }
 
+/// Re-encodes a proof, e.g. an Ethereum proof into the form accepted by `groth16_verify`.
+pub fn convert_proof(proof: &[u8], from: Encoding, to: Encoding) -> io::Result<Vec<u8>> {
+    if proof.len() != 4 * from.g1_len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let proof = Proof::<Bn256>::read_encoded(proof, from)?;
+
+    let mut res = vec![];
+    proof.write_encoded(&mut res, to)?;
+    Ok(res)
+}
+
+/// Re-encodes a vk, e.g. an Ethereum vk into the form accepted by `groth16_verify`.
+pub fn convert_vk(vk: &[u8], from: Encoding, to: Encoding) -> io::Result<Vec<u8>> {
+    if (vk.len() % from.g1_len() != 0) || (vk.len() < 8 * from.g1_len()) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = TruncatedVerifyingKey::<Bn256>::read_encoded(vk, from)?;
+
+    let mut res = vec![];
+    vk.write_encoded(&mut res, to)?;
+    Ok(res)
+}
+
+/// Verifies all `(proofs[i], inputs[i])` pairs against one verifying key.
+///
+/// Returns `None` if every proof is valid, otherwise the index of the first invalid one.
+/// Malformed proofs and inputs are reported as invalid, a malformed vk is an error.
+pub fn groth16_verify_batch(vk: &[u8], proofs: &[Vec<u8>], inputs: &[Vec<u8>]) -> io::Result<Option<usize>> {
+    if proofs.len() != inputs.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong batch length"));
+    }
+
+    if (vk.len() % 32 != 0) || (vk.len() < 8 * 32) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = TruncatedVerifyingKey::<Bn256>::read(vk)?;
+    let inputs_len = vk.ic.len() - 1;
+
+    let mut batch = Vec::with_capacity(proofs.len());
+
+    for (i, (proof, inputs)) in proofs.iter().zip(inputs.iter()).enumerate() {
+        if (proof.len() != 128) || (inputs.len() != inputs_len * 32) {
+            return Ok(Some(i));
+        }
+
+        let proof = match Proof::<Bn256>::read(proof.as_slice()) {
+            Ok(proof) => proof,
+            _ => return Ok(Some(i)),
+        };
+
+        let inputs = match read_fr_vec::<Fr>(inputs) {
+            Ok(inputs) => inputs,
+            _ => return Ok(Some(i)),
+        };
+
+        batch.push((proof, inputs));
+    }
+
+    let mut rng = OsRng::new()?;
+
+    if verify_proofs_batch(&vk, &batch, &mut rng).unwrap_or(false) {
+        return Ok(None);
+    }
+
+    // The batch is invalid, look for the proof which broke it
+    Ok(batch
+        .iter()
+        .position(|(proof, inputs)| !verify_proof(&vk, proof, inputs.as_slice()).unwrap_or(false)))
+}
+
+/// Strictly checks the vk: every point must be on the curve and in the prime order subgroup,
+/// and there must be no trailing bytes. `groth16_verify` itself does not check G2 subgroups of the vk,
+/// so this is meant to be called once when the vk is published.
+pub fn validate_vk(vk: &[u8]) -> io::Result<()> {
+    if (vk.len() % 32 != 0) || (vk.len() < 8 * 32) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    TruncatedVerifyingKey::<Bn256>::read_checked(vk)?;
+    Ok(())
+}
+
+/// Reads the vk and precomputes its part of the pairing check for `groth16_verify_prepared`.
+pub fn prepare_vk(vk: &[u8]) -> io::Result<PreparedVerifyingKey<Bn256>> {
+    if (vk.len() % 32 != 0) || (vk.len() < 8 * 32) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = TruncatedVerifyingKey::<Bn256>::read(vk)?;
+    Ok(prepare_verifying_key(&vk))
+}
+
+pub fn groth16_verify_prepared(pvk: &PreparedVerifyingKey<Bn256>, proof: &[u8], inputs: &[u8]) -> io::Result<u8> {
+    if (inputs.len() % 32 != 0) || ((inputs.len() / 32 + 1) != pvk.ic.len()) || (proof.len() != 128) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let proof = Proof::<Bn256>::read(proof)?;
+    let inputs = read_fr_vec::<Fr>(inputs)?;
+
+    Ok(verify_prepared_proof(pvk, &proof, inputs.as_slice())
+        .map(|r| r as u8)
+        .unwrap_or(0))
+}
+
+/// Same check as `groth16_verify`, but reports why and on which element the verification failed.
+pub fn verify_detailed(vk: &[u8], proof: &[u8], inputs: &[u8]) -> Result<(), VerificationFailure> {
+    if (vk.len() % 32 != 0) || (inputs.len() % 32 != 0) {
+        return Err(VerificationFailure::WrongBufferLength);
+    }
+
+    let inputs_len = inputs.len() / 32;
+
+    if ((vk.len() / 32) != (inputs_len + 8)) || (proof.len() != 128) {
+        return Err(VerificationFailure::WrongBufferLength);
+    }
+
+    // The vk is read unchecked by `TruncatedVerifyingKey::read`, the proof is fully checked
+    let vk = TruncatedVerifyingKey::<Bn256> {
+        alpha_g1: decode_point::<G1Affine>(&vk[0..32], Element::Alpha, false)?,
+        beta_g2: decode_point::<G2Affine>(&vk[32..96], Element::Beta, false)?,
+        gamma_g2: decode_point::<G2Affine>(&vk[96..160], Element::Gamma, false)?,
+        delta_g2: decode_point::<G2Affine>(&vk[160..224], Element::Delta, false)?,
+        ic: vk[224..]
+            .chunks(32)
+            .enumerate()
+            .map(|(i, data)| decode_point::<G1Affine>(data, Element::Ic(i), false))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let proof = Proof::<Bn256> {
+        a: decode_point::<G1Affine>(&proof[0..32], Element::A, true)?,
+        b: decode_point::<G2Affine>(&proof[32..96], Element::B, true)?,
+        c: decode_point::<G1Affine>(&proof[96..128], Element::C, true)?,
+    };
+
+    if !proof.b.mul(Fr::char()).is_zero() {
+        return Err(VerificationFailure::NotInSubgroup(Element::B));
+    }
+
+    let inputs = inputs
+        .chunks(32)
+        .enumerate()
+        .map(|(i, data)| {
+            read_fr_repr_be::<Fr>(data)
+                .ok()
+                .and_then(|repr| Fr::from_repr(repr).ok())
+                .ok_or(VerificationFailure::InputNotInField(i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match verify_proof(&vk, &proof, inputs.as_slice()) {
+        Ok(true) => Ok(()),
+        _ => Err(VerificationFailure::PairingMismatch),
+    }
+}
+
+fn decode_point<G: CurveAffine>(data: &[u8], element: Element, checked: bool) -> Result<G, VerificationFailure> {
+    let mut repr = G::Compressed::empty();
+    repr.as_mut().copy_from_slice(data);
+
+    let point = if checked {
+        repr.into_affine()
+    } else {
+        repr.into_affine_unchecked()
+    };
+
+    match point {
+        Ok(p) if p.is_zero() => Err(VerificationFailure::PointAtInfinity(element)),
+        Ok(p) => Ok(p),
+        Err(GroupDecodingError::NotOnCurve) => Err(VerificationFailure::NotOnCurve(element)),
+        Err(GroupDecodingError::NotInSubgroup) => Err(VerificationFailure::NotInSubgroup(element)),
+        Err(_) => Err(VerificationFailure::InvalidEncoding(element)),
+    }
+}
+
 #[cfg(test)]
 mod local_tests {
     use base64::decode;
+    use ff::Field;
+    use pairing_ce::{
+        bn256::{G2Affine, G1},
+        CurveAffine, CurveProjective,
+    };
+    use rand::{Rand, SeedableRng, XorShiftRng};
 
     use super::*;
+    use serialization::{read_g1_eth, read_g2_eth, write_fr_iter, write_g1_eth, write_g2_eth};
     use test_case::test_case;
 
     #[test_case(
@@ -79,6 +286,7 @@ mod local_tests {
 
         let res = groth16_verify(&vk, &proof, &inputs).unwrap_or(0) != 0;
         assert!(res, "groth16_verify should return true");
+        assert!(validate_vk(&vk).is_ok(), "validate_vk should accept the vk");
     }
 
     #[test_case(
@@ -112,4 +320,248 @@ mod local_tests {
         let res = groth16_verify(&vk, &proof, &inputs).unwrap_or(0) != 0;
         assert!(!res, "groth16_verify should return false");
     }
+
+    fn random_batch(n: usize, ninputs: usize) -> (Vec<u8>, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let g2_gen = G2Affine::one();
+        let b_2 = Fr::rand(&mut rng);
+        let b_3 = Fr::rand(&mut rng);
+
+        let vk = TruncatedVerifyingKey::<Bn256> {
+            alpha_g1: G1::rand(&mut rng).into_affine(),
+            beta_g2: g2_gen.mul(b_3).into_affine(),
+            gamma_g2: g2_gen,
+            delta_g2: g2_gen.mul(b_2).into_affine(),
+            ic: (0..ninputs + 1).map(|_| G1::rand(&mut rng).into_affine()).collect(),
+        };
+        let mut vk_buff = vec![];
+        vk.write(&mut vk_buff).unwrap();
+
+        let (proofs, inputs) = (0..n)
+            .map(|_| {
+                let inputs = (0..ninputs).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+                let mut inputs_buff = vec![0u8; 32 * ninputs];
+                write_fr_iter(inputs.iter(), &mut inputs_buff).unwrap();
+
+                let mut x_sum = vk.ic[0].into_projective();
+                for i in 1..ninputs + 1 {
+                    x_sum.add_assign(&vk.ic[i].mul(inputs[i - 1]));
+                }
+
+                // A * B = alpha * beta + x_sum * gamma + C * delta holds for
+                // A = (b_3 * alpha + x_sum + b_2 * C) / b_4, B = b_4 * gamma
+                let b_4 = Fr::rand(&mut rng);
+                let c = G1::rand(&mut rng).into_affine();
+
+                let mut a = vk.alpha_g1.mul(b_3);
+                a.add_assign(&x_sum);
+                a.add_assign(&c.mul(b_2));
+                a.mul_assign(b_4.inverse().unwrap());
+
+                let proof = Proof::<Bn256> {
+                    a: a.into_affine(),
+                    b: g2_gen.mul(b_4).into_affine(),
+                    c,
+                };
+                let mut proof_buff = vec![];
+                proof.write(&mut proof_buff).unwrap();
+
+                (proof_buff, inputs_buff)
+            })
+            .unzip();
+
+        (vk_buff, proofs, inputs)
+    }
+
+    #[test_case(1, 0)]
+    #[test_case(2, 1)]
+    #[test_case(16, 3)]
+    fn groth16_verify_batch_ok_test(n: usize, ninputs: usize) {
+        let (vk, proofs, inputs) = random_batch(n, ninputs);
+
+        for (proof, inputs) in proofs.iter().zip(inputs.iter()) {
+            assert!(
+                groth16_verify(&vk, proof, inputs).unwrap_or(0) != 0,
+                "groth16_verify should return true"
+            );
+        }
+
+        let res = groth16_verify_batch(&vk, &proofs, &inputs).unwrap();
+        assert_eq!(res, None, "groth16_verify_batch should accept the batch");
+    }
+
+    #[test_case(16, 3, 0)]
+    #[test_case(16, 3, 7)]
+    #[test_case(16, 3, 15)]
+    fn groth16_verify_batch_fail_test(n: usize, ninputs: usize, bad: usize) {
+        let (vk, proofs, mut inputs) = random_batch(n, ninputs);
+        inputs[bad] = inputs[(bad + 1) % n].clone();
+
+        let res = groth16_verify_batch(&vk, &proofs, &inputs).unwrap();
+        assert_eq!(res, Some(bad), "groth16_verify_batch should find the invalid proof");
+    }
+
+    #[test]
+    fn groth16_verify_batch_malformed_test() {
+        let (vk, mut proofs, inputs) = random_batch(4, 1);
+        proofs[2].truncate(64);
+
+        assert_eq!(groth16_verify_batch(&vk, &proofs, &inputs).unwrap(), Some(2));
+        assert!(groth16_verify_batch(&vk, &proofs, &inputs[..3]).is_err());
+        assert!(groth16_verify_batch(&vk[..224], &proofs, &inputs).is_err());
+    }
+
+    #[test_case(4, 1)]
+    #[test_case(4, 3)]
+    fn groth16_verify_prepared_test(n: usize, ninputs: usize) {
+        let (vk, proofs, inputs) = random_batch(n, ninputs);
+        let pvk = prepare_vk(&vk).unwrap();
+
+        for i in 0..n {
+            assert_eq!(groth16_verify_prepared(&pvk, &proofs[i], &inputs[i]).unwrap(), 1);
+            assert_eq!(
+                groth16_verify_prepared(&pvk, &proofs[i], &inputs[(i + 1) % n]).unwrap(),
+                0
+            );
+        }
+
+        assert!(groth16_verify_prepared(&pvk, &proofs[0], &inputs[0][32..]).is_err());
+        assert!(prepare_vk(&vk[..224]).is_err());
+    }
+
+    #[test]
+    fn validate_vk_test() {
+        let (vk, _, _) = random_batch(0, 2);
+        assert!(validate_vk(&vk).is_ok());
+
+        let mut trailing = vk.clone();
+        trailing.extend_from_slice(&[0; 5]);
+        assert!(TruncatedVerifyingKey::<Bn256>::read(trailing.as_slice()).is_ok());
+        assert!(
+            TruncatedVerifyingKey::<Bn256>::read_checked(trailing.as_slice()).is_err(),
+            "read_checked should reject trailing bytes"
+        );
+        assert!(validate_vk(&trailing).is_err());
+        assert!(validate_vk(&vk[..vk.len() - 32]).is_ok());
+        assert!(validate_vk(&[1; 256]).is_err());
+
+        // A decompressed G2 point is on the curve, but almost surely outside of the subgroup
+        let mut g2_repr = <G2Affine as CurveAffine>::Compressed::empty();
+        let mut x = 1;
+        g2_repr.as_mut()[63] = x;
+        while g2_repr.into_affine_unchecked().is_err() {
+            x += 1;
+            g2_repr.as_mut()[63] = x;
+        }
+
+        let mut bad_beta = vk.clone();
+        bad_beta[32..96].copy_from_slice(g2_repr.as_ref());
+        assert!(TruncatedVerifyingKey::<Bn256>::read(bad_beta.as_slice()).is_ok());
+        assert!(
+            validate_vk(&bad_beta).is_err(),
+            "validate_vk should reject beta out of the subgroup"
+        );
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn eth_encoding_generators_test() {
+        let mut g1_expected = vec![0; 64];
+        g1_expected[31] = 1;
+        g1_expected[63] = 2;
+
+        let mut g1 = vec![];
+        write_g1_eth(&G1Affine::one(), &mut g1).unwrap();
+        assert_eq!(g1, g1_expected);
+        assert_eq!(read_g1_eth(g1.as_slice()).unwrap(), G1Affine::one());
+
+        // G2 generator from EIP-197
+        let g2_expected = hex(concat!(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        ));
+
+        let mut g2 = vec![];
+        write_g2_eth(&G2Affine::one(), &mut g2).unwrap();
+        assert_eq!(g2, g2_expected);
+        assert_eq!(read_g2_eth(g2.as_slice()).unwrap(), G2Affine::one());
+
+        assert!(read_g1_eth(&[0; 64][..]).unwrap().is_zero());
+        assert!(read_g2_eth(&[0; 128][..]).unwrap().is_zero());
+
+        // (1, 3) is not on the curve
+        g1_expected[63] = 3;
+        assert!(read_g1_eth(g1_expected.as_slice()).is_err());
+    }
+
+    #[test_case(1, 0)]
+    #[test_case(2, 3)]
+    fn eth_encoding_roundtrip_test(n: usize, ninputs: usize) {
+        let (vk, proofs, inputs) = random_batch(n, ninputs);
+
+        let vk_eth = convert_vk(&vk, Encoding::Compressed, Encoding::Ethereum).unwrap();
+        assert_eq!(vk_eth.len(), 2 * vk.len());
+        assert_eq!(
+            convert_vk(&vk_eth, Encoding::Ethereum, Encoding::Compressed).unwrap(),
+            vk
+        );
+
+        for (proof, inputs) in proofs.iter().zip(inputs.iter()) {
+            let proof_eth = convert_proof(proof, Encoding::Compressed, Encoding::Ethereum).unwrap();
+            assert_eq!(proof_eth.len(), 256);
+            assert_eq!(
+                convert_proof(&proof_eth, Encoding::Ethereum, Encoding::Compressed).unwrap(),
+                *proof
+            );
+
+            assert_eq!(
+                groth16_verify_encoded(&vk_eth, &proof_eth, inputs, Encoding::Ethereum).unwrap(),
+                1
+            );
+            assert!(groth16_verify(&vk_eth, &proof_eth, inputs).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_detailed_test() {
+        let (mut vk, mut proofs, mut inputs) = random_batch(2, 3);
+        let g1_zero = G1Affine::zero().into_compressed();
+
+        assert_eq!(verify_detailed(&vk, &proofs[0], &inputs[0]), Ok(()));
+        assert_eq!(
+            verify_detailed(&vk, &proofs[0], &inputs[1]),
+            Err(VerificationFailure::PairingMismatch)
+        );
+        assert_eq!(
+            verify_detailed(&vk, &proofs[0][..96], &inputs[0]),
+            Err(VerificationFailure::WrongBufferLength)
+        );
+
+        inputs[0][32..64].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            verify_detailed(&vk, &proofs[0], &inputs[0]),
+            Err(VerificationFailure::InputNotInField(1))
+        );
+
+        proofs[1][96..128].copy_from_slice(g1_zero.as_ref());
+        assert_eq!(
+            verify_detailed(&vk, &proofs[1], &inputs[1]),
+            Err(VerificationFailure::PointAtInfinity(Element::C))
+        );
+
+        vk[224 + 64..224 + 96].copy_from_slice(g1_zero.as_ref());
+        assert_eq!(
+            verify_detailed(&vk, &proofs[1], &inputs[1]),
+            Err(VerificationFailure::PointAtInfinity(Element::Ic(2)))
+        );
+    }
 }
@@ -0,0 +1,172 @@
+//! BN256 curve operations with the semantics of the Ethereum precompiles from EIP-196 and EIP-197.
+//!
+//! Points are in `Encoding::Ethereum`. As in the precompiles, `g1_add` and `g1_mul` inputs are
+//! padded with zeros or truncated to their fixed length, while `pairing_check` requires
+//! the exact length. Invalid points are errors, never silently mapped to infinity.
+
+use std::io;
+
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{
+    bn256::{Bn256, Fq12, Fr, G1Affine},
+    CurveAffine, CurveProjective, Engine,
+};
+
+use super::serialization::{read_g1_eth, read_g2_eth, write_g1_eth};
+
+pub const G1_ADD_INPUT_LEN: usize = 128;
+pub const G1_MUL_INPUT_LEN: usize = 96;
+pub const PAIRING_PAIR_LEN: usize = 192;
+
+fn padded<'a>(input: &[u8], buf: &'a mut [u8]) -> &'a [u8] {
+    let len = input.len().min(buf.len());
+    buf[..len].copy_from_slice(&input[..len]);
+    buf
+}
+
+fn write_result(p: &G1Affine) -> io::Result<[u8; 64]> {
+    let mut res = [0; 64];
+    write_g1_eth(p, &mut res[..])?;
+    Ok(res)
+}
+
+/// `ECADD` precompile: the sum of two G1 points.
+pub fn g1_add(input: &[u8]) -> io::Result<[u8; 64]> {
+    let mut buf = [0; G1_ADD_INPUT_LEN];
+    let input = padded(input, &mut buf);
+
+    let a = read_g1_eth(&input[..64])?;
+    let b = read_g1_eth(&input[64..])?;
+
+    let mut sum = a.into_projective();
+    sum.add_assign_mixed(&b);
+
+    write_result(&sum.into_affine())
+}
+
+/// `ECMUL` precompile: a G1 point multiplied by a 256-bit big-endian scalar.
+/// The scalar is not required to be less than the group order.
+pub fn g1_mul(input: &[u8]) -> io::Result<[u8; 64]> {
+    let mut buf = [0; G1_MUL_INPUT_LEN];
+    let input = padded(input, &mut buf);
+
+    let p = read_g1_eth(&input[..64])?;
+
+    let mut scalar = Fr::zero().into_repr();
+    scalar.read_be(&input[64..])?;
+
+    write_result(&p.mul(scalar).into_affine())
+}
+
+/// `ECPAIRING` precompile: checks that the product of pairings of all `(G1, G2)` pairs is one.
+/// An empty input is a valid check, which succeeds.
+pub fn pairing_check(input: &[u8]) -> io::Result<bool> {
+    if input.len() % PAIRING_PAIR_LEN != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let mut pairs = vec![];
+
+    for pair in input.chunks(PAIRING_PAIR_LEN) {
+        let a = read_g1_eth(&pair[..64])?;
+        let b = read_g2_eth(&pair[64..])?;
+
+        if !b.mul(Fr::char()).is_zero() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "point not in subgroup"));
+        }
+
+        // Pairings with the point at infinity are one and do not change the product
+        if !a.is_zero() && !b.is_zero() {
+            pairs.push((a.prepare(), b.prepare()));
+        }
+    }
+
+    let refs = pairs.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>();
+
+    Ok(Bn256::final_exponentiation(&Bn256::miller_loop(&refs)).unwrap() == Fq12::one())
+}
+
+#[cfg(test)]
+mod local_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const G1: &str = concat!(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+    );
+    const G1_NEG: &str = concat!(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45",
+    );
+    const G1_DOUBLE: &str = concat!(
+        "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+        "15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+    );
+    const G2: &str = concat!(
+        "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+        "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+        "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+        "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+    );
+    const ZERO: &str = "";
+    const INFINITY: &str = concat!(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    );
+    const GROUP_ORDER: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    const FIELD_MODULUS: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+
+    #[test_case(&[G1, G1], G1_DOUBLE; "double")]
+    #[test_case(&[G1, G1_NEG], ZERO; "inverse")]
+    #[test_case(&[G1], G1; "padded with infinity")]
+    #[test_case(&[G1, INFINITY, G1_DOUBLE], G1; "extra bytes are ignored")]
+    #[test_case(&[], ZERO; "empty")]
+    fn g1_add_test(input: &[&str], expected: &str) {
+        let res = g1_add(&hex(&input.concat())).unwrap();
+        assert_eq!(res.to_vec(), hex(&format!("{:0<128}", expected)));
+    }
+
+    #[test_case(G1, "02", G1_DOUBLE; "by two")]
+    #[test_case(G1, GROUP_ORDER, ZERO; "by group order")]
+    #[test_case(G1, "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000003", G1_DOUBLE; "by group order plus two")]
+    #[test_case(G1, "", ZERO; "by zero")]
+    fn g1_mul_test(point: &str, scalar: &str, expected: &str) {
+        let input = format!("{}{:0>64}", point, scalar);
+        let res = g1_mul(&hex(&input)).unwrap();
+        assert_eq!(res.to_vec(), hex(&format!("{:0<128}", expected)));
+    }
+
+    #[test]
+    fn invalid_point_test() {
+        let not_on_curve = format!("{:0>64}{:0>64}", "1", "3");
+        let not_in_field = format!("{}{:0>64}", FIELD_MODULUS, "2");
+
+        assert!(g1_add(&hex(&[G1, &not_on_curve].concat())).is_err());
+        assert!(g1_add(&hex(&not_in_field)).is_err());
+        assert!(g1_mul(&hex(&not_on_curve)).is_err());
+    }
+
+    #[test_case(&[], true; "empty")]
+    #[test_case(&[G1, G2, G1_NEG, G2], true; "inverse")]
+    #[test_case(&[G1, G2, G1, G2], false; "square")]
+    #[test_case(&[G1, G2], false; "single")]
+    #[test_case(&[G1_DOUBLE, G2, G1_NEG, G2, G1_NEG, G2], true; "three pairs")]
+    fn pairing_check_test(input: &[&str], expected: bool) {
+        assert_eq!(pairing_check(&hex(&input.concat())).unwrap(), expected);
+    }
+
+    #[test]
+    fn pairing_check_infinity_test() {
+        assert!(pairing_check(&hex(&[INFINITY, G2].concat())).unwrap());
+        assert!(pairing_check(&hex(&[G1, INFINITY, INFINITY].concat())).unwrap());
+        assert!(pairing_check(&hex(&[G1, G2].concat())[..191]).is_err());
+    }
+}
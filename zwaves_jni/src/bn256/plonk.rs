@@ -0,0 +1,558 @@
+//! PLONK verifier over BN256 with KZG commitments, compatible with snarkjs.
+//!
+//! The checks follow `plonk_verify.js` of snarkjs 0.7 and the solidity verifier it exports, so proofs
+//! produced by `snarkjs plonk prove` are accepted as is. Challenges are Keccak-256 of the transcript,
+//! points in the transcript and in the byte layouts below are in `Encoding::Ethereum`, scalars are
+//! 32 bytes big-endian.
+//!
+//! The proof layout is the calldata of the solidity verifier:
+//! `A, B, C, Z, T1, T2, T3, Wxi, Wxiw, eval_a, eval_b, eval_c, eval_s1, eval_s2, eval_zw`.
+//!
+//! The vk layout has the fields of `verification_key.json` in the order of the solidity verifier constants:
+//! `power, nPublic, k1, k2, w` as 32 bytes words, then `Qm, Ql, Qr, Qo, Qc, S1, S2, S3` and `X_2`.
+
+use std::{
+    io,
+    io::{Read, Write},
+};
+
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{
+    bn256::{Bn256, Fq12, Fr, FrRepr, G1Affine, G2Affine},
+    CurveAffine, CurveProjective, Engine,
+};
+
+use super::serialization::{read_fr_vec, read_g1_eth, read_g2_eth, write_g1_eth, write_g2_eth};
+
+pub const VK_LEN: usize = 5 * 32 + 8 * 64 + 128;
+pub const PROOF_LEN: usize = 9 * 64 + 6 * 32;
+
+/// Two-adicity of the BN256 scalar field, the largest supported domain is `2^28`.
+const MAX_POWER: u32 = 28;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyingKey {
+    /// The evaluation domain has `2^power` elements.
+    pub power: u32,
+    pub n_public: usize,
+    pub k1: Fr,
+    pub k2: Fr,
+    /// Generator of the evaluation domain.
+    pub w: Fr,
+    pub qm: G1Affine,
+    pub ql: G1Affine,
+    pub qr: G1Affine,
+    pub qo: G1Affine,
+    pub qc: G1Affine,
+    pub s1: G1Affine,
+    pub s2: G1Affine,
+    pub s3: G1Affine,
+    /// `[tau]_2` of the powers of tau.
+    pub x_2: G2Affine,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G1Affine,
+    pub c: G1Affine,
+    pub z: G1Affine,
+    pub t1: G1Affine,
+    pub t2: G1Affine,
+    pub t3: G1Affine,
+    pub wxi: G1Affine,
+    pub wxiw: G1Affine,
+    pub eval_a: Fr,
+    pub eval_b: Fr,
+    pub eval_c: Fr,
+    pub eval_s1: Fr,
+    pub eval_s2: Fr,
+    pub eval_zw: Fr,
+}
+
+fn read_fr<R: Read>(reader: R) -> io::Result<Fr> {
+    let mut repr = FrRepr::default();
+    repr.read_be(reader)?;
+    Fr::from_repr(repr).map_err(|_e| io::Error::new(io::ErrorKind::InvalidData, "not in field"))
+}
+
+fn write_fr<W: Write>(fr: &Fr, writer: W) -> io::Result<()> {
+    fr.into_repr().write_be(writer)
+}
+
+// Small integers are stored as 32 bytes words like the other vk fields
+fn read_u32_word<R: Read>(mut reader: R) -> io::Result<u32> {
+    let mut buf = [0; 32];
+    reader.read_exact(&mut buf)?;
+
+    if buf[..28].iter().any(|&b| b != 0) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "value is too large"));
+    }
+
+    let mut res = [0; 4];
+    res.copy_from_slice(&buf[28..]);
+    Ok(u32::from_be_bytes(res))
+}
+
+fn write_u32_word<W: Write>(value: u32, mut writer: W) -> io::Result<()> {
+    writer.write_all(&[0; 28])?;
+    writer.write_all(&value.to_be_bytes())
+}
+
+fn fr_from_u64(value: u64) -> Fr {
+    Fr::from_repr(FrRepr::from(value)).unwrap()
+}
+
+impl VerifyingKey {
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let power = read_u32_word(&mut reader)?;
+        let n_public = read_u32_word(&mut reader)? as usize;
+        let k1 = read_fr(&mut reader)?;
+        let k2 = read_fr(&mut reader)?;
+        let w = read_fr(&mut reader)?;
+
+        if power == 0 || power > MAX_POWER {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported domain size"));
+        }
+
+        // w must generate the domain, otherwise the Lagrange evaluations are meaningless
+        let mut w_half = w;
+        for _ in 1..power {
+            w_half.square();
+        }
+        let mut w_full = w_half;
+        w_full.square();
+
+        if (w_half == Fr::one()) || (w_full != Fr::one()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "w does not generate the domain",
+            ));
+        }
+
+        if n_public > (1 << power) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many public inputs"));
+        }
+
+        Ok(VerifyingKey {
+            power,
+            n_public,
+            k1,
+            k2,
+            w,
+            qm: read_g1_eth(&mut reader)?,
+            ql: read_g1_eth(&mut reader)?,
+            qr: read_g1_eth(&mut reader)?,
+            qo: read_g1_eth(&mut reader)?,
+            qc: read_g1_eth(&mut reader)?,
+            s1: read_g1_eth(&mut reader)?,
+            s2: read_g1_eth(&mut reader)?,
+            s3: read_g1_eth(&mut reader)?,
+            x_2: read_g2_eth(&mut reader)?,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_u32_word(self.power, &mut writer)?;
+        write_u32_word(self.n_public as u32, &mut writer)?;
+        write_fr(&self.k1, &mut writer)?;
+        write_fr(&self.k2, &mut writer)?;
+        write_fr(&self.w, &mut writer)?;
+
+        for p in [
+            &self.qm, &self.ql, &self.qr, &self.qo, &self.qc, &self.s1, &self.s2, &self.s3,
+        ]
+        .iter()
+        {
+            write_g1_eth(p, &mut writer)?;
+        }
+        write_g2_eth(&self.x_2, &mut writer)
+    }
+}
+
+impl Proof {
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        Ok(Proof {
+            a: read_g1_eth(&mut reader)?,
+            b: read_g1_eth(&mut reader)?,
+            c: read_g1_eth(&mut reader)?,
+            z: read_g1_eth(&mut reader)?,
+            t1: read_g1_eth(&mut reader)?,
+            t2: read_g1_eth(&mut reader)?,
+            t3: read_g1_eth(&mut reader)?,
+            wxi: read_g1_eth(&mut reader)?,
+            wxiw: read_g1_eth(&mut reader)?,
+            eval_a: read_fr(&mut reader)?,
+            eval_b: read_fr(&mut reader)?,
+            eval_c: read_fr(&mut reader)?,
+            eval_s1: read_fr(&mut reader)?,
+            eval_s2: read_fr(&mut reader)?,
+            eval_zw: read_fr(&mut reader)?,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for p in [
+            &self.a, &self.b, &self.c, &self.z, &self.t1, &self.t2, &self.t3, &self.wxi, &self.wxiw,
+        ]
+        .iter()
+        {
+            write_g1_eth(p, &mut writer)?;
+        }
+        for e in self.evals().iter() {
+            write_fr(e, &mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// `eval_a, eval_b, eval_c, eval_s1, eval_s2, eval_zw` in the order of the layout.
+    fn evals(&self) -> [Fr; 6] {
+        [
+            self.eval_a,
+            self.eval_b,
+            self.eval_c,
+            self.eval_s1,
+            self.eval_s2,
+            self.eval_zw,
+        ]
+    }
+}
+
+/// Fiat-Shamir transcript of snarkjs, a challenge is Keccak-256 of the data added since the last reset.
+struct Transcript {
+    data: Vec<u8>,
+}
+
+impl Transcript {
+    fn new() -> Self {
+        Transcript { data: vec![] }
+    }
+
+    fn add_point(&mut self, p: &G1Affine) {
+        write_g1_eth(p, &mut self.data).unwrap();
+    }
+
+    fn add_scalar(&mut self, fr: &Fr) {
+        write_fr(fr, &mut self.data).unwrap();
+    }
+
+    fn challenge(&mut self) -> Fr {
+        let hash = tiny_keccak::keccak256(&self.data);
+        self.data.clear();
+
+        // The hash is less than 6r, so a few subtractions reduce it
+        let mut repr = FrRepr::default();
+        repr.read_be(&hash[..]).unwrap();
+        while repr >= Fr::char() {
+            repr.sub_noborrow(&Fr::char());
+        }
+        Fr::from_repr(repr).unwrap()
+    }
+}
+
+struct Challenges {
+    beta: Fr,
+    gamma: Fr,
+    alpha: Fr,
+    xi: Fr,
+    v: [Fr; 5],
+    u: Fr,
+}
+
+fn calculate_challenges(vk: &VerifyingKey, proof: &Proof, inputs: &[Fr]) -> Challenges {
+    let mut transcript = Transcript::new();
+
+    for p in [&vk.qm, &vk.ql, &vk.qr, &vk.qo, &vk.qc, &vk.s1, &vk.s2, &vk.s3].iter() {
+        transcript.add_point(p);
+    }
+    for input in inputs.iter() {
+        transcript.add_scalar(input);
+    }
+    transcript.add_point(&proof.a);
+    transcript.add_point(&proof.b);
+    transcript.add_point(&proof.c);
+    let beta = transcript.challenge();
+
+    transcript.add_scalar(&beta);
+    let gamma = transcript.challenge();
+
+    transcript.add_scalar(&beta);
+    transcript.add_scalar(&gamma);
+    transcript.add_point(&proof.z);
+    let alpha = transcript.challenge();
+
+    transcript.add_scalar(&alpha);
+    transcript.add_point(&proof.t1);
+    transcript.add_point(&proof.t2);
+    transcript.add_point(&proof.t3);
+    let xi = transcript.challenge();
+
+    transcript.add_scalar(&xi);
+    for e in proof.evals().iter() {
+        transcript.add_scalar(e);
+    }
+    let v1 = transcript.challenge();
+
+    let mut v = [Fr::zero(); 5];
+    let mut acc = Fr::one();
+    for vi in v.iter_mut() {
+        acc.mul_assign(&v1);
+        *vi = acc;
+    }
+
+    transcript.add_point(&proof.wxi);
+    transcript.add_point(&proof.wxiw);
+    let u = transcript.challenge();
+
+    Challenges {
+        beta,
+        gamma,
+        alpha,
+        xi,
+        v,
+        u,
+    }
+}
+
+fn mul(p: &G1Affine, s: &Fr) -> <G1Affine as CurveAffine>::Projective {
+    p.mul(s.into_repr())
+}
+
+/// `a + b * c + d`, the shape of every permutation argument factor.
+fn lin(a: &Fr, b: &Fr, c: &Fr, d: &Fr) -> Fr {
+    let mut res = *b;
+    res.mul_assign(c);
+    res.add_assign(a);
+    res.add_assign(d);
+    res
+}
+
+fn product(factors: &[&Fr]) -> Fr {
+    let mut res = Fr::one();
+    for f in factors.iter() {
+        res.mul_assign(f);
+    }
+    res
+}
+
+/// Checks a PLONK proof. Returns `Ok(false)` for an invalid proof and an error
+/// if the number of inputs does not match the vk.
+pub fn verify_proof(vk: &VerifyingKey, proof: &Proof, inputs: &[Fr]) -> io::Result<bool> {
+    if inputs.len() != vk.n_public {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong number of inputs"));
+    }
+
+    let ch = calculate_challenges(vk, proof, inputs);
+
+    // Vanishing polynomial and Lagrange basis polynomials of the public inputs at xi
+    let mut xin = ch.xi;
+    for _ in 0..vk.power {
+        xin.square();
+    }
+    let mut zh = xin;
+    zh.sub_assign(&Fr::one());
+
+    let n = fr_from_u64(1 << vk.power);
+    let mut l = Vec::with_capacity(vk.n_public.max(1));
+    let mut w = Fr::one();
+    for _ in 0..vk.n_public.max(1) {
+        let mut den = ch.xi;
+        den.sub_assign(&w);
+        den.mul_assign(&n);
+
+        let mut li = match den.inverse() {
+            Some(inv) => inv,
+            None => return Ok(false),
+        };
+        li.mul_assign(&w);
+        li.mul_assign(&zh);
+        l.push(li);
+
+        w.mul_assign(&vk.w);
+    }
+
+    let mut pi = Fr::zero();
+    for (input, li) in inputs.iter().zip(l.iter()) {
+        let mut t = *input;
+        t.mul_assign(li);
+        pi.sub_assign(&t);
+    }
+
+    let mut alpha2 = ch.alpha;
+    alpha2.square();
+    let mut l1_alpha2 = l[0];
+    l1_alpha2.mul_assign(&alpha2);
+
+    // Permutation factors shared by r0 and D
+    let e3a = lin(&proof.eval_a, &ch.beta, &proof.eval_s1, &ch.gamma);
+    let e3b = lin(&proof.eval_b, &ch.beta, &proof.eval_s2, &ch.gamma);
+
+    let mut r0 = pi;
+    r0.sub_assign(&l1_alpha2);
+    let mut e3c = proof.eval_c;
+    e3c.add_assign(&ch.gamma);
+    r0.sub_assign(&product(&[&e3a, &e3b, &e3c, &proof.eval_zw, &ch.alpha]));
+
+    // D, the commitment of the linearisation polynomial
+    let mut d = mul(&vk.qm, &product(&[&proof.eval_a, &proof.eval_b]));
+    d.add_assign(&mul(&vk.ql, &proof.eval_a));
+    d.add_assign(&mul(&vk.qr, &proof.eval_b));
+    d.add_assign(&mul(&vk.qo, &proof.eval_c));
+    d.add_assign_mixed(&vk.qc);
+
+    let mut betaxi = ch.beta;
+    betaxi.mul_assign(&ch.xi);
+    let d2a1 = lin(&proof.eval_a, &betaxi, &Fr::one(), &ch.gamma);
+    let d2a2 = lin(&proof.eval_b, &betaxi, &vk.k1, &ch.gamma);
+    let d2a3 = lin(&proof.eval_c, &betaxi, &vk.k2, &ch.gamma);
+    let mut d2k = product(&[&d2a1, &d2a2, &d2a3, &ch.alpha]);
+    d2k.add_assign(&l1_alpha2);
+    d2k.add_assign(&ch.u);
+    d.add_assign(&mul(&proof.z, &d2k));
+
+    let d3k = product(&[&e3a, &e3b, &ch.alpha, &ch.beta, &proof.eval_zw]);
+    d.sub_assign(&mul(&vk.s3, &d3k));
+
+    let mut xin2 = xin;
+    xin2.square();
+    let mut d4 = proof.t1.into_projective();
+    d4.add_assign(&mul(&proof.t2, &xin));
+    d4.add_assign(&mul(&proof.t3, &xin2));
+    d4.mul_assign(zh.into_repr());
+    d.sub_assign(&d4);
+
+    // F, the batched commitment opened at xi, and E, its claimed evaluation
+    let mut f = d;
+    f.add_assign(&mul(&proof.a, &ch.v[0]));
+    f.add_assign(&mul(&proof.b, &ch.v[1]));
+    f.add_assign(&mul(&proof.c, &ch.v[2]));
+    f.add_assign(&mul(&vk.s1, &ch.v[3]));
+    f.add_assign(&mul(&vk.s2, &ch.v[4]));
+
+    // The evaluations of A, B, C, S1, S2 are batched with v, the one of Z at xi * w with u
+    let mut e = r0;
+    e.negate();
+    for (v, eval) in ch.v.iter().zip(proof.evals().iter()) {
+        e.add_assign(&product(&[v, eval]));
+    }
+    e.add_assign(&product(&[&ch.u, &proof.eval_zw]));
+
+    // e(-A1, X_2) * e(B1, G2) == 1
+    let mut a1 = proof.wxi.into_projective();
+    a1.add_assign(&mul(&proof.wxiw, &ch.u));
+    a1.negate();
+
+    let mut b1 = mul(&proof.wxi, &ch.xi);
+    b1.add_assign(&mul(&proof.wxiw, &product(&[&ch.u, &ch.xi, &vk.w])));
+    b1.add_assign(&f);
+    b1.sub_assign(&mul(&G1Affine::one(), &e));
+
+    Ok(Bn256::final_exponentiation(&Bn256::miller_loop(&[
+        (&a1.into_affine().prepare(), &vk.x_2.prepare()),
+        (&b1.into_affine().prepare(), &G2Affine::one().prepare()),
+    ]))
+    .unwrap()
+        == Fq12::one())
+}
+
+/// Same as `groth16_verify` for a PLONK vk and proof in the layouts described in the module docs.
+pub fn plonk_verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> io::Result<u8> {
+    if (vk.len() != VK_LEN) || (proof.len() != PROOF_LEN) || (inputs.len() % 32 != 0) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer length"));
+    }
+
+    let vk = VerifyingKey::read(vk)?;
+    let proof = Proof::read(proof)?;
+    let inputs = read_fr_vec::<Fr>(inputs)?;
+
+    if inputs.len() != vk.n_public {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong buffer parsing"));
+    }
+
+    Ok(verify_proof(&vk, &proof, &inputs).map(|r| r as u8).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod local_tests {
+    use base64::decode;
+
+    use super::*;
+    use test_case::test_case;
+
+    // Proofs of out = x^3 + x + 5 made with a known tau, the second one also has x public
+    const VK_1: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMrM33hyMFPIuybni+Wr+82UmJzZvgXCgqUja1Kwb1egA7KqkxwufmyVDnKKooHOtwMG/+P26vBEWmunAdxjrKgHYA9WbYqYzYGeC3u24TEET5jjXKACffv+ZX47G+jBbAuvPggQFXkv1HD1K3gFrE+VonFvoY9b6cMm40ykQq//wW4tXS52c0CHGvAfjXy80P/WO1PR6FzKwn7nQVlGmD2InjIKetFrPVHpC1K+G/XKZABhoZdFSWGeTeiNpIO2nEIydOAeycQG6sxMB1bO6pvMXFIm3NSSzaVBGOaxweeXibRU53mlSTgerV2STOEkMfJKoqy2GUtjgE34rLAE/lRBb/DSMnnPY2jKfrkasvgjqSKXCAkm7BOcEJ714BNhVgs7a3D1/x50Jsx02O/lol89iL6oRX8IwN/4Q3gYAJVPQPUEhBcjsiV8rxslZu9iRnL2e0LRwyAocTc79mKlUJFC8mgrxdA+4S6lAoiT3JjMNMv7y0osqAu2aoUNg0nhF0cvrjPb2wZHTNMXny35bo7AEIe0kK1ijAgVmTEhlnoki3HXum4S6IcrLvgc+TSW398p508ho5cO1czL7xAy+gjHMBwCw/xm4QvxfdAI5JeTi05skOICyQloz8Pom/WgsguAxQZXEnGP0sM6H3kYWknIeBEsiWSjDp9Nn3RSx4SwCDxYGWMl48MZeeO1mymJwYu++SlDNftq+XK6UrzhmSYIGVbJlhdWAmbdUuZFRIn/D6EZjdOUOR4H7/tH+OiE9UMz2V45GEdElFNOSTai2F88nfMDIYbdKV0dVHOtxinAATz0lQw3hB/1U4wVKhD0yfozPE2Fjx++XkDdb6TmYoTENGk6LRyiYMJQknN79JQD9CxuP0gB4HldwtK10ythaI=";
+    const PROOF_1: &str = "GEdu+us7F4jm4+HmmObUR1jY3iUSbF3/w4g/bPgKRQEtosmD8Bu5MjyAbIj4tprq7rCkP4l6tZlNTt57RhTawQZbSLfcTGHvYtlo4JNuSvCsapMG0x/lQDgbWGVw5az7HtTTQd9QPm/ohhtKEvlq6IzuH3X+uaqVT5722MbGZMcNDyAwWX5bPn+Nm6/M4bTVTTWSUEM7ye59Rkrtrma9ABbxkpJLjIYUZxyYAMiSV+ZkjwxdKdyuBRddy2P9rShqKERVPTM8kI9R14F4FRZ2hLR2CCHBJmu8MtK6QuGqO5YBs/6RAt+yM4pmoxtN7lEHsHNQsYqENjjl5zjIJCaKDy4p2MbpxLFeA18dsXw5eUpR8+NzEoT3gYRrKHg1PKtSEmIIvDtV8Zzxt1wnk84xMpb/GABc5of/je3yrzRsMGQKWwEC5lvMVPfDTD5Eu1e/jIBFjOHKVT2Xf3DNexojaiqsqwAN4nRm/FRuPVW1IYp9rkPjjCiA88U05bHFQfLRACNyJqKi8H3siRY2YR6Sc8A+5Mp+Pb668RJVyaE7if0WO0ri6IwGZEyNh5N1wflbxV65lnec2nNJYCZvT+sR4xZyd+jeFOiq/z2YMw5ag5O8xTBLu3efKlRFVIMMUEadD2Raw8uzldi7ICDLII7KwOwe2IykZJ6mJPG3Bequ7S4X7dU8MWY8rFYgyYhjIlKwoxHosrPdyCS9GpMH48dagCnOeM8eCK5NDd3mywAxe1JJbTfQf5U4txz5ybo8PNBZFC0QrDyC/IDrHU/5ERe4vC58ZykFbHNLzRrQ/XqQXEYE/HJ/xKIvt/2/yg+v/Fl/LkIZAh4MfvfSW2eiGSmw6hoWfYPPH+yZaXWijX+GpfI1Y7pTRsBA81MDd0V1BKppGEQBgA7JMKWHihXRh/FQ3iE9vweskz/alZBHWASbzKEtJ9Eem+FWK9h8yDB0p6AuqZZc+rGYCWymhExQ7sMQGh4IN1tyQObmaf4ZFmC/qYpfw4YQSq0CfMzzQCNjyF+2";
+    const INPUTS_1: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACM=";
+
+    const VK_2: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMhCCyiFsu/ThxuT0WU3VCMmW374RdO+5ixFQnG4wZGCw7OYft/FweM5SR/8XktMG3xUR4X21O4obKNrvY2jSO6BEep+1vONXZYCW0X1lQ3GFTrWNqnELwEKRvAFogIvGgkhEmniVw8b6vGSHpeCt45jcp1+Bx+1tYVoeCKnL9GuihmfAY6YpGHBPR99GdWnkXThOj1M/cWvLayF1AWxpapGA1eIfv4MfFUesDDDv3Q5YIWqXOx8XRl9B4L8fvYVToYUk2Z9QzAnGk2ZqIj/wi+VG9hMA14guXVeTJ6nuwOMAPTA4fBd+NH9/uhoZIf2RsVfII/VVMd/oRzL5bE+8F3K6y6EsAMrK+3xVvhPllsBnUkZ+p5p5N1Ly+K+8GDqyIIm+dl/DvkG0Wed2CG9fiaOUJ4HTm9dzbXyt62sp0hfBYGKTihbmwvSdIvMeTIOfBikLSk7kul26+9C632mR03HaWb3abuXlQsBJ/kNiL5XLtvY2u7FUSzTuQO/Z7EcogeGo8y92IuC/3cfwzN3uajTFNXzwxIbMB4XFSKp6dk1ho32x1fT2yo3klNIeiBA9EVtXnu0of/ULUlP20638ceLG3/xQ3gNA08gXettzIsO9AKrpHlOvbAvnR/BKiT7jIFRlR/XQoo3bPIxaq8jffzVUOcri8PN33Q0BNRAA3JxRfgxBzyKgLMc3R06uG0vzkdoupUgG1//oCOYTupS0S/EG+Z1aNXHrX+I2QroWa5O9JUejYo+pDkZCkXvf4vyJIl42vtMvjIK0zHGjpo+D2RV/RdoGG9vEjsCZF5pRvfNgiHyd0FJlBvZKHGQWlaqY0Xk/yPRc9JdFPiP6fT63LJJcMlQR4oxk7zRz1BGXIJSXcvPpPslZK5xD+GwoUMs9s=";
+    const PROOF_2: &str = "A38egryv4oT5XymEbU1oO4BS+Q26GBybXFmZRsphQ30RCJSWuvJ9rE1HGWPTMIyr6/gebMpwKoiC11II8VDWcQWRNALTMsVhH0q0O0V+uzi98jOliNNkxidnKoBGEmUGAGBwvlECeNZzrSqMsjZEKK7534Kavqb9BkpKLeHr1/MYFBJJnELn5aiVMPoxJXWHLSZV7mYKSZTBsdiiOtTCPw4wHAqpgyKokxsjLXRBx+yDjDqv4iraaZ9x9+JBU62pFqfreDjHqU1xHtVJYDaj6xoW6OdQ721GohN2MaflOV0WKQ5UpsoiszJwVAZ7CXkylW2xmRmxSANGB5QPHK0HpxJfpypRf0u9qxFhSHH/XZOQVROpZV7jznHf2peRAY2sAAhzc44cUe4A2zAOGXh8VZLBnI1geq6LIB8gYBhsR44JbcTzFLUUEbCkPk77MFBiGLwnbH1ADsWQ05BBwgS9nBgrUjoPzXdGyiJcC3SsuwU+l/cJaJHTYK8zWwppTq7ZI/Lau2rdNhMrKs2QhuahFiPOysjUzjZ9XOadzHryg0skofvo6w4PiAFIKlcCbhfsh51qBLzsngluE/KJJHzEshgWQuJo05qsCpverdxBDQqYf0HitKzg/lchae8FEs86DXCdvVj+ycZydO0/hdSLFAkkKCqd8a6HObZ7cj1mBvMSyctRReZUoWVPIt77tTzk8y+YwmEl8ALi3c9wl//ScQNkQs8VCaEe5WN80j7ynYHEPoNz4skAPGquvpd6iKtMFQxRATgtjs5mO3Tmsy8KmlQSRoGh9BphOFmYsOAdtJYqp6sZjwCfp2EtUO4lsIcxnadFH97uSJQ2jvRYYo/4Fxw+mR9bhvlT1zRMFuS/+4xynt088jiqNRDpgDgjgnyzDMKliCw8yElvOwDIL3rqMYBrDYD6a9+5reHWGocpQtcLjtc4jkCjmSjTEIlj1L6yd3+exTJrG/MWh/QaXG4diiyjLd+iuxklGxdYWW7kAQl4fmhNlI2Psh16u+txs5hj";
+    const INPUTS_2: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWMAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABw==";
+
+    #[test_case(VK_1, PROOF_1, INPUTS_1; "one input")]
+    #[test_case(VK_2, PROOF_2, INPUTS_2; "two inputs")]
+    fn plonk_verify_ok_test(vk_b64: &str, proof_b64: &str, inputs_b64: &str) {
+        let vk = decode(vk_b64).unwrap();
+        let proof = decode(proof_b64).unwrap();
+        let inputs = decode(inputs_b64).unwrap();
+
+        assert_eq!(plonk_verify(&vk, &proof, &inputs).unwrap(), 1);
+    }
+
+    #[test_case(VK_1, PROOF_1, "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACQ="; "wrong input")]
+    #[test_case(VK_2, PROOF_2, "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABw=="; "wrong first input")]
+    #[test_case(VK_1, PROOF_2, INPUTS_1; "proof of another circuit")]
+    fn plonk_verify_fail_test(vk_b64: &str, proof_b64: &str, inputs_b64: &str) {
+        let vk = decode(vk_b64).unwrap();
+        let proof = decode(proof_b64).unwrap();
+        let inputs = decode(inputs_b64).unwrap();
+
+        assert_eq!(plonk_verify(&vk, &proof, &inputs).unwrap(), 0);
+    }
+
+    #[test_case(VK_1, PROOF_1, ""; "missing input")]
+    #[test_case(VK_1, PROOF_1, INPUTS_2; "extra input")]
+    #[test_case(VK_1, &PROOF_1[4..], INPUTS_1; "short proof")]
+    #[test_case(&VK_1[4..], PROOF_1, INPUTS_1; "short vk")]
+    fn plonk_verify_malformed_test(vk_b64: &str, proof_b64: &str, inputs_b64: &str) {
+        let vk = decode(vk_b64).unwrap();
+        let proof = decode(proof_b64).unwrap();
+        let inputs = decode(inputs_b64).unwrap();
+
+        assert!(plonk_verify(&vk, &proof, &inputs).is_err());
+    }
+
+    #[test]
+    fn tampered_proof_test() {
+        let vk = decode(VK_1).unwrap();
+        let inputs = decode(INPUTS_1).unwrap();
+        let mut proof = Proof::read(&decode(PROOF_1).unwrap()[..]).unwrap();
+
+        proof.eval_a.add_assign(&Fr::one());
+        let mut data = vec![];
+        proof.write(&mut data).unwrap();
+        assert_eq!(data.len(), PROOF_LEN);
+        assert_eq!(plonk_verify(&vk, &data, &inputs).unwrap(), 0);
+
+        // An evaluation out of the field is malformed rather than invalid
+        Fr::char().write_be(&mut data[PROOF_LEN - 32..]).unwrap();
+        assert!(plonk_verify(&vk, &data, &inputs).is_err());
+    }
+
+    #[test]
+    fn verifying_key_test() {
+        let data = decode(VK_2).unwrap();
+        let vk = VerifyingKey::read(&data[..]).unwrap();
+        assert_eq!((vk.power, vk.n_public), (4, 2));
+
+        let mut res = vec![];
+        vk.write(&mut res).unwrap();
+        assert_eq!(res, data);
+
+        // w of a smaller domain
+        let mut vk_1 = VerifyingKey::read(&decode(VK_1).unwrap()[..]).unwrap();
+        vk_1.power = 4;
+        let mut res = vec![];
+        vk_1.write(&mut res).unwrap();
+        assert!(VerifyingKey::read(&res[..]).is_err());
+    }
+}
@@ -1,6 +1,38 @@
-use std::{io, mem};
+use std::{
+    io,
+    io::{Read, Write},
+    mem,
+};
 
-use ff::{PrimeField, PrimeFieldRepr};
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{
+    bn256::{Fq, Fq2, G1Affine, G2Affine},
+    CurveAffine,
+};
+
+/// Byte encoding of BN256 curve points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// `pairing_ce` compressed points, 32 bytes for G1 and 64 bytes for G2.
+    Compressed,
+    /// EIP-196/197 uncompressed points as used by Ethereum precompiles and snarkjs solidity verifiers.
+    /// G1 is `x || y` in 64 bytes, G2 is `x_im || x_re || y_im || y_re` in 128 bytes, all coordinates
+    /// are big-endian, and the point at infinity is all zeros.
+    Ethereum,
+}
+
+impl Encoding {
+    pub fn g1_len(&self) -> usize {
+        match self {
+            Encoding::Compressed => 32,
+            Encoding::Ethereum => 64,
+        }
+    }
+
+    pub fn g2_len(&self) -> usize {
+        2 * self.g1_len()
+    }
+}
 
 pub fn read_fr_repr_be<Fr: PrimeField>(data: &[u8]) -> io::Result<Fr::Repr> {
     let mut fr_repr = Fr::zero().into_repr();
@@ -43,3 +75,60 @@ where
     }
     Ok(())
 }
+
+fn read_fq_be<R: Read>(reader: R) -> io::Result<Fq> {
+    let mut repr = Fq::zero().into_repr();
+    repr.read_be(reader)?;
+    Fq::from_repr(repr).map_err(|_e| io::Error::new(io::ErrorKind::InvalidData, "not in field"))
+}
+
+/// Reads a G1 point in `Encoding::Ethereum`, the point is checked to be on the curve.
+pub fn read_g1_eth<R: Read>(mut reader: R) -> io::Result<G1Affine> {
+    let x = read_fq_be(&mut reader)?;
+    let y = read_fq_be(&mut reader)?;
+
+    if x.is_zero() && y.is_zero() {
+        return Ok(G1Affine::zero());
+    }
+
+    G1Affine::from_xy_checked(x, y).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads a G2 point in `Encoding::Ethereum`, the point is checked to be on the curve.
+pub fn read_g2_eth<R: Read>(mut reader: R) -> io::Result<G2Affine> {
+    let x_c1 = read_fq_be(&mut reader)?;
+    let x_c0 = read_fq_be(&mut reader)?;
+    let y_c1 = read_fq_be(&mut reader)?;
+    let y_c0 = read_fq_be(&mut reader)?;
+
+    let x = Fq2 { c0: x_c0, c1: x_c1 };
+    let y = Fq2 { c0: y_c0, c1: y_c1 };
+
+    if x.is_zero() && y.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+
+    G2Affine::from_xy_checked(x, y).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write_g1_eth<W: Write>(p: &G1Affine, mut writer: W) -> io::Result<()> {
+    if p.is_zero() {
+        return writer.write_all(&[0; 64]);
+    }
+
+    let (x, y) = p.into_xy_unchecked();
+    x.into_repr().write_be(&mut writer)?;
+    y.into_repr().write_be(&mut writer)
+}
+
+pub fn write_g2_eth<W: Write>(p: &G2Affine, mut writer: W) -> io::Result<()> {
+    if p.is_zero() {
+        return writer.write_all(&[0; 128]);
+    }
+
+    let (x, y) = p.into_xy_unchecked();
+    x.c1.into_repr().write_be(&mut writer)?;
+    x.c0.into_repr().write_be(&mut writer)?;
+    y.c1.into_repr().write_be(&mut writer)?;
+    y.c0.into_repr().write_be(&mut writer)
+}
@@ -0,0 +1,275 @@
+//! Import of snarkjs (circom) JSON files for the bn128 curve, see `zwaves_primitives::snarkjs`.
+
+use std::io;
+
+use ff::PrimeField;
+use pairing_ce::{
+    bn256::{Bn256, Fr, G1Affine, G2Affine},
+    CurveAffine,
+};
+use zwaves_primitives::snarkjs::{
+    decimal_to_bytes_be, g1_coords, g2_coords, parse_json, public_signals_to_bytes, PlonkProofJson,
+    PlonkVerificationKeyJson, ProofJson, VerificationKeyJson, CURVE_BN128,
+};
+
+use super::{
+    plonk,
+    serialization::{read_fr_repr_be, read_fr_vec, read_g1_eth, read_g2_eth},
+    verifier::{Proof, TruncatedVerifyingKey},
+};
+
+// Points go through the EIP-196/197 encoding, it has the same big-endian affine coordinates
+fn g1_or_zero(p: &[String]) -> io::Result<G1Affine> {
+    let mut buf = [0; 64];
+
+    if let Some((x, y)) = g1_coords(p)? {
+        buf[..32].copy_from_slice(&decimal_to_bytes_be(x, 32)?);
+        buf[32..].copy_from_slice(&decimal_to_bytes_be(y, 32)?);
+    }
+
+    read_g1_eth(&buf[..])
+}
+
+fn g1(p: &[String]) -> io::Result<G1Affine> {
+    non_zero(g1_or_zero(p)?)
+}
+
+fn g2(p: &[Vec<String>]) -> io::Result<G2Affine> {
+    let mut buf = [0; 128];
+
+    if let Some(([x_c0, x_c1], [y_c0, y_c1])) = g2_coords(p)? {
+        buf[..32].copy_from_slice(&decimal_to_bytes_be(x_c1, 32)?);
+        buf[32..64].copy_from_slice(&decimal_to_bytes_be(x_c0, 32)?);
+        buf[64..96].copy_from_slice(&decimal_to_bytes_be(y_c1, 32)?);
+        buf[96..].copy_from_slice(&decimal_to_bytes_be(y_c0, 32)?);
+    }
+
+    non_zero(read_g2_eth(&buf[..])?)
+}
+
+fn non_zero<G: CurveAffine>(p: G) -> io::Result<G> {
+    if p.is_zero() {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"))
+    } else {
+        Ok(p)
+    }
+}
+
+fn fr(s: &str) -> io::Result<Fr> {
+    let repr = read_fr_repr_be::<Fr>(&decimal_to_bytes_be(s, 32)?)?;
+    Fr::from_repr(repr).map_err(|_e| io::Error::new(io::ErrorKind::InvalidData, "not in field"))
+}
+
+pub fn read_vk(json: &str) -> io::Result<TruncatedVerifyingKey<Bn256>> {
+    let vk: VerificationKeyJson = parse_json(json)?;
+    vk.check(CURVE_BN128)?;
+
+    Ok(TruncatedVerifyingKey {
+        alpha_g1: g1(&vk.vk_alpha_1)?,
+        beta_g2: g2(&vk.vk_beta_2)?,
+        gamma_g2: g2(&vk.vk_gamma_2)?,
+        delta_g2: g2(&vk.vk_delta_2)?,
+        ic: vk.ic.iter().map(|p| g1(p)).collect::<io::Result<_>>()?,
+    })
+}
+
+pub fn read_proof(json: &str) -> io::Result<Proof<Bn256>> {
+    let proof: ProofJson = parse_json(json)?;
+    proof.check(CURVE_BN128)?;
+
+    Ok(Proof {
+        a: g1(&proof.pi_a)?,
+        b: g2(&proof.pi_b)?,
+        c: g1(&proof.pi_c)?,
+    })
+}
+
+pub fn read_public_signals(json: &str) -> io::Result<Vec<Fr>> {
+    read_fr_vec::<Fr>(&public_signals_to_bytes(json)?)
+}
+
+// Unlike groth16 points, plonk commitments may be zero, e.g. `Qc` of a circuit without constants
+pub fn read_plonk_vk(json: &str) -> io::Result<plonk::VerifyingKey> {
+    let vk: PlonkVerificationKeyJson = parse_json(json)?;
+    vk.check(CURVE_BN128)?;
+
+    let res = plonk::VerifyingKey {
+        power: vk.power,
+        n_public: vk.n_public,
+        k1: fr(&vk.k1)?,
+        k2: fr(&vk.k2)?,
+        w: fr(&vk.w)?,
+        qm: g1_or_zero(&vk.qm)?,
+        ql: g1_or_zero(&vk.ql)?,
+        qr: g1_or_zero(&vk.qr)?,
+        qo: g1_or_zero(&vk.qo)?,
+        qc: g1_or_zero(&vk.qc)?,
+        s1: g1_or_zero(&vk.s1)?,
+        s2: g1_or_zero(&vk.s2)?,
+        s3: g1_or_zero(&vk.s3)?,
+        x_2: g2(&vk.x_2)?,
+    };
+
+    // Goes through the byte layout to get the same checks of the domain
+    let mut data = vec![];
+    res.write(&mut data)?;
+    plonk::VerifyingKey::read(&data[..])
+}
+
+pub fn read_plonk_proof(json: &str) -> io::Result<plonk::Proof> {
+    let proof: PlonkProofJson = parse_json(json)?;
+    proof.check(CURVE_BN128)?;
+
+    Ok(plonk::Proof {
+        a: g1_or_zero(&proof.a)?,
+        b: g1_or_zero(&proof.b)?,
+        c: g1_or_zero(&proof.c)?,
+        z: g1_or_zero(&proof.z)?,
+        t1: g1_or_zero(&proof.t1)?,
+        t2: g1_or_zero(&proof.t2)?,
+        t3: g1_or_zero(&proof.t3)?,
+        wxi: g1_or_zero(&proof.wxi)?,
+        wxiw: g1_or_zero(&proof.wxiw)?,
+        eval_a: fr(&proof.eval_a)?,
+        eval_b: fr(&proof.eval_b)?,
+        eval_c: fr(&proof.eval_c)?,
+        eval_s1: fr(&proof.eval_s1)?,
+        eval_s2: fr(&proof.eval_s2)?,
+        eval_zw: fr(&proof.eval_zw)?,
+    })
+}
+
+#[cfg(test)]
+mod local_tests {
+    use ff::Field;
+
+    use super::*;
+
+    const G1_GEN: &str = r#"["1", "2", "1"]"#;
+
+    // G2 generator from EIP-197, with the real parts first as snarkjs writes them
+    const G2_GEN: &str = r#"[
+        [
+            "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+            "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+        ],
+        [
+            "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+            "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+        ],
+        ["1", "0"]
+    ]"#;
+
+    #[test]
+    fn read_snarkjs_test() {
+        let vk_json = format!(
+            r#"{{"protocol": "groth16", "curve": "bn128", "nPublic": 1,
+                "vk_alpha_1": {g1}, "vk_beta_2": {g2}, "vk_gamma_2": {g2}, "vk_delta_2": {g2}, "IC": [{g1}, {g1}]}}"#,
+            g1 = G1_GEN,
+            g2 = G2_GEN
+        );
+        let vk = read_vk(&vk_json).unwrap();
+        assert_eq!(vk.alpha_g1, G1Affine::one());
+        assert_eq!(vk.beta_g2, G2Affine::one());
+        assert_eq!(vk.ic.len(), 2);
+        assert!(read_vk(&vk_json.replace(r#""nPublic": 1"#, r#""nPublic": 2"#)).is_err());
+        assert!(read_vk(&vk_json.replace("bn128", "bls12381")).is_err());
+
+        // Older snarkjs versions do not write the curve, it is bn128 then
+        let proof_json = format!(
+            r#"{{"protocol": "groth16", "pi_a": {g1}, "pi_b": {g2}, "pi_c": {g1}}}"#,
+            g1 = G1_GEN,
+            g2 = G2_GEN
+        );
+        let proof = read_proof(&proof_json).unwrap();
+        assert!(
+            proof
+                == Proof {
+                    a: G1Affine::one(),
+                    b: G2Affine::one(),
+                    c: G1Affine::one()
+                }
+        );
+        assert!(read_proof(&proof_json.replace(r#"["1", "2", "1"]"#, r#"["1", "3", "1"]"#)).is_err());
+        assert!(read_proof(&proof_json.replace(r#"["1", "2", "1"]"#, r#"["0", "1", "0"]"#)).is_err());
+
+        let inputs = read_public_signals(r#"["0", "1", "255"]"#).unwrap();
+        assert_eq!(inputs, vec![Fr::zero(), Fr::one(), Fr::from_str("255").unwrap()]);
+        assert!(read_public_signals(
+            r#"["21888242871839275222246405745257275088548364400416034343698204186575808495617"]"#
+        )
+        .is_err());
+    }
+
+    // Vector of `plonk::local_tests`, out = x^3 + x + 5
+    const PLONK_VK: &str = r#"{
+        "protocol": "plonk",
+        "curve": "bn128",
+        "nPublic": 1,
+        "power": 3,
+        "k1": "2",
+        "k2": "3",
+        "Qm": ["6690458344957346670711701879658525282559607253606354213452846978290737394336", "13343652458353040365328824438533311381702529310192101054317269034651955168688", "1"],
+        "Ql": ["21140270785596208749210457716490460388226586820048256404105038134198921773055", "2587916465850509156497599530377182487723100182675365347028710041118133870838", "1"],
+        "Qr": ["15592039979008686218891643033163509193414530162906205808934806976748246194801", "3975098781008413990828368321314902439426151545241859687166294539038492171870", "1"],
+        "Qo": ["17557736384644396829891593935799603802246813595375078067066089697491505379665", "2600379835205684969459753540494401324107478668661381631475048810901152826712", "1"],
+        "Qc": ["20321707374069798281244399447033684851592109495186995616833377110085502915901", "1731634796023674121691086635469353775351788755938615161917578899225752977989", "1"],
+        "S1": ["5331686594291453719816140960693308864568715976666735143904157131278828143709", "13001736216410254815995636656212042811362906464631041335139292343664966756498", "1"],
+        "S2": ["20706335817370633098903490696456569987602247206821932804813210623593329453091", "13004767690598238477286947723993573375329508640139780346165978658567297073864", "1"],
+        "S3": ["20811830294669271370912181625148179376721042256687151374427985742433596871360", "14900486602686088828315283988045454490094356568154088491990275162881143497880", "1"],
+        "X_2": [["5794191861034100823853642072491971226865851550732460332270430116851073197824", "14653091800908553656907639412863794953447053492343584445168833244953862869973"], ["7607414773982775323529034714067101552400729037097117359345310641978411419042", "2240046867588901021084076220685705530634404471406292161303999343520527256083"], ["1", "0"]],
+        "w": "19540430494807482326159819597004422086093766032135589407132600596362845576832"
+    }"#;
+
+    const PLONK_PROOF: &str = r#"{
+        "A": ["10981720464455169911290451479898411355172093536628561322172482991644813706497", "20641698218796158793339582813145528283147332808631613874623785416995049364161", "1"],
+        "B": ["2875162057001766442704871346099261588746991588162423334878555306715725016315", "13945415079624366768675301010511195667656099774979815611904198633531789173959", "1"],
+        "C": ["5906791896939450110796008675030551476230824078645700944728413134057267182848", "10377704410518525986365369733493343175640176540224510087114723412629053581418", "1"],
+        "Z": ["18213247842127651305622501703638623921576853955561171111369047160945841421206", "770335426245596544318932121515792866869916363892820370793840475115134290447", "1"],
+        "T1": ["20880327904385193180537222469740159633438734761087528060663498663837956877138", "8314842575536604841360930896949937808608471433525649560897531086559567949924", "1"],
+        "T2": ["4683918548653327643750301875924578544118201621433521339433704621825804870506", "19302217535726589453220104730024002616858154177978975545054453096908173996753", "1"],
+        "T3": ["62627487956371567977440808843121573657959022689452524473024248842005744125", "10055643492321449095250428881065709446419331216008828881622407245618488218083", "1"],
+        "Wxi": ["10153130820127421370023110507144770992691362531202364818412566026359309092509", "6962003871039917835541927463005129792735076119586053050867840516747772751150", "1"],
+        "Wxiw": ["10823409966538630593849809660940128642902304712249744997382652796256299473536", "18909631080691783913105285238891180971597036369121727512692520685690181963865", "1"],
+        "eval_a": "9125880161005352617075911826691422498411987421130021182896674490606584618054",
+        "eval_b": "2255287098361908039579635996676019422102815643920740466047053908072544710890",
+        "eval_c": "11799870970449190006235950485478842316942898106858377333047281392380102879849",
+        "eval_s1": "10975664320579980500653643520046522868713066595532395601758864764857774492833",
+        "eval_s2": "20424428511974327019875222517288657969484378206392909537220740728689996795930",
+        "eval_zw": "13583902295452739282768088960601232233361716400019753330322933229813185929142",
+        "protocol": "plonk",
+        "curve": "bn128"
+    }"#;
+
+    #[test]
+    fn read_plonk_test() {
+        let vk = read_plonk_vk(PLONK_VK).unwrap();
+        let proof = read_plonk_proof(PLONK_PROOF).unwrap();
+        let inputs = read_public_signals(r#"["35"]"#).unwrap();
+
+        assert!(plonk::verify_proof(&vk, &proof, &inputs).unwrap());
+        assert!(!plonk::verify_proof(&vk, &proof, &read_public_signals(r#"["36"]"#).unwrap()).unwrap());
+
+        assert!(read_plonk_vk(&PLONK_VK.replace(r#""power": 3"#, r#""power": 4"#)).is_err());
+        assert!(read_plonk_proof(&PLONK_PROOF.replace(r#""protocol": "plonk""#, r#""protocol": "groth16""#)).is_err());
+        assert!(read_proof(PLONK_PROOF).is_err());
+    }
+
+    fn read_fixture(name: &str) -> String {
+        let path = format!("{}/test_vectors/plonk/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}, run test_vectors/plonk/generate.sh", path, e))
+    }
+
+    // Output of `snarkjs plonk setup/prove` itself, unlike the vectors made with a known tau
+    #[test]
+    #[ignore = "needs the files of test_vectors/plonk/generate.sh"]
+    fn snarkjs_plonk_fixture_test() {
+        let vk = read_plonk_vk(&read_fixture("verification_key.json")).unwrap();
+        let proof = read_plonk_proof(&read_fixture("proof.json")).unwrap();
+        let inputs = read_public_signals(&read_fixture("public.json")).unwrap();
+        assert_eq!(inputs, vec![Fr::from_str("35").unwrap()]);
+
+        assert!(plonk::verify_proof(&vk, &proof, &inputs).unwrap());
+        assert!(!plonk::verify_proof(&vk, &proof, &read_public_signals(r#"["36"]"#).unwrap()).unwrap());
+    }
+}
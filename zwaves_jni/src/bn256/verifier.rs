@@ -4,7 +4,10 @@ use std::{
 };
 
 use ff::{Field, PrimeField};
-use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+use pairing_ce::{bn256::Bn256, CurveAffine, CurveProjective, EncodedPoint, Engine};
+use rand::{Rand, Rng};
+
+use super::serialization::{read_g1_eth, read_g2_eth, write_g1_eth, write_g2_eth, Encoding};
 
 #[derive(Debug)]
 pub enum SynthesisError {
@@ -92,6 +95,30 @@ impl<E: Engine> Proof<E> {
     }
 }
 
+impl Proof<Bn256> {
+    pub fn write_encoded<W: Write>(&self, mut writer: W, encoding: Encoding) -> io::Result<()> {
+        match encoding {
+            Encoding::Compressed => self.write(writer),
+            Encoding::Ethereum => {
+                write_g1_eth(&self.a, &mut writer)?;
+                write_g2_eth(&self.b, &mut writer)?;
+                write_g1_eth(&self.c, &mut writer)
+            }
+        }
+    }
+
+    pub fn read_encoded<R: Read>(mut reader: R, encoding: Encoding) -> io::Result<Self> {
+        match encoding {
+            Encoding::Compressed => Self::read(reader),
+            Encoding::Ethereum => Ok(Proof {
+                a: non_zero(read_g1_eth(&mut reader)?)?,
+                b: non_zero(read_g2_eth(&mut reader)?)?,
+                c: non_zero(read_g1_eth(&mut reader)?)?,
+            }),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TruncatedVerifyingKey<E: Engine> {
     pub alpha_g1: E::G1Affine,
@@ -189,8 +216,128 @@ impl<E: Engine> TruncatedVerifyingKey<E> {
             ic: ic.clone(),
         })
     }
+
+    /// Same as `read`, but every point is checked to be on the curve and in the prime order
+    /// subgroup, and the reader must hold nothing after the last `ic` point.
+    pub fn read_checked<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut g1_repr = <E::G1Affine as CurveAffine>::Compressed::empty();
+        let mut g2_repr = <E::G2Affine as CurveAffine>::Compressed::empty();
+
+        reader.read_exact(g1_repr.as_mut())?;
+        let alpha_g1 = read_point_checked(&g1_repr)?;
+
+        reader.read_exact(g2_repr.as_mut())?;
+        let beta_g2 = read_point_checked(&g2_repr)?;
+
+        reader.read_exact(g2_repr.as_mut())?;
+        let gamma_g2 = read_point_checked(&g2_repr)?;
+
+        reader.read_exact(g2_repr.as_mut())?;
+        let delta_g2 = read_point_checked(&g2_repr)?;
+
+        let mut rest = vec![];
+        reader.read_to_end(&mut rest)?;
+
+        let g1_len = g1_repr.as_ref().len();
+
+        if rest.is_empty() || (rest.len() % g1_len != 0) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong ic length"));
+        }
+
+        let ic = rest
+            .chunks(g1_len)
+            .map(|data| {
+                g1_repr.as_mut().copy_from_slice(data);
+                read_point_checked(&g1_repr)
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(TruncatedVerifyingKey {
+            alpha_g1: alpha_g1,
+            beta_g2: beta_g2,
+            gamma_g2: gamma_g2,
+            delta_g2: delta_g2,
+            ic: ic,
+        })
+    }
 }
 
+impl TruncatedVerifyingKey<Bn256> {
+    pub fn write_encoded<W: Write>(&self, mut writer: W, encoding: Encoding) -> io::Result<()> {
+        match encoding {
+            Encoding::Compressed => self.write(writer),
+            Encoding::Ethereum => {
+                write_g1_eth(&self.alpha_g1, &mut writer)?;
+                write_g2_eth(&self.beta_g2, &mut writer)?;
+                write_g2_eth(&self.gamma_g2, &mut writer)?;
+                write_g2_eth(&self.delta_g2, &mut writer)?;
+                for ic in &self.ic {
+                    write_g1_eth(ic, &mut writer)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn read_encoded<R: Read>(mut reader: R, encoding: Encoding) -> io::Result<Self> {
+        if encoding == Encoding::Compressed {
+            return Self::read(reader);
+        }
+
+        let alpha_g1 = non_zero(read_g1_eth(&mut reader)?)?;
+        let beta_g2 = non_zero(read_g2_eth(&mut reader)?)?;
+        let gamma_g2 = non_zero(read_g2_eth(&mut reader)?)?;
+        let delta_g2 = non_zero(read_g2_eth(&mut reader)?)?;
+
+        let mut g1_buf = vec![0; encoding.g1_len()];
+        let mut ic = vec![];
+
+        while reader.read_exact(&mut g1_buf).is_ok() {
+            ic.push(non_zero(read_g1_eth(g1_buf.as_slice())?)?);
+        }
+
+        Ok(TruncatedVerifyingKey {
+            alpha_g1: alpha_g1,
+            beta_g2: beta_g2,
+            gamma_g2: gamma_g2,
+            delta_g2: delta_g2,
+            ic: ic,
+        })
+    }
+}
+
+fn non_zero<G: CurveAffine>(point: G) -> io::Result<G> {
+    if point.is_zero() {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"))
+    } else {
+        Ok(point)
+    }
+}
+
+fn read_point_checked<G: CurveAffine>(repr: &G::Compressed) -> io::Result<G> {
+    let point = repr
+        .into_affine()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if point.is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"));
+    }
+
+    // Explicit check, as decoding does not guarantee the prime order subgroup for every curve
+    if !point.mul(G::Scalar::char()).is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "point not in subgroup"));
+    }
+
+    Ok(point)
+}
+
+// Decoding does not check the subgroup of G2 for BN256
+fn b_in_subgroup<E: Engine>(b: &E::G2Affine) -> bool {
+    b.mul(E::Fr::char()).is_zero()
+}
+
+zwaves_primitives::groth16_verifier!();
+
 pub fn verify_proof<'a, E: Engine>(
     tvk: &'a TruncatedVerifyingKey<E>,
     proof: &Proof<E>,
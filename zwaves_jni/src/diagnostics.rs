@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// Element of the verifying key, the proof or the public inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Element {
+    Alpha,
+    Beta,
+    Gamma,
+    Delta,
+    Ic(usize),
+    A,
+    B,
+    C,
+    Input(usize),
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Element::Alpha => write!(f, "alpha"),
+            Element::Beta => write!(f, "beta"),
+            Element::Gamma => write!(f, "gamma"),
+            Element::Delta => write!(f, "delta"),
+            Element::Ic(i) => write!(f, "ic[{}]", i),
+            Element::A => write!(f, "a"),
+            Element::B => write!(f, "b"),
+            Element::C => write!(f, "c"),
+            Element::Input(i) => write!(f, "input[{}]", i),
+        }
+    }
+}
+
+/// The reason a Groth16 proof was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationFailure {
+    /// Lengths of vk, proof and inputs do not match each other.
+    WrongBufferLength,
+    /// Point encoding is malformed: wrong flag bits or a coordinate out of the base field.
+    InvalidEncoding(Element),
+    /// Point is not on the curve.
+    NotOnCurve(Element),
+    /// Point is at infinity.
+    PointAtInfinity(Element),
+    /// Point is not in the prime order subgroup.
+    NotInSubgroup(Element),
+    /// Public input is not less than the scalar field modulus.
+    InputNotInField(usize),
+    /// All elements are well-formed, but the pairing equation does not hold.
+    PairingMismatch,
+}
+
+impl VerificationFailure {
+    /// Stable numeric code of the reason, `0` is reserved for valid proofs.
+    pub fn code(&self) -> i32 {
+        match self {
+            VerificationFailure::WrongBufferLength => 1,
+            VerificationFailure::InvalidEncoding(_) => 2,
+            VerificationFailure::NotOnCurve(_) => 3,
+            VerificationFailure::PointAtInfinity(_) => 4,
+            VerificationFailure::NotInSubgroup(_) => 5,
+            VerificationFailure::InputNotInField(_) => 6,
+            VerificationFailure::PairingMismatch => 7,
+        }
+    }
+
+    /// The element which caused the failure, if there is a single one.
+    pub fn element(&self) -> Option<Element> {
+        match *self {
+            VerificationFailure::InvalidEncoding(e)
+            | VerificationFailure::NotOnCurve(e)
+            | VerificationFailure::PointAtInfinity(e)
+            | VerificationFailure::NotInSubgroup(e) => Some(e),
+            VerificationFailure::InputNotInField(i) => Some(Element::Input(i)),
+            VerificationFailure::WrongBufferLength | VerificationFailure::PairingMismatch => None,
+        }
+    }
+}
+
+impl fmt::Display for VerificationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerificationFailure::WrongBufferLength => write!(f, "wrong buffer length"),
+            VerificationFailure::InvalidEncoding(e) => write!(f, "invalid point encoding: {}", e),
+            VerificationFailure::NotOnCurve(e) => write!(f, "point not on curve: {}", e),
+            VerificationFailure::PointAtInfinity(e) => write!(f, "point at infinity: {}", e),
+            VerificationFailure::NotInSubgroup(e) => write!(f, "point not in subgroup: {}", e),
+            VerificationFailure::InputNotInField(i) => write!(f, "not in field: input[{}]", i),
+            VerificationFailure::PairingMismatch => write!(f, "pairing check failed"),
+        }
+    }
+}
@@ -0,0 +1,796 @@
+//! `Java_*` exports of the verifiers and primitives for the `com.wavesplatform.zwaves` classes.
+
+use std::{
+    any::Any,
+    io, mem,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+use jni::{
+    errors::{Error as JniCallError, ErrorKind as JniCallErrorKind},
+    objects::{JClass, JObject, JString, JValue},
+    sys::{jboolean, jbyteArray, jint, jlong, jobject, jobjectArray},
+    JNIEnv,
+};
+use lazy_static::lazy_static;
+use pairing::bls12_381::Bls12;
+use pairing_ce::bn256::Bn256;
+
+use crate::{bls12, bn256, diagnostics::VerificationFailure, handles::HandleRegistry};
+
+const ILLEGAL_ARGUMENT_EXCEPTION: &str = "java/lang/IllegalArgumentException";
+const INTERNAL_EXCEPTION: &str = "com/wavesplatform/zwaves/ZWavesInternalException";
+const VERIFICATION_RESULT: &str = "com/wavesplatform/zwaves/VerificationResult";
+
+lazy_static! {
+    static ref BLS12_VKS: HandleRegistry<zwaves_primitives::verifier::PreparedVerifyingKey<Bls12>> =
+        HandleRegistry::new();
+    static ref BN256_VKS: HandleRegistry<bn256::verifier::PreparedVerifyingKey<Bn256>> = HandleRegistry::new();
+    static ref KZG_SETUPS: HandleRegistry<bls12::kzg::TrustedSetup> = HandleRegistry::new();
+    static ref MERKLE_TREES: HandleRegistry<Mutex<bls12::merkle_tree::MerkleTree>> = HandleRegistry::new();
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_verify(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        Ok(bls12::groth16_verify(&vk, &proof, &inputs)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_verifyBatch(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproofs: jobjectArray,
+    jinputs: jobjectArray,
+) -> jint {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proofs = parse_jni_bytes_array(&env, jproofs)?;
+        let inputs = parse_jni_bytes_array(&env, jinputs)?;
+
+        Ok(bls12::groth16_verify_batch(&vk, &proofs, &inputs)?.map_or(-1, |i| i as jint))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_verifyDetailed(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jobject {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        verification_result(&env, bls12::verify_detailed(&vk, &proof, &inputs))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_prepareVerifyingKey(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+
+        Ok(BLS12_VKS.insert(bls12::prepare_vk(&vk)?))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_verifyWithHandle(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let pvk = BLS12_VKS
+            .get(handle)
+            .ok_or_else(|| JniError::InvalidArgument("unknown vk handle".to_string()))?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        Ok(bls12::groth16_verify_prepared(&pvk, &proof, &inputs)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Groth16_release(env: JNIEnv, _class: JClass, handle: jlong) {
+    jni_guard(&env, (), || {
+        BLS12_VKS.remove(handle);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_BlsSig_verify(
+    env: JNIEnv,
+    _class: JClass,
+    jpk: jbyteArray,
+    jmsg: jbyteArray,
+    jsig: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let pk = parse_jni_bytes(&env, jpk)?;
+        let msg = parse_jni_bytes(&env, jmsg)?;
+        let sig = parse_jni_bytes(&env, jsig)?;
+
+        Ok(bls12::bls_sig::verify(&pk, &msg, &sig)? as jboolean)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_BlsSig_aggregateVerify(
+    env: JNIEnv,
+    _class: JClass,
+    jpks: jobjectArray,
+    jmsgs: jobjectArray,
+    jsig: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let pks = parse_jni_bytes_array(&env, jpks)?;
+        let msgs = parse_jni_bytes_array(&env, jmsgs)?;
+        let sig = parse_jni_bytes(&env, jsig)?;
+
+        Ok(bls12::bls_sig::aggregate_verify(&pks, &msgs, &sig)? as jboolean)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_BlsSig_fastAggregateVerify(
+    env: JNIEnv,
+    _class: JClass,
+    jpks: jobjectArray,
+    jmsg: jbyteArray,
+    jsig: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let pks = parse_jni_bytes_array(&env, jpks)?;
+        let msg = parse_jni_bytes(&env, jmsg)?;
+        let sig = parse_jni_bytes(&env, jsig)?;
+
+        Ok(bls12::bls_sig::fast_aggregate_verify(&pks, &msg, &sig)? as jboolean)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Kzg_loadTrustedSetup(
+    env: JNIEnv,
+    _class: JClass,
+    jpath: JString,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let path: String = env.get_string(jpath)?.into();
+
+        Ok(KZG_SETUPS.insert(bls12::kzg::TrustedSetup::load(path)?))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Kzg_verifyProof(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    jcommitment: jbyteArray,
+    jz: jbyteArray,
+    jy: jbyteArray,
+    jproof: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let setup = KZG_SETUPS
+            .get(handle)
+            .ok_or_else(|| JniError::InvalidArgument("unknown trusted setup handle".to_string()))?;
+        let commitment = parse_jni_bytes(&env, jcommitment)?;
+        let z = parse_jni_bytes(&env, jz)?;
+        let y = parse_jni_bytes(&env, jy)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+
+        Ok(bls12::kzg::verify_kzg_proof(&setup, &commitment, &z, &y, &proof)? as jboolean)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Kzg_verifyProofBatch(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    jcommitments: jobjectArray,
+    jzs: jobjectArray,
+    jys: jobjectArray,
+    jproofs: jobjectArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let setup = KZG_SETUPS
+            .get(handle)
+            .ok_or_else(|| JniError::InvalidArgument("unknown trusted setup handle".to_string()))?;
+        let commitments = parse_jni_bytes_array(&env, jcommitments)?;
+        let zs = parse_jni_bytes_array(&env, jzs)?;
+        let ys = parse_jni_bytes_array(&env, jys)?;
+        let proofs = parse_jni_bytes_array(&env, jproofs)?;
+
+        Ok(bls12::kzg::verify_kzg_proof_batch(&setup, &commitments, &zs, &ys, &proofs)? as jboolean)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Kzg_release(env: JNIEnv, _class: JClass, handle: jlong) {
+    jni_guard(&env, (), || {
+        KZG_SETUPS.remove(handle);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_compress(
+    env: JNIEnv,
+    _class: JClass,
+    jleft: jbyteArray,
+    jright: jbyteArray,
+    level: jint,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let left = parse_jni_bytes(&env, jleft)?;
+        let right = parse_jni_bytes(&env, jright)?;
+        if level < 0 {
+            return Err(JniError::InvalidArgument("negative merkle tree level".to_string()));
+        }
+
+        Ok(env.byte_array_from_slice(&bls12::shielded::compress(&left, &right, level as usize)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_merkleRoot(
+    env: JNIEnv,
+    _class: JClass,
+    jsibling: jobjectArray,
+    index: jlong,
+    jleaf: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let sibling = parse_jni_bytes_array(&env, jsibling)?;
+        let index = parse_jni_index(index)?;
+        let leaf = parse_jni_bytes(&env, jleaf)?;
+
+        Ok(env.byte_array_from_slice(&bls12::shielded::merkle_root(&sibling, index, &leaf)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_updateMerkleRootAndProof(
+    env: JNIEnv,
+    _class: JClass,
+    jroot: jbyteArray,
+    jsibling: jobjectArray,
+    index: jlong,
+    jleaves: jobjectArray,
+) -> jobjectArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let root = parse_jni_bytes(&env, jroot)?;
+        let sibling = parse_jni_bytes_array(&env, jsibling)?;
+        let index = parse_jni_index(index)?;
+        let leaves = parse_jni_bytes_array(&env, jleaves)?;
+
+        let (root, proof) = bls12::shielded::update_merkle_root_and_proof(&root, &sibling, index, &leaves)?;
+        let res = std::iter::once(root).chain(proof).collect::<Vec<_>>();
+
+        jni_bytes_array(&env, &res)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_noteHash(
+    env: JNIEnv,
+    _class: JClass,
+    jasset_id: jbyteArray,
+    jamount: jbyteArray,
+    jnative_amount: jbyteArray,
+    jtxid: jbyteArray,
+    jowner: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let asset_id = parse_jni_bytes(&env, jasset_id)?;
+        let amount = parse_jni_bytes(&env, jamount)?;
+        let native_amount = parse_jni_bytes(&env, jnative_amount)?;
+        let txid = parse_jni_bytes(&env, jtxid)?;
+        let owner = parse_jni_bytes(&env, jowner)?;
+
+        let hash = bls12::shielded::note_hash(&asset_id, &amount, &native_amount, &txid, &owner)?;
+        Ok(env.byte_array_from_slice(&hash)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_nullifier(
+    env: JNIEnv,
+    _class: JClass,
+    jnote_hash: jbyteArray,
+    jsk: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let note_hash = parse_jni_bytes(&env, jnote_hash)?;
+        let sk = parse_jni_bytes(&env, jsk)?;
+
+        Ok(env.byte_array_from_slice(&bls12::shielded::nullifier(&note_hash, &sk)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_pubkey(
+    env: JNIEnv,
+    _class: JClass,
+    jsk: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let sk = parse_jni_bytes(&env, jsk)?;
+
+        Ok(env.byte_array_from_slice(&bls12::shielded::pubkey(&sk)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_edh(
+    env: JNIEnv,
+    _class: JClass,
+    jpk: jbyteArray,
+    jsk: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let pk = parse_jni_bytes(&env, jpk)?;
+        let sk = parse_jni_bytes(&env, jsk)?;
+
+        Ok(env.byte_array_from_slice(&bls12::shielded::edh(&pk, &sk)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_encryptNote(
+    env: JNIEnv,
+    _class: JClass,
+    jasset_id: jbyteArray,
+    jamount: jbyteArray,
+    jnative_amount: jbyteArray,
+    jtxid: jbyteArray,
+    jowner: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let asset_id = parse_jni_bytes(&env, jasset_id)?;
+        let amount = parse_jni_bytes(&env, jamount)?;
+        let native_amount = parse_jni_bytes(&env, jnative_amount)?;
+        let txid = parse_jni_bytes(&env, jtxid)?;
+        let owner = parse_jni_bytes(&env, jowner)?;
+
+        let res = bls12::shielded::encrypt_note(&asset_id, &amount, &native_amount, &txid, &owner)?;
+        Ok(env.byte_array_from_slice(&res)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_Shielded_tryDecryptNote(
+    env: JNIEnv,
+    _class: JClass,
+    jdata: jbyteArray,
+    jsk: jbyteArray,
+) -> jobjectArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let data = parse_jni_bytes(&env, jdata)?;
+        let sk = parse_jni_bytes(&env, jsk)?;
+
+        match bls12::shielded::try_decrypt_note(&data, &sk)? {
+            Some(note) => jni_bytes_array(&env, &note),
+            None => Ok(JObject::null().into_inner()),
+        }
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_create(
+    env: JNIEnv,
+    _class: JClass,
+    depth: jint,
+    frontier: jboolean,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let depth = parse_jni_depth(depth)?;
+
+        Ok(MERKLE_TREES.insert(Mutex::new(bls12::merkle_tree::MerkleTree::new(depth, frontier != 0)?)))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_fromFrontier(
+    env: JNIEnv,
+    _class: JClass,
+    depth: jint,
+    size: jlong,
+    jsibling: jobjectArray,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let depth = parse_jni_depth(depth)?;
+        let size = parse_jni_index(size)?;
+        let sibling = parse_jni_bytes_array(&env, jsibling)?;
+
+        let tree = bls12::merkle_tree::MerkleTree::from_frontier(depth, size, &sibling)?;
+        Ok(MERKLE_TREES.insert(Mutex::new(tree)))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_append(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    jleaves: jobjectArray,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let tree = merkle_tree(handle)?;
+        let leaves = parse_jni_bytes_array(&env, jleaves)?;
+
+        let index = tree.lock().unwrap().append(&leaves)?;
+        Ok(index as jlong)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_root(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let root = merkle_tree(handle)?.lock().unwrap().root();
+
+        Ok(env.byte_array_from_slice(&root)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_proof(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    index: jlong,
+) -> jobjectArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let index = parse_jni_index(index)?;
+        let proof = merkle_tree(handle)?.lock().unwrap().proof(index)?;
+
+        jni_bytes_array(&env, &proof)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_size(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jlong {
+    jni_guard(&env, 0, || Ok(merkle_tree(handle)?.lock().unwrap().size() as jlong))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bls12_MerkleTree_release(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    jni_guard(&env, (), || {
+        MERKLE_TREES.remove(handle);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_verify(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        Ok(bn256::groth16_verify(&vk, &proof, &inputs)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_verifyBatch(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproofs: jobjectArray,
+    jinputs: jobjectArray,
+) -> jint {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proofs = parse_jni_bytes_array(&env, jproofs)?;
+        let inputs = parse_jni_bytes_array(&env, jinputs)?;
+
+        Ok(bn256::groth16_verify_batch(&vk, &proofs, &inputs)?.map_or(-1, |i| i as jint))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_validateVerifyingKey(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+) {
+    jni_guard(&env, (), || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+
+        Ok(bn256::validate_vk(&vk)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_verifyDetailed(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jobject {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        verification_result(&env, bn256::verify_detailed(&vk, &proof, &inputs))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_prepareVerifyingKey(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+) -> jlong {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+
+        Ok(BN256_VKS.insert(bn256::prepare_vk(&vk)?))
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_verifyWithHandle(
+    env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let pvk = BN256_VKS
+            .get(handle)
+            .ok_or_else(|| JniError::InvalidArgument("unknown vk handle".to_string()))?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        Ok(bn256::groth16_verify_prepared(&pvk, &proof, &inputs)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Groth16_release(env: JNIEnv, _class: JClass, handle: jlong) {
+    jni_guard(&env, (), || {
+        BN256_VKS.remove(handle);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Plonk_verify(
+    env: JNIEnv,
+    _class: JClass,
+    jvk: jbyteArray,
+    jproof: jbyteArray,
+    jinputs: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let vk = parse_jni_bytes(&env, jvk)?;
+        let proof = parse_jni_bytes(&env, jproof)?;
+        let inputs = parse_jni_bytes(&env, jinputs)?;
+
+        Ok(bn256::plonk::plonk_verify(&vk, &proof, &inputs)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Ops_g1Add(
+    env: JNIEnv,
+    _class: JClass,
+    jinput: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let input = parse_jni_bytes(&env, jinput)?;
+
+        Ok(env.byte_array_from_slice(&bn256::ops::g1_add(&input)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Ops_g1Mul(
+    env: JNIEnv,
+    _class: JClass,
+    jinput: jbyteArray,
+) -> jbyteArray {
+    jni_guard(&env, JObject::null().into_inner(), || {
+        let input = parse_jni_bytes(&env, jinput)?;
+
+        Ok(env.byte_array_from_slice(&bn256::ops::g1_mul(&input)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_wavesplatform_zwaves_bn256_Ops_pairingCheck(
+    env: JNIEnv,
+    _class: JClass,
+    jinput: jbyteArray,
+) -> jboolean {
+    jni_guard(&env, 0, || {
+        let input = parse_jni_bytes(&env, jinput)?;
+
+        Ok(bn256::ops::pairing_check(&input)? as jboolean)
+    })
+}
+
+/// Errors which are rethrown to the JVM as exceptions.
+#[derive(Debug)]
+enum JniError {
+    /// Malformed data passed by the caller, thrown as `IllegalArgumentException`.
+    InvalidArgument(String),
+    /// Everything else, thrown as `ZWavesInternalException`.
+    Internal(String),
+}
+
+impl From<io::Error> for JniError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::InvalidData | io::ErrorKind::InvalidInput | io::ErrorKind::UnexpectedEof => {
+                JniError::InvalidArgument(e.to_string())
+            }
+            _ => JniError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<JniCallError> for JniError {
+    fn from(e: JniCallError) -> Self {
+        match e.kind() {
+            JniCallErrorKind::NullPtr(_) | JniCallErrorKind::NullDeref(_) => JniError::InvalidArgument(e.to_string()),
+            _ => JniError::Internal(e.to_string()),
+        }
+    }
+}
+
+/// Runs `f` turning its errors and panics into Java exceptions, so nothing unwinds across
+/// the FFI boundary. `default` is returned to the JVM whenever an exception was thrown.
+fn jni_guard<T, F>(env: &JNIEnv, default: T, f: F) -> T
+where
+    F: FnOnce() -> Result<T, JniError>,
+{
+    let (class, msg) = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(r)) => return r,
+        Ok(Err(JniError::InvalidArgument(msg))) => (ILLEGAL_ARGUMENT_EXCEPTION, msg),
+        Ok(Err(JniError::Internal(msg))) => (INTERNAL_EXCEPTION, msg),
+        Err(payload) => (INTERNAL_EXCEPTION, panic_message(payload.as_ref())),
+    };
+
+    // A failed JNI call may have left its own exception pending, it should not be replaced
+    if !env.exception_check().unwrap_or(false) {
+        let _ = env.throw_new(class, msg);
+    }
+
+    default
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        format!("panic: {}", msg)
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        format!("panic: {}", msg)
+    } else {
+        "panic".to_string()
+    }
+}
+
+/// Builds a `VerificationResult`, the reason code `0` and a null element stand for a valid proof.
+fn verification_result(env: &JNIEnv, result: Result<(), VerificationFailure>) -> Result<jobject, JniError> {
+    let (reason, element) = match result {
+        Ok(()) => (0, None),
+        Err(failure) => (failure.code(), failure.element()),
+    };
+
+    let element = match element {
+        Some(element) => JObject::from(env.new_string(element.to_string())?),
+        None => JObject::null(),
+    };
+
+    let result = env.new_object(
+        VERIFICATION_RESULT,
+        "(ILjava/lang/String;)V",
+        &[JValue::Int(reason), JValue::Object(element)],
+    )?;
+
+    Ok(result.into_inner())
+}
+
+fn parse_jni_bytes(env: &JNIEnv, jv: jbyteArray) -> Result<Vec<u8>, JniError> {
+    let v_len = env.get_array_length(jv)? as usize;
+    let mut v = vec![0i8; v_len];
+    env.get_byte_array_region(jv, 0, &mut v[..])?;
+
+    unsafe {
+        let ptr = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        mem::forget(v);
+        Ok(Vec::from_raw_parts(ptr as *mut u8, len, cap))
+    }
+}
+
+fn parse_jni_bytes_array(env: &JNIEnv, jv: jobjectArray) -> Result<Vec<Vec<u8>>, JniError> {
+    let v_len = env.get_array_length(jv)?;
+
+    (0..v_len)
+        .map(|i| parse_jni_bytes(env, env.get_object_array_element(jv, i)?.into_inner()))
+        .collect()
+}
+
+fn parse_jni_index(index: jlong) -> Result<u64, JniError> {
+    if index < 0 {
+        return Err(JniError::InvalidArgument("negative index".to_string()));
+    }
+
+    Ok(index as u64)
+}
+
+fn parse_jni_depth(depth: jint) -> Result<usize, JniError> {
+    if depth < 0 {
+        return Err(JniError::InvalidArgument("negative merkle tree depth".to_string()));
+    }
+
+    Ok(depth as usize)
+}
+
+fn merkle_tree(handle: jlong) -> Result<Arc<Mutex<bls12::merkle_tree::MerkleTree>>, JniError> {
+    MERKLE_TREES
+        .get(handle)
+        .ok_or_else(|| JniError::InvalidArgument("unknown merkle tree handle".to_string()))
+}
+
+fn jni_bytes_array(env: &JNIEnv, v: &[Vec<u8>]) -> Result<jobjectArray, JniError> {
+    let res = env.new_object_array(v.len() as jint, "[B", JObject::null())?;
+
+    for (i, e) in v.iter().enumerate() {
+        env.set_object_array_element(res, i as jint, JObject::from(env.byte_array_from_slice(e)?))?;
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,41 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+// Shared by all registries, so a handle of one registry is never valid in another one
+static NEXT_HANDLE: AtomicUsize = AtomicUsize::new(1);
+
+/// Objects owned by the JVM through opaque `long` handles.
+///
+/// Handles are looked up instead of being dereferenced, so a stale or forged handle
+/// is reported as an error rather than crashing the JVM. `0` is never a valid handle.
+pub struct HandleRegistry<T> {
+    items: Mutex<HashMap<i64, Arc<T>>>,
+}
+
+impl<T> HandleRegistry<T> {
+    pub fn new() -> Self {
+        HandleRegistry {
+            items: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn insert(&self, item: T) -> i64 {
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed) as i64;
+        self.items.lock().unwrap().insert(handle, Arc::new(item));
+        handle
+    }
+
+    pub fn get(&self, handle: i64) -> Option<Arc<T>> {
+        self.items.lock().unwrap().get(&handle).cloned()
+    }
+
+    /// Returns `false` if the handle is unknown or already released.
+    pub fn remove(&self, handle: i64) -> bool {
+        self.items.lock().unwrap().remove(&handle).is_some()
+    }
+}
@@ -0,0 +1,13 @@
+pragma circom 2.0.0;
+
+// out = x^3 + x + 5, the circuit of the plonk.rs test vectors
+template Cube() {
+    signal input x;
+    signal output out;
+    signal x2;
+
+    x2 <== x * x;
+    out <== x2 * x + x + 5;
+}
+
+component main = Cube();
@@ -0,0 +1,21 @@
+#!/bin/sh
+# Generates the snarkjs fixture of `bn256::snarkjs::local_tests::snarkjs_plonk_fixture_test`:
+# verification_key.json, proof.json and public.json of cube.circom with x = 3.
+# Needs circom 2 and node, snarkjs 0.7 is run with npx.
+set -e
+cd "$(dirname "$0")"
+
+SNARKJS="npx -y snarkjs@0.7"
+mkdir -p build
+
+circom cube.circom --r1cs --wasm -o build
+$SNARKJS powersoftau new bn128 8 build/pot_0.ptau
+$SNARKJS powersoftau contribute build/pot_0.ptau build/pot_1.ptau -e="zwaves plonk fixture"
+$SNARKJS powersoftau prepare phase2 build/pot_1.ptau build/pot.ptau
+$SNARKJS plonk setup build/cube.r1cs build/pot.ptau build/cube.zkey
+$SNARKJS zkey export verificationkey build/cube.zkey verification_key.json
+
+echo '{"x": "3"}' > build/input.json
+node build/cube_js/generate_witness.js build/cube_js/cube.wasm build/input.json build/witness.wtns
+$SNARKJS plonk prove build/cube.zkey build/witness.wtns proof.json public.json
+$SNARKJS plonk verify verification_key.json public.json proof.json
@@ -0,0 +1,762 @@
+//! Offline model of the `zwaves_demo/ride/zwaves.ride` dApp: the data entries and the WAVES balance of its account,
+//! and the `transferExternal`, `transferInternal` and `utxoAccumulator` calls with the checks, errors and actions
+//! of the contract. Proofs are checked with the `groth16_verify` of the node bindings, so the scenarios of
+//! `zwaves_demo/test.js` run in `cargo test` without a node.
+//!
+//! The receiver of a `ScriptTransfer` is checked as an `Address` of the chain of the dApp by the node: the version,
+//! the chain id and the checksum.
+//!
+//! A failed call changes nothing. Signatures and the fees of the invoke transactions are not modelled.
+
+use std::{collections::BTreeMap, io};
+
+use blake2_rfc::blake2b::blake2b;
+use byteorder::{BigEndian, ByteOrder};
+
+use zwaves_jni::bls12::groth16_verify;
+
+pub const ADDRESS_LEN: usize = 26;
+pub const ADDRESS_VERSION: u8 = 1;
+pub const ADDRESS_CHECKSUM_LEN: usize = 4;
+pub const FR_LEN: usize = 32;
+pub const TRANSFER_INPUTS_LEN: usize = 7 * FR_LEN;
+
+/// Gas cost of a transfer, paid by `transferInternal`.
+pub const TRANSFER_FEE: i64 = 400000;
+/// Gas cost of the accumulation of the outputs, paid by every transfer.
+pub const ACCUMULATOR_FEE: i64 = 400000;
+
+/// Root of the empty UTXO tree, it exists without the `R:` entry.
+pub const EMPTY_ROOT: &str = "4ABQyM1tpHEDkbHes1t7G1F3yyMJXQSvaUR1rqkZqKak";
+
+pub const MESSAGE_NUM_KEY: &str = "MESSAGE_NUM";
+pub const ROOT_NUM_KEY: &str = "ROOT_NUM";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataEntry {
+    Boolean(bool),
+    Binary(Vec<u8>),
+}
+
+/// Attached payment of `transferExternal`, `asset_id` is `None` for WAVES.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payment {
+    pub amount: i64,
+    pub asset_id: Option<Vec<u8>>,
+}
+
+/// WAVES sent from the dApp by a call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptTransfer {
+    pub recipient: Vec<u8>,
+    pub amount: i64,
+}
+
+pub fn utxo_key(utxo: &[u8]) -> String {
+    format!("U:{}", bs58::encode(utxo).into_string())
+}
+
+pub fn root_key(root: &[u8]) -> String {
+    format!("R:{}", bs58::encode(root).into_string())
+}
+
+pub fn nullifier_key(nullifier: &[u8]) -> String {
+    format!("N:{}", bs58::encode(nullifier).into_string())
+}
+
+pub fn message_key(n: u64) -> String {
+    format!("M:{}", n)
+}
+
+pub fn root_value_key(n: u64) -> String {
+    format!("RV:{}", n)
+}
+
+pub fn utxo_message_key(n: u64) -> String {
+    format!("UM:{}", n)
+}
+
+fn error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn overflow() -> io::Error {
+    error("long overflow")
+}
+
+fn int_bytes(n: u64) -> Vec<u8> {
+    let mut res = vec![0u8; 8];
+    BigEndian::write_u64(&mut res, n);
+    res
+}
+
+/// Checksum of the version, the chain id and the public key hash of an address, Keccak-256 of Blake2b-256.
+pub fn address_checksum(data: &[u8]) -> Vec<u8> {
+    tiny_keccak::keccak256(blake2b(32, &[], data).as_bytes())[..ADDRESS_CHECKSUM_LEN].to_vec()
+}
+
+/// The checks of the node for the `Address` of the `chain_id`.
+fn check_address(address: &[u8], chain_id: u8) -> io::Result<()> {
+    if address.len() != ADDRESS_LEN {
+        return Err(error("wrong address length"));
+    }
+    if address[0] != ADDRESS_VERSION {
+        return Err(error("unknown address version"));
+    }
+    if address[1] != chain_id {
+        return Err(error("address of another network"));
+    }
+
+    let (data, checksum) = address.split_at(ADDRESS_LEN - ADDRESS_CHECKSUM_LEN);
+    if address_checksum(data) != checksum {
+        return Err(error("bad address checksum"));
+    }
+    Ok(())
+}
+
+/// The fields of the Transfer inputs read by the contract.
+struct TransferInputs<'a> {
+    receiver: &'a [u8],
+    root_hash: &'a [u8],
+    native_amount: i64,
+    amount: i64,
+    asset_id: i64,
+    out_hash: [&'a [u8]; 2],
+    nf: [&'a [u8]; 2],
+}
+
+impl<'a> TransferInputs<'a> {
+    fn read(v: &'a [u8]) -> io::Result<Self> {
+        if v.len() != TRANSFER_INPUTS_LEN {
+            return Err(error("wrong transfer inputs length"));
+        }
+
+        Ok(TransferInputs {
+            receiver: &v[32 - ADDRESS_LEN..32],
+            root_hash: &v[32..64],
+            native_amount: BigEndian::read_i64(&v[72..80]),
+            amount: BigEndian::read_i64(&v[80..88]),
+            asset_id: BigEndian::read_i64(&v[88..96]),
+            out_hash: [&v[96..128], &v[128..160]],
+            nf: [&v[160..192], &v[192..224]],
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ledger {
+    address: Vec<u8>,
+    transfer_vk: Vec<u8>,
+    utxo_accumulator_vk: Vec<u8>,
+    empty_root: Vec<u8>,
+    data: BTreeMap<String, DataEntry>,
+    balance: i64,
+    transfers: Vec<ScriptTransfer>,
+}
+
+impl Ledger {
+    /// The dApp at the `address` with no data and no WAVES, the verifying keys are truncated ones of `groth16Verify`.
+    /// The `address` is a valid one, its chain id is the chain of the receivers.
+    pub fn new(address: &[u8], transfer_vk: &[u8], utxo_accumulator_vk: &[u8]) -> Self {
+        Ledger {
+            address: address.to_vec(),
+            transfer_vk: transfer_vk.to_vec(),
+            utxo_accumulator_vk: utxo_accumulator_vk.to_vec(),
+            empty_root: bs58::decode(EMPTY_ROOT).into_vec().unwrap(),
+            data: BTreeMap::new(),
+            balance: 0,
+            transfers: vec![],
+        }
+    }
+
+    pub fn address(&self) -> &[u8] {
+        &self.address
+    }
+
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    /// Every `ScriptTransfer` of the successful calls, in order.
+    pub fn transfers(&self) -> &[ScriptTransfer] {
+        &self.transfers
+    }
+
+    pub fn data(&self) -> &BTreeMap<String, DataEntry> {
+        &self.data
+    }
+
+    pub fn get_boolean(&self, key: &str) -> Option<bool> {
+        match self.data.get(key) {
+            Some(DataEntry::Boolean(a)) => Some(*a),
+            _ => None,
+        }
+    }
+
+    pub fn get_binary(&self, key: &str) -> Option<&[u8]> {
+        match self.data.get(key) {
+            Some(DataEntry::Binary(a)) => Some(a),
+            _ => None,
+        }
+    }
+
+    fn flag_exists(&self, key: &str) -> bool {
+        self.get_boolean(key).unwrap_or(false)
+    }
+
+    fn get_int(&self, key: &str) -> u64 {
+        self.get_binary(key).map(BigEndian::read_u64).unwrap_or(0)
+    }
+
+    pub fn nullifier_exists(&self, nullifier: &[u8]) -> bool {
+        self.flag_exists(&nullifier_key(nullifier))
+    }
+
+    pub fn utxo_exists(&self, utxo: &[u8]) -> bool {
+        self.flag_exists(&utxo_key(utxo))
+    }
+
+    pub fn root_exists(&self, root: &[u8]) -> bool {
+        root == self.empty_root.as_slice() || self.flag_exists(&root_key(root))
+    }
+
+    /// Number of the transfers, the index of the next message.
+    pub fn message_num(&self) -> u64 {
+        self.get_int(MESSAGE_NUM_KEY)
+    }
+
+    /// Number of the accumulated messages, the index of the next one.
+    pub fn root_num(&self) -> u64 {
+        self.get_int(ROOT_NUM_KEY)
+    }
+
+    /// Root of the tree with `n` accumulated messages.
+    pub fn root_value(&self, n: u64) -> &[u8] {
+        self.get_binary(&root_value_key(n)).unwrap_or(&self.empty_root)
+    }
+
+    /// Nullifiers of the transfer `n` and its `m`.
+    pub fn message(&self, n: u64) -> Option<&[u8]> {
+        self.get_binary(&message_key(n))
+    }
+
+    /// Output hashes of the transfer `n`.
+    pub fn utxo_message(&self, n: u64) -> Option<&[u8]> {
+        self.get_binary(&utxo_message_key(n))
+    }
+
+    /// `transferExternal` with the attached `payments`, the WAVES payment pays for a deposit.
+    pub fn transfer_external(&mut self, payments: &[Payment], proof: &[u8], v: &[u8], m: &[u8]) -> io::Result<()> {
+        let inputs = TransferInputs::read(v)?;
+
+        if payments.iter().any(|p| p.amount <= 0) {
+            return Err(error("non-positive payment amount"));
+        }
+
+        let payment = match payments {
+            [] => Ok(0),
+            [Payment { amount, asset_id: None }] => Ok(*amount),
+            _ => Err(error("Explicit script termination")),
+        };
+
+        self.transfer(&inputs, payment, ACCUMULATOR_FEE, proof, v, m)
+    }
+
+    /// `transferInternal`, the `caller` should be the dApp itself.
+    pub fn transfer_internal(&mut self, caller: &[u8], proof: &[u8], v: &[u8], m: &[u8]) -> io::Result<()> {
+        if caller != self.address.as_slice() {
+            return Err(error("wrong caller"));
+        }
+
+        let inputs = TransferInputs::read(v)?;
+        self.transfer(&inputs, Ok(0), ACCUMULATOR_FEE + TRANSFER_FEE, proof, v, m)
+    }
+
+    /// The common part of the transfers, `payment` is evaluated after the asset check as in the contract.
+    fn transfer(
+        &mut self,
+        inputs: &TransferInputs,
+        payment: io::Result<i64>,
+        fee: i64,
+        proof: &[u8],
+        v: &[u8],
+        m: &[u8],
+    ) -> io::Result<()> {
+        if inputs.asset_id != 0 || inputs.amount != 0 {
+            return Err(error(
+                "TODO: implement mapping from WAVES assetId into internal u64 asset_id to transfer tokens",
+            ));
+        }
+
+        let payment = payment?;
+        let withdraw = payment
+            .checked_sub(inputs.native_amount)
+            .and_then(|n| n.checked_sub(fee))
+            .ok_or_else(overflow)?;
+
+        if withdraw < 0 {
+            return Err(error(
+                "not enough WAVES to process transaction and positive number of money",
+            ));
+        }
+        if self.nullifier_exists(inputs.nf[0]) || self.nullifier_exists(inputs.nf[1]) {
+            return Err(error("doublespend detected"));
+        }
+        if self.utxo_exists(inputs.out_hash[0]) || self.utxo_exists(inputs.out_hash[1]) {
+            return Err(error("output utxo already exists"));
+        }
+        if !self.root_exists(inputs.root_hash) {
+            return Err(error("root not exists"));
+        }
+        if groth16_verify(&self.transfer_vk, proof, v)? == 0 {
+            return Err(error("wrong proof"));
+        }
+        // the actions are checked after the script
+        check_address(inputs.receiver, self.address[1])?;
+
+        let balance = self
+            .balance
+            .checked_add(payment)
+            .and_then(|n| n.checked_sub(withdraw))
+            .ok_or_else(overflow)?;
+        if balance < 0 {
+            return Err(error("negative waves balance"));
+        }
+
+        let mn = self.message_num();
+        let message = [inputs.nf[0], inputs.nf[1], m].concat();
+        let utxo_message = [inputs.out_hash[0], inputs.out_hash[1]].concat();
+
+        self.data.insert(nullifier_key(inputs.nf[0]), DataEntry::Boolean(true));
+        self.data.insert(nullifier_key(inputs.nf[1]), DataEntry::Boolean(true));
+        self.data.insert(utxo_key(inputs.out_hash[0]), DataEntry::Boolean(true));
+        self.data.insert(utxo_key(inputs.out_hash[1]), DataEntry::Boolean(true));
+        self.data
+            .insert(MESSAGE_NUM_KEY.to_string(), DataEntry::Binary(int_bytes(mn + 1)));
+        self.data.insert(message_key(mn), DataEntry::Binary(message));
+        self.data.insert(utxo_message_key(mn), DataEntry::Binary(utxo_message));
+
+        self.balance = balance;
+        self.transfers.push(ScriptTransfer {
+            recipient: inputs.receiver.to_vec(),
+            amount: withdraw,
+        });
+        Ok(())
+    }
+
+    /// `utxoAccumulator`, adds the outputs of the message `ROOT_NUM` to the tree with the `new_root`.
+    pub fn utxo_accumulator(&mut self, proof: &[u8], new_root: &[u8]) -> io::Result<()> {
+        let rn = self.root_num();
+        let key = utxo_message_key(rn);
+        let utxo_pair = self
+            .get_binary(&key)
+            .ok_or_else(|| error(&format!("value by key '{}' not found", key)))?;
+        let old_root = self.root_value(rn);
+
+        let index = rn.checked_mul(2).ok_or_else(overflow)?;
+        let inputs = [utxo_pair, &[0u8; 24][..], &int_bytes(index)[..], old_root, new_root].concat();
+
+        if groth16_verify(&self.utxo_accumulator_vk, proof, &inputs)? == 0 {
+            return Err(error("wrong proof or data racing case"));
+        }
+
+        self.data
+            .insert(ROOT_NUM_KEY.to_string(), DataEntry::Binary(int_bytes(rn + 1)));
+        self.data
+            .insert(root_value_key(rn + 1), DataEntry::Binary(new_root.to_vec()));
+        self.data.insert(root_key(new_root), DataEntry::Boolean(true));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ledger_tests {
+    use super::*;
+
+    use bellman::groth16::{create_random_proof, generate_random_parameters, Parameters, Proof};
+    use pairing::{
+        bls12_381::{Bls12, Fr, FrRepr},
+        Field, PrimeField, PrimeFieldRepr,
+    };
+    use rand::{os::OsRng, Rng};
+    use sapling_crypto::jubjub::JubjubBls12;
+
+    use zwaves_circuit::{
+        circuit::{Transfer, UtxoAccumulator, MERKLE_PROOF_LEN},
+        prover::{prove_transfer, TransferWitness},
+    };
+    use zwaves_primitives::{
+        merkle_tree::IncrementalMerkleTree,
+        note_encryption::{encrypt_note, try_decrypt_note, NOTE_CIPHERTEXT_LEN},
+        packed_asset::PackedAsset,
+        serialization::{read_fr_repr_be, read_fr_vec},
+        transactions::{note_hash, pubkey, NoteData},
+        verifier::truncate_verifying_key,
+    };
+
+    const DEPOSIT: i64 = 1000000000;
+
+    fn address(tag: u8) -> Vec<u8> {
+        let mut res = vec![ADDRESS_VERSION, b'T'];
+        res.extend_from_slice(&[tag; ADDRESS_LEN - 2 - ADDRESS_CHECKSUM_LEN]);
+        let checksum = address_checksum(&res);
+        res.extend_from_slice(&checksum);
+        res
+    }
+
+    fn fr(n: u64) -> Fr {
+        Fr::from_repr(FrRepr([n, 0, 0, 0])).unwrap()
+    }
+
+    fn fr_from_bytes(data: &[u8]) -> Fr {
+        let mut buf = vec![0u8; FR_LEN - data.len()];
+        buf.extend_from_slice(data);
+        Fr::from_repr(read_fr_repr_be::<Fr>(&buf).unwrap()).unwrap()
+    }
+
+    fn fr_to_bytes(fr: &Fr) -> Vec<u8> {
+        let mut res = vec![];
+        fr.into_repr().write_be(&mut res).unwrap();
+        res
+    }
+
+    fn proof_to_bytes(proof: &Proof<Bls12>) -> Vec<u8> {
+        let mut res = vec![];
+        proof.write(&mut res).unwrap();
+        res
+    }
+
+    fn vk_to_bytes(params: &Parameters<Bls12>) -> Vec<u8> {
+        let mut res = vec![];
+        truncate_verifying_key(&params.vk).write(&mut res).unwrap();
+        res
+    }
+
+    fn note(native_amount: i64, owner: Fr, rng: &mut OsRng) -> NoteData<Bls12> {
+        NoteData {
+            asset_id: Fr::zero(),
+            amount: Fr::zero(),
+            native_amount: fr(native_amount as u64),
+            txid: rng.gen(),
+            owner,
+        }
+    }
+
+    /// Transfer inputs with the amounts and the empty root, its checks fail before the proof.
+    fn inputs(ledger: &Ledger, native_amount: i64) -> Vec<u8> {
+        let mut v = vec![0u8; TRANSFER_INPUTS_LEN];
+        v[32 - ADDRESS_LEN..32].copy_from_slice(&address(1));
+        v[32..64].copy_from_slice(ledger.root_value(0));
+        BigEndian::write_i64(&mut v[72..80], native_amount);
+        for i in 0..4 {
+            v[96 + i * FR_LEN + 31] = i as u8 + 1;
+        }
+        v
+    }
+
+    struct Scenario {
+        jubjub: JubjubBls12,
+        transfer_params: Parameters<Bls12>,
+        accumulator_params: Parameters<Bls12>,
+        tree: IncrementalMerkleTree<Bls12>,
+        ledger: Ledger,
+        rng: OsRng,
+    }
+
+    impl Scenario {
+        fn new() -> Self {
+            let jubjub = JubjubBls12::new();
+            let mut rng = OsRng::new().unwrap();
+
+            let transfer_params = generate_random_parameters(Transfer::<Bls12>::blank(&jubjub), &mut rng).unwrap();
+
+            let accumulator_params =
+                generate_random_parameters(UtxoAccumulator::<Bls12>::blank(&jubjub), &mut rng).unwrap();
+
+            let ledger = Ledger::new(
+                &address(0),
+                &vk_to_bytes(&transfer_params),
+                &vk_to_bytes(&accumulator_params),
+            );
+            let tree = IncrementalMerkleTree::new(MERKLE_PROOF_LEN, &jubjub).unwrap();
+
+            Scenario {
+                jubjub,
+                transfer_params,
+                accumulator_params,
+                tree,
+                ledger,
+                rng,
+            }
+        }
+
+        /// Proof and inputs of the transfer of the `in_note` at the leaves `in_index` of the tree.
+        fn transfer(
+            &mut self,
+            receiver: &[u8],
+            sk: Fr,
+            in_note: [NoteData<Bls12>; 2],
+            in_index: [u64; 2],
+            out_note: [NoteData<Bls12>; 2],
+            packed_asset: PackedAsset,
+        ) -> (Vec<u8>, Vec<u8>) {
+            let witness = TransferWitness {
+                receiver: fr_from_bytes(receiver),
+                in_note,
+                in_proof_sibling: [
+                    self.tree.proof(in_index[0]).unwrap(),
+                    self.tree.proof(in_index[1]).unwrap(),
+                ],
+                in_proof_index: in_index,
+                out_note,
+                root_hash: self.tree.root(),
+                sk,
+                packed_asset: packed_asset.to_fr(),
+            };
+
+            let (proof, inputs) = prove_transfer(&self.transfer_params, &witness, &self.jubjub, &mut self.rng).unwrap();
+            (proof_to_bytes(&proof), inputs.to_bytes())
+        }
+
+        /// Proof and root of the accumulation of the next message, the tree gets its outputs.
+        fn accumulate(&mut self) -> (Vec<u8>, Vec<u8>) {
+            let rn = self.ledger.root_num();
+            let pair = read_fr_vec::<Fr>(self.ledger.utxo_message(rn).unwrap()).unwrap();
+
+            // the siblings of the pair above the leaves are the same before and after it
+            let sibling = self.tree.proof(2 * rn).unwrap()[1..].to_vec();
+            self.tree.append_many(&pair, &self.jubjub).unwrap();
+
+            let c = UtxoAccumulator::<Bls12> {
+                note_hashes: [Some(pair[0]), Some(pair[1])],
+                index: Some(fr(2 * rn)),
+                old_proof: Some(sibling.clone()),
+                new_proof: Some(sibling),
+                params: &self.jubjub,
+            };
+            let proof = create_random_proof(c, &self.accumulator_params, &mut self.rng).unwrap();
+            (proof_to_bytes(&proof), fr_to_bytes(&self.tree.root()))
+        }
+    }
+
+    #[test]
+    fn test_transfer_checks() {
+        let mut ledger = Ledger::new(&address(0), &[0; 15 * 48], &[0; 13 * 48]);
+        let proof = [0u8; 192];
+        let wrong = |res: io::Result<()>| res.unwrap_err().to_string();
+
+        let mut v = inputs(&ledger, -ACCUMULATOR_FEE);
+        v[95] = 1;
+        assert_eq!(
+            wrong(ledger.transfer_external(&[], &proof, &v, &[])),
+            "TODO: implement mapping from WAVES assetId into internal u64 asset_id to transfer tokens"
+        );
+
+        let v = inputs(&ledger, 100);
+        assert_eq!(
+            wrong(ledger.transfer_external(&[], &proof, &v, &[])),
+            "not enough WAVES to process transaction and positive number of money"
+        );
+        let payment = Payment {
+            amount: 100 + ACCUMULATOR_FEE,
+            asset_id: Some(vec![1; 32]),
+        };
+        assert_eq!(
+            wrong(ledger.transfer_external(&[payment], &proof, &v, &[])),
+            "Explicit script termination"
+        );
+
+        let v = inputs(&ledger, -ACCUMULATOR_FEE);
+        assert_eq!(
+            wrong(ledger.transfer_internal(&address(1), &proof, &v, &[])),
+            "wrong caller"
+        );
+        assert_eq!(
+            wrong(ledger.transfer_internal(&address(0), &proof, &v, &[])),
+            "not enough WAVES to process transaction and positive number of money"
+        );
+
+        let mut v = inputs(&ledger, -ACCUMULATOR_FEE);
+        v[63] ^= 1;
+        assert_eq!(wrong(ledger.transfer_external(&[], &proof, &v, &[])), "root not exists");
+
+        let v = inputs(&ledger, i64::MIN);
+        assert_eq!(wrong(ledger.transfer_external(&[], &proof, &v, &[])), "long overflow");
+
+        // malformed keys and proofs are errors of groth16Verify
+        let v = inputs(&ledger, -ACCUMULATOR_FEE);
+        assert!(ledger.transfer_external(&[], &proof, &v, &[]).is_err());
+        assert!(ledger.transfer_external(&[], &proof, &v[1..], &[]).is_err());
+
+        let empty_root = ledger.root_value(0).to_vec();
+        assert_eq!(
+            wrong(ledger.utxo_accumulator(&proof, &empty_root)),
+            "value by key 'UM:0' not found"
+        );
+
+        assert!(ledger.data().is_empty(), "failed calls should not change the state");
+        assert_eq!(ledger.message_num(), 0);
+        assert_eq!(ledger.root_num(), 0);
+        assert!(ledger.root_exists(&empty_root));
+    }
+
+    #[test]
+    fn test_deposit_transfer_withdraw() {
+        let mut s = Scenario::new();
+        let (sk_a, sk_b): (Fr, Fr) = (s.rng.gen(), s.rng.gen());
+        let (pk_a, pk_b) = (pubkey::<Bls12>(&sk_a, &s.jubjub), pubkey::<Bls12>(&sk_b, &s.jubjub));
+        let (address_a, address_b) = (address(1), address(2));
+
+        // Alice deposits, the inputs of zero value need no merkle proofs
+        let deposit = PackedAsset::deposit(DEPOSIT as u64, ACCUMULATOR_FEE as u64).unwrap();
+        let in_note = [note(0, pk_a, &mut s.rng), note(0, pk_a, &mut s.rng)];
+        let deposit_note = [note(deposit.native_amount, pk_a, &mut s.rng), note(0, pk_a, &mut s.rng)];
+        let (proof, v) = s.transfer(&address_a, sk_a, in_note, [0, 0], deposit_note.clone(), deposit);
+        let payment = Payment {
+            amount: DEPOSIT,
+            asset_id: None,
+        };
+
+        assert_eq!(
+            s.ledger
+                .transfer_external(&[], &proof, &v, &[])
+                .unwrap_err()
+                .to_string(),
+            "not enough WAVES to process transaction and positive number of money"
+        );
+        s.ledger.transfer_external(&[payment.clone()], &proof, &v, &[]).unwrap();
+        assert_eq!(s.ledger.message_num(), 1);
+        assert_eq!(s.ledger.balance(), DEPOSIT);
+        assert_eq!(
+            s.ledger.transfers()[0],
+            ScriptTransfer {
+                recipient: address_a.clone(),
+                amount: 0
+            }
+        );
+        assert_eq!(
+            s.ledger
+                .transfer_external(&[payment], &proof, &v, &[])
+                .unwrap_err()
+                .to_string(),
+            "doublespend detected"
+        );
+
+        let (proof, new_root) = s.accumulate();
+        let mut wrong_root = new_root.clone();
+        wrong_root[31] ^= 1;
+        assert_eq!(
+            s.ledger.utxo_accumulator(&proof, &wrong_root).unwrap_err().to_string(),
+            "wrong proof or data racing case"
+        );
+        s.ledger.utxo_accumulator(&proof, &new_root).unwrap();
+        assert_eq!(s.ledger.root_num(), 1);
+        assert!(s.ledger.root_exists(&new_root));
+
+        // Alice sends the deposit to Bob, the dApp calls transferInternal itself
+        let fee = ACCUMULATOR_FEE + TRANSFER_FEE;
+        let bob_note = note(deposit.native_amount - fee, pk_b, &mut s.rng);
+        let out_note = [bob_note.clone(), note(0, pk_a, &mut s.rng)];
+        let packed_asset = PackedAsset::transfer(fee as u64).unwrap();
+        let dapp = s.ledger.address().to_vec();
+        let (proof, v) = s.transfer(&address_b, sk_a, deposit_note, [0, 1], out_note, packed_asset);
+
+        let m = encrypt_note(&bob_note, &mut s.rng, &s.jubjub).unwrap();
+        assert_eq!(
+            s.ledger
+                .transfer_internal(&address_a, &proof, &v, &m)
+                .unwrap_err()
+                .to_string(),
+            "wrong caller"
+        );
+        s.ledger.transfer_internal(&dapp, &proof, &v, &m).unwrap();
+        assert_eq!(s.ledger.balance(), DEPOSIT);
+
+        // Bob finds the note in the message queue
+        let message = s.ledger.message(1).unwrap();
+        assert_eq!(&message[..2 * FR_LEN], &v[160..224]);
+        let found = try_decrypt_note::<Bls12>(&message[2 * FR_LEN..], &sk_b, &s.jubjub).unwrap();
+        assert!(note_hash(&found, &s.jubjub) == note_hash(&bob_note, &s.jubjub));
+        assert_eq!(message.len(), 2 * FR_LEN + NOTE_CIPHERTEXT_LEN);
+
+        let (proof, new_root) = s.accumulate();
+        s.ledger.utxo_accumulator(&proof, &new_root).unwrap();
+
+        // Bob withdraws all, the accumulation is paid from the note
+        let amount = deposit.native_amount - fee - ACCUMULATOR_FEE;
+        let in_note = [bob_note, note(0, pk_b, &mut s.rng)];
+        let out_note = [note(0, pk_b, &mut s.rng), note(0, pk_b, &mut s.rng)];
+        let packed_asset = PackedAsset::withdraw(amount as u64, ACCUMULATOR_FEE as u64).unwrap();
+        let (proof, v) = s.transfer(&address_b, sk_b, in_note, [2, 0], out_note, packed_asset);
+
+        let mut wrong_receiver = v.clone();
+        wrong_receiver[31] ^= 1;
+        assert_eq!(
+            s.ledger
+                .transfer_external(&[], &proof, &wrong_receiver, &[])
+                .unwrap_err()
+                .to_string(),
+            "wrong proof"
+        );
+        s.ledger.transfer_external(&[], &proof, &v, &[]).unwrap();
+        assert_eq!(
+            s.ledger.transfers()[2],
+            ScriptTransfer {
+                recipient: address_b,
+                amount
+            }
+        );
+        assert_eq!(s.ledger.balance(), DEPOSIT - amount);
+        assert_eq!(
+            s.ledger.balance(),
+            3 * ACCUMULATOR_FEE + TRANSFER_FEE,
+            "the fees stay in the dApp"
+        );
+
+        let (proof, new_root) = s.accumulate();
+        s.ledger.utxo_accumulator(&proof, &new_root).unwrap();
+        assert_eq!(s.ledger.root_num(), s.ledger.message_num());
+        assert_eq!(s.ledger.root_value(3), &new_root[..]);
+    }
+
+    #[test]
+    fn test_invalid_receiver() {
+        let mut s = Scenario::new();
+        let sk: Fr = s.rng.gen();
+        let pk = pubkey::<Bls12>(&sk, &s.jubjub);
+        let deposit = PackedAsset::deposit(DEPOSIT as u64, ACCUMULATOR_FEE as u64).unwrap();
+        let payment = Payment {
+            amount: DEPOSIT,
+            asset_id: None,
+        };
+
+        let mut bad_checksum = address(1);
+        bad_checksum[ADDRESS_LEN - 1] ^= 1;
+        let mut other_network = vec![ADDRESS_VERSION, b'W'];
+        other_network.extend_from_slice(&[1; ADDRESS_LEN - 2 - ADDRESS_CHECKSUM_LEN]);
+        let checksum = address_checksum(&other_network);
+        other_network.extend_from_slice(&checksum);
+
+        for (receiver, msg) in [
+            (bad_checksum, "bad address checksum"),
+            (other_network, "address of another network"),
+        ]
+        .iter()
+        {
+            let in_note = [note(0, pk, &mut s.rng), note(0, pk, &mut s.rng)];
+            let out_note = [note(deposit.native_amount, pk, &mut s.rng), note(0, pk, &mut s.rng)];
+            let (proof, v) = s.transfer(receiver, sk, in_note, [0, 0], out_note, deposit);
+            assert_eq!(
+                s.ledger
+                    .transfer_external(&[payment.clone()], &proof, &v, &[])
+                    .unwrap_err()
+                    .to_string(),
+                *msg
+            );
+        }
+
+        assert!(s.ledger.data().is_empty(), "failed calls should not change the state");
+        assert_eq!(s.ledger.balance(), 0);
+        assert!(s.ledger.transfers().is_empty());
+    }
+}
@@ -75,8 +75,11 @@ function merkleHash(a, b, l) {
     return debufferizeBigints(native.merkle_hash(bufferizeBigints(a), bufferizeBigints(b), l));
 }
 
-function utxoAccumulator(mpc_params, data) {
-    const proof = native.utxo_accumulator(mpc_params, bufferizeBigints(data));
+// Parses the MPC parameters once, the result is passed to utxoAccumulator and transfer
+const loadParams = (mpc_params) => new native.Params(mpc_params);
+
+function utxoAccumulator(params, data) {
+    const proof = native.utxo_accumulator(params, bufferizeBigints(data));
     const pair_hash = merkleHash(data.note_hashes[0], data.note_hashes[1], 0);
     const zero_hash = merkleHash(0n, 0n, 0);
     
@@ -88,11 +91,9 @@ function utxoAccumulator(mpc_params, data) {
 const nullifier = (hash, sk) => debufferizeBigints(native.nullifier(bufferizeBigints(hash), bufferizeBigints(sk)));
 
 
-function transfer(mpc_params, data) {
-    const proof = native.transfer(mpc_params, bufferizeBigints(data));
-    const nf = data.in_note.map(n => nullifier(note_hash(n), data.sk));
-    const out_hash = data.out_note.map(n=>note_hash(n));
-    const publicInputs = [data.receiver, data.root_hash, data.packed_asset, ...out_hash, ...nf];
+function transfer(params, data) {
+    const {proof, inputs} = native.transfer(params, bufferizeBigints(data));
+    const publicInputs = debufferizeBigints(inputs);
     return {proof, publicInputs};
 }
 
@@ -122,50 +123,42 @@ for (let i = 1; i < maxheight; i++) {
 
 
 class MerkleTree{
-    constructor(proof_length) {
+    // frontier: true keeps only the proof of the next leaf, {size, sibling} resumes it from that proof
+    constructor(proof_length, frontier) {
         assert(proof_length < maxheight, `height should be less or equal ${maxheight}`);
         this.proof_length = proof_length;
-        this._merkleState = Array(proof_length+1).fill(0).map(() => []);
-    }
-
-    cell(row, index) {
-        index = BigInt(index);
-        assert(row <= this.proof_length, "too big row");
-        if (index < this.size(row)) {
-            return this._merkleState[row][index];
+        if (typeof frontier === "undefined") {
+            this._tree = new native.MerkleTree(proof_length);
+        } else if (typeof frontier === "boolean") {
+            this._tree = new native.MerkleTree(proof_length, frontier);
         } else {
-            return merkleDefaults[row];
+            this._tree = new native.MerkleTree(proof_length, {size: Number(frontier.size), sibling: bufferizeBigints(frontier.sibling)});
         }
     }
 
-    size(n) {
-        n = typeof n === "undefined" ? 0 : n;
-        return BigInt(this._merkleState[n].length);
+    size() {
+        return BigInt(this._tree.size());
+    }
+
+    isFrontier() {
+        return this._tree.isFrontier();
+    }
+
+    push(element) {
+        return this.pushMany([element]);
     }
 
     pushMany(elements) {
-        let index = this.size();
-        let s = BigInt(elements.length);
-        this._merkleState[0].push(...elements);
-
-        for (let i = 1; i<= this.proof_length; i++) {
-            let rl = this.size(i);
-            this._merkleState[i].push(...Array(parseInt(1n + (index+s>>BigInt(i)) - rl)).fill(0n));
-            
-            for (let j = (index >> BigInt(i)); j <= (index+s>>BigInt(i)); j++) {
-                this._merkleState[i][j] = merkleHash(this.cell(i-1, j*2n), this.cell(i-1, j*2n+1n), i-1);
-            }
-        }
+        return BigInt(this._tree.append(bufferizeBigints(elements)));
     }
 
     root() {
-        return this.cell(this.proof_length, 0n)
+        return debufferizeBigints(this._tree.root());
     }
 
     proof(index, offset) {
-        index = BigInt(index);
         offset = typeof offset === "undefined" ? 0 : offset;
-        return Array(this.proof_length).fill(0).map((o, i) => this.cell(i, (index >> BigInt(i))^1n)).slice(offset);
+        return debufferizeBigints(this._tree.proof(Number(index))).slice(offset);
     }
 
     static getRoot(proof, index, leaf, offset) {
@@ -182,6 +175,6 @@ class MerkleTree{
 
 
 
-module.exports = {MerkleTree, merkleDefaults, merkleHash, utxoAccumulator, verify, fr_random, fs_random, u64_random, fr_order, fs_order, extract_vk, u32_random, note_hash, pubkey,
+module.exports = {MerkleTree, merkleDefaults, merkleHash, utxoAccumulator, verify, fr_random, fs_random, u64_random, fr_order, fs_order, extract_vk, loadParams, u32_random, note_hash, pubkey,
     randrange, nullifier, transfer, bufferizeBigints, debufferizeBigints}; 
 
@@ -32,7 +32,7 @@ lazy_static! {
 }
 
 
-pub fn buf_copy_from_slice(cx: &FunctionContext, source: &[u8], buf: &mut Handle<JsBuffer>) {
+pub fn buf_copy_from_slice<'a, C: Context<'a>>(cx: &C, source: &[u8], buf: &mut Handle<JsBuffer>) {
     cx.borrow_mut(buf, |data| {
         data.as_mut_slice().copy_from_slice(source);
     });
@@ -43,7 +43,7 @@ pub fn read_obj_fr(cx: &mut FunctionContext, obj: Handle<JsObject>, key: &str) -
     read_val_fr(cx, value)
 }
 
-pub fn read_val_fr(cx: &mut FunctionContext, val: Handle<JsValue>) -> NeonResult<Fr> {
+pub fn read_val_fr<'a, C: Context<'a>>(cx: &mut C, val: Handle<JsValue>) -> NeonResult<Fr> {
     let buff_field = val.downcast::<JsBuffer>().or_else(|_| cx.throw_error("could not downcast value to Buffer"))?;
     let buff_field_slice = cx.borrow(&buff_field, |data| data.as_slice());
     let repr = read_fr_repr_be::<Fr>(buff_field_slice).or_else(|_| cx.throw_error("Buffer must be uint256 BE number"))?;
@@ -67,7 +67,7 @@ pub fn parse_note_data(cx: &mut FunctionContext, note_obj:Handle<JsObject>) -> N
     })
 }
 
-pub fn fr_to_js<'a>(cx: &mut FunctionContext<'a>, fr: &Fr) -> JsResult<'a, JsBuffer> {
+pub fn fr_to_js<'a, C: Context<'a>>(cx: &mut C, fr: &Fr) -> JsResult<'a, JsBuffer> {
     let mut buff = Cursor::new(Vec::<u8>::new());
     fr.into_repr().write_be(&mut buff).unwrap();
 
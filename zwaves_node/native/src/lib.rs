@@ -10,6 +10,8 @@ extern crate phase2;
 extern crate rand;
 
 pub mod helpers;
+pub mod merkle_tree;
+pub mod params;
 
 use neon::prelude::*;
 
@@ -30,7 +32,8 @@ use sapling_crypto::pedersen_hash::{Personalization};
 use sapling_crypto::circuit::{pedersen_hash};
 use sapling_crypto::circuit::num::{AllocatedNum, Num};
 use bellman::groth16::{Proof, generate_random_parameters, prepare_verifying_key, create_random_proof, verify_proof};
-use zwaves_circuit::circuit::{Transfer, MERKLE_PROOF_LEN, UtxoAccumulator};
+use zwaves_circuit::circuit::{MERKLE_PROOF_LEN, UtxoAccumulator};
+use zwaves_circuit::prover::{prove_transfer, TransferWitness};
 use zwaves_primitives::transactions::NoteData;
 use zwaves_primitives::fieldtools::fr_to_repr_bool;
 use zwaves_primitives::serialization::read_fr_repr_be;
@@ -38,6 +41,7 @@ use zwaves_primitives::verifier;
 use arrayvec::ArrayVec;
 
 use crate::helpers::*;
+use crate::params::params_argument;
 
 
 pub fn extract_vk(mut cx: FunctionContext) -> JsResult<JsBuffer> {
@@ -110,78 +114,87 @@ pub fn edh(mut cx: FunctionContext) ->JsResult<JsBuffer> {
 
 
 
-pub fn parse_transfer(cx: &mut FunctionContext, transfer_obj:Handle<JsObject>) -> NeonResult<Transfer<'static, Bls12>> {
+pub fn parse_transfer(cx: &mut FunctionContext, transfer_obj:Handle<JsObject>) -> NeonResult<TransferWitness<Bls12>> {
 
     let in_note = transfer_obj.get(cx, "in_note")?;
     let in_note = parse_pair::<JsObject>(cx, in_note)?;
     let in_note = in_note.iter().map(|&item| {
-        parse_note_data(cx, item).map(|e| Some(e))
-    }).collect::<NeonResult<ArrayVec<[Option<NoteData<Bls12>>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse in_note"))?;
+        parse_note_data(cx, item)
+    }).collect::<NeonResult<ArrayVec<[NoteData<Bls12>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse in_note"))?;
     
     
 
     let out_note = transfer_obj.get(cx, "out_note")?;
     let out_note = parse_pair::<JsObject>(cx, out_note)?;
     let out_note = out_note.iter().map(|&item| {
-        parse_note_data(cx, item).map(|e| Some(e))
-    }).collect::<NeonResult<ArrayVec<[Option<NoteData<Bls12>>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse out_note"))?;
+        parse_note_data(cx, item)
+    }).collect::<NeonResult<ArrayVec<[NoteData<Bls12>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse out_note"))?;
     
 
     let in_index = transfer_obj.get(cx, "in_proof_index")?;
     let in_index = parse_pair::<JsBuffer>(cx, in_index)?;
     let in_index = in_index.iter().map(|&item| {
-        read_buf_fr(cx, item)
-    }).collect::<NeonResult<ArrayVec<[Fr;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse in_proof_index"))?;
+        let index = read_buf_fr(cx, item)?;
+        if fr_to_repr_bool::<Fr>(&index).into_iter().skip(MERKLE_PROOF_LEN).any(|e| e) {
+            return cx.throw_error("Index value should not be bigger than 2^MERKLE_PROOF_LEN");
+        }
+        Ok(index.into_repr().as_ref()[0])
+    }).collect::<NeonResult<ArrayVec<[u64;2]>>>()?.into_inner().or_else(|_| cx.throw_error("Could not parse in_proof_index"))?;
     
 
     let in_proof = transfer_obj.get(cx, "in_proof_sibling")?;
     let in_proof = parse_pair::<JsArray>(cx, in_proof)?;
 
-    let in_proof = in_proof.iter().zip(in_index.iter()).map(|(&item, &index)| {
+    let in_proof = in_proof.iter().map(|&item| {
         let item = item.to_vec(cx)?;
         if item.len() != MERKLE_PROOF_LEN {
             return cx.throw_error(format!("Merkle proof length should be {}.", MERKLE_PROOF_LEN));
         }
 
-        if fr_to_repr_bool::<Fr>(&index).into_iter().skip(MERKLE_PROOF_LEN).any(|e| e) {
-            return cx.throw_error("Index value should not be bigger than 2^MERKLE_PROOF_LEN");
-        }
-
-        let item = item.into_iter().zip(fr_to_repr_bool::<Fr>(&index)).map(|(e, b)| (read_val_fr(cx, e).unwrap(), b)).collect::<Vec<(Fr, bool)>>();
-        Ok(Some(item))
-    }).collect::<NeonResult<ArrayVec<[Option<Vec<(Fr, bool)>>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("in_proof_sibling.length should be 2"))?;
+        item.into_iter().map(|e| read_val_fr(cx, e)).collect::<NeonResult<Vec<Fr>>>()
+    }).collect::<NeonResult<ArrayVec<[Vec<Fr>;2]>>>()?.into_inner().or_else(|_| cx.throw_error("in_proof_sibling.length should be 2"))?;
 
-    let root_hash = Some(read_obj_fr(cx, transfer_obj, "root_hash")?);
-    let sk = Some(read_obj_fr(cx, transfer_obj, "sk")?);
-    let packed_asset = Some(read_obj_fr(cx, transfer_obj, "packed_asset")?);
-    let receiver = Some(read_obj_fr(cx, transfer_obj, "receiver")?);
+    let root_hash = read_obj_fr(cx, transfer_obj, "root_hash")?;
+    let sk = read_obj_fr(cx, transfer_obj, "sk")?;
+    let packed_asset = read_obj_fr(cx, transfer_obj, "packed_asset")?;
+    let receiver = read_obj_fr(cx, transfer_obj, "receiver")?;
 
 
-    Ok(Transfer {
+    Ok(TransferWitness {
         receiver,
         in_note,
+        in_proof_sibling: in_proof,
+        in_proof_index: in_index,
         out_note,
-        in_proof,
         root_hash,
         sk,
-        packed_asset,
-        params: &JUBJUB_PARAMS
+        packed_asset
     })
 }
 
 
-pub fn transfer(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+pub fn transfer(mut cx: FunctionContext) -> JsResult<JsObject> {
     let mut rng = OsRng::new().unwrap();
-    let mpc_params_buff : Handle<JsBuffer> = cx.argument(0)?;
-    let mpc_params_slice = cx.borrow(&mpc_params_buff, |data| data.as_slice());
+    let params = params_argument(&mut cx, 0)?;
 
     let transfer_obj : Handle<JsObject> = cx.argument(1)?;
-    let params = phase2::MPCParameters::read(mpc_params_slice, false).or_else(|_| cx.throw_error("Could not read mpc params"))?;
 
-    let c = parse_transfer(&mut cx, transfer_obj)?;
-    let proof = create_random_proof(c, params.get_params(), &mut rng).or_else(|_| cx.throw_error("Could not create proof"))?;
+    let witness = parse_transfer(&mut cx, transfer_obj)?;
+    let (proof, inputs) = prove_transfer(&params, &witness, &JUBJUB_PARAMS, &mut rng).or_else(|_| cx.throw_error("Could not create proof"))?;
 
-    proof_to_js(&mut cx, &proof)
+    let res = JsObject::new(&mut cx);
+    let proof = proof_to_js(&mut cx, &proof)?;
+    res.set(&mut cx, "proof", proof)?;
+
+    let inputs = inputs.to_vec();
+    let inputs_js = JsArray::new(&mut cx, inputs.len() as u32);
+    for (i, e) in inputs.iter().enumerate() {
+        let e = fr_to_js(&mut cx, e)?;
+        inputs_js.set(&mut cx, i as u32, e)?;
+    }
+    res.set(&mut cx, "inputs", inputs_js)?;
+
+    Ok(res)
 }
 
 
@@ -234,14 +247,12 @@ pub fn merkle_hash(mut cx: FunctionContext) -> JsResult<JsBuffer> {
 
 pub fn utxo_accumulator(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     let mut rng = OsRng::new().unwrap();
-    let mpc_params_buff : Handle<JsBuffer> = cx.argument(0)?;
-    let mpc_params_slice = cx.borrow(&mpc_params_buff, |data| data.as_slice());
+    let params = params_argument(&mut cx, 0)?;
 
     let acc_obj : Handle<JsObject> = cx.argument(1)?;
-    let params = phase2::MPCParameters::read(mpc_params_slice, false).unwrap();
 
     let c = parse_utxo_accumulator(&mut cx, acc_obj)?;
-    let proof = create_random_proof(c, params.get_params(), &mut rng).unwrap();
+    let proof = create_random_proof(c, &params, &mut rng).unwrap();
 
     proof_to_js(&mut cx, &proof)
 }
@@ -256,6 +267,8 @@ register_module!(mut cx, {
     cx.export_function("nullifier", nullifier)?;
     cx.export_function("edh", edh)?;
     cx.export_function("pubkey", pubkey)?;
-    cx.export_function("note_hash", note_hash)
+    cx.export_function("note_hash", note_hash)?;
+    cx.export_class::<merkle_tree::JsMerkleTree>("MerkleTree")?;
+    cx.export_class::<params::JsParams>("Params")
     
 });
@@ -0,0 +1,125 @@
+use neon::prelude::*;
+
+use pairing::bls12_381::{Fr, Bls12};
+
+use zwaves_primitives::merkle_tree::IncrementalMerkleTree;
+
+use crate::helpers::*;
+
+
+pub type MerkleTree = IncrementalMerkleTree<Bls12>;
+
+
+fn read_u64<'a, C: Context<'a>>(cx: &mut C, val: Handle<JsValue>) -> NeonResult<u64> {
+    let n = val.downcast::<JsNumber>().or_else(|_| cx.throw_error("could not downcast value to Number"))?.value();
+    if n.fract() != 0.0 || n < 0.0 || n > 9007199254740991.0 {
+        return cx.throw_error("Number should be a nonnegative safe integer");
+    }
+    Ok(n as u64)
+}
+
+fn read_fr_array<'a, C: Context<'a>>(cx: &mut C, val: Handle<JsValue>) -> NeonResult<Vec<Fr>> {
+    let items = val.downcast::<JsArray>().or_else(|_| cx.throw_error("could not downcast value to Array"))?.to_vec(cx)?;
+    items.into_iter().map(|e| read_val_fr(cx, e)).collect()
+}
+
+
+declare_types! {
+    // `new MerkleTree(depth)` keeps all the nodes, `new MerkleTree(depth, true)` only the frontier and
+    // `new MerkleTree(depth, {size, sibling})` resumes a frontier from the proof of the leaf at the index `size`.
+    pub class JsMerkleTree for MerkleTree {
+        init(mut cx) {
+            let depth = cx.argument::<JsValue>(0)?;
+            let depth = read_u64(&mut cx, depth)? as usize;
+
+            let frontier = cx.argument_opt(1);
+            let res = match frontier {
+                None => MerkleTree::new(depth, &JUBJUB_PARAMS),
+                Some(frontier) => {
+                    if let Ok(compact) = frontier.downcast::<JsBoolean>() {
+                        if compact.value() { MerkleTree::new_frontier(depth, &JUBJUB_PARAMS) } else { MerkleTree::new(depth, &JUBJUB_PARAMS) }
+                    } else {
+                        let frontier = frontier.downcast::<JsObject>().or_else(|_| cx.throw_error("frontier should be a Boolean or an Object"))?;
+                        let size = frontier.get(&mut cx, "size")?;
+                        let size = read_u64(&mut cx, size)?;
+                        let sibling = frontier.get(&mut cx, "sibling")?;
+                        let sibling = read_fr_array(&mut cx, sibling)?;
+
+                        MerkleTree::from_frontier(depth, size, &sibling, &JUBJUB_PARAMS)
+                    }
+                }
+            };
+
+            res.or_else(|e| cx.throw_error(e.to_string()))
+        }
+
+        // Appends an Array of leaves and returns the index of the first one.
+        method append(mut cx) {
+            let leaves = cx.argument::<JsValue>(0)?;
+            let leaves = read_fr_array(&mut cx, leaves)?;
+
+            let mut this = cx.this();
+            let res = {
+                let guard = cx.lock();
+                let mut tree = this.borrow_mut(&guard);
+                tree.append_many(&leaves, &JUBJUB_PARAMS)
+            };
+
+            let index = res.or_else(|e| cx.throw_error(e.to_string()))?;
+            Ok(cx.number(index as f64).upcast())
+        }
+
+        method root(mut cx) {
+            let this = cx.this();
+            let root = {
+                let guard = cx.lock();
+                let tree = this.borrow(&guard);
+                tree.root()
+            };
+
+            Ok(fr_to_js(&mut cx, &root)?.upcast())
+        }
+
+        method proof(mut cx) {
+            let index = cx.argument::<JsValue>(0)?;
+            let index = read_u64(&mut cx, index)?;
+
+            let this = cx.this();
+            let res = {
+                let guard = cx.lock();
+                let tree = this.borrow(&guard);
+                tree.proof(index)
+            };
+            let proof = res.or_else(|e| cx.throw_error(e.to_string()))?;
+
+            let proof_js = JsArray::new(&mut cx, proof.len() as u32);
+            for (i, e) in proof.iter().enumerate() {
+                let e = fr_to_js(&mut cx, e)?;
+                proof_js.set(&mut cx, i as u32, e)?;
+            }
+            Ok(proof_js.upcast())
+        }
+
+        method size(mut cx) {
+            let this = cx.this();
+            let size = {
+                let guard = cx.lock();
+                let tree = this.borrow(&guard);
+                tree.size()
+            };
+
+            Ok(cx.number(size as f64).upcast())
+        }
+
+        method isFrontier(mut cx) {
+            let this = cx.this();
+            let res = {
+                let guard = cx.lock();
+                let tree = this.borrow(&guard);
+                tree.is_frontier()
+            };
+
+            Ok(cx.boolean(res).upcast())
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use neon::prelude::*;
+
+use bellman::groth16::Parameters;
+use pairing::bls12_381::Bls12;
+use phase2::MPCParameters;
+
+
+pub type Params = Parameters<Bls12>;
+
+
+declare_types! {
+    // `new Params(mpc_params)` parses the MPC parameters once, the provers take it instead of the buffer.
+    pub class JsParams for Params {
+        init(mut cx) {
+            let mpc_params_buff : Handle<JsBuffer> = cx.argument(0)?;
+            let mpc_params_slice = cx.borrow(&mpc_params_buff, |data| data.as_slice());
+
+            let params = MPCParameters::read(mpc_params_slice, false).or_else(|_| cx.throw_error("Could not read mpc params"))?;
+            Ok(params.get_params().clone())
+        }
+    }
+}
+
+
+/// The parameters of the `Params` argument `i`, the points are shared with it.
+pub fn params_argument(cx: &mut FunctionContext, i: i32) -> NeonResult<Params> {
+    let params = cx.argument::<JsParams>(i)?;
+    let guard = cx.lock();
+    let res = params.borrow(&guard).clone();
+    Ok(res)
+}
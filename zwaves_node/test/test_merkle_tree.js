@@ -0,0 +1,28 @@
+const {MerkleTree, fr_random} = require("../lib/index.js");
+const assert = require("assert");
+
+let mt = new MerkleTree(48);
+let frontier = new MerkleTree(48, true);
+
+let leaves = Array(37).fill(0).map(()=>fr_random());
+
+mt.pushMany(leaves.slice(0, 10));
+frontier.pushMany(leaves.slice(0, 10));
+mt.push(leaves[10]);
+frontier.push(leaves[10]);
+
+let resumed = new MerkleTree(48, {size: frontier.size(), sibling: frontier.proof(frontier.size())});
+assert(resumed.isFrontier());
+
+mt.pushMany(leaves.slice(11));
+frontier.pushMany(leaves.slice(11));
+resumed.pushMany(leaves.slice(11));
+
+assert.equal(mt.size(), 37n);
+assert.equal(mt.root(), frontier.root());
+assert.equal(mt.root(), resumed.root());
+
+leaves.forEach((leaf, i) => assert.equal(MerkleTree.getRoot(mt.proof(i), i, leaf), mt.root()));
+assert.deepEqual(mt.proof(37), frontier.proof(37));
+
+console.log("ok");
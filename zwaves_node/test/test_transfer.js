@@ -1,5 +1,5 @@
 let {utxoAccumulator, MerkleTree, fr_random, fs_random, u64_random, 
-    verify, u32_random, pubkey, note_hash, randrange, transfer, extract_vk, bufferizeBigints} = require("../lib/index.js");
+    verify, u32_random, pubkey, note_hash, randrange, transfer, extract_vk, loadParams, bufferizeBigints} = require("../lib/index.js");
 
 
 
@@ -22,6 +22,7 @@ function utxo(asset_id, amount, native_amount, txid, owner) {
 
 let fs = require("fs");
 let mpc_params = fs.readFileSync("../zwaves_setup/mpc_params_transfer");
+let params = loadParams(mpc_params);
 
 
 let sk = fs_random();
@@ -64,6 +65,6 @@ let data = {
 
 let vk = extract_vk(mpc_params);
 console.log(data);
-let res = transfer(mpc_params, data);
+let res = transfer(params, data);
 console.log(res);
 console.log(verify(vk, res));
@@ -1,8 +1,9 @@
-let {utxoAccumulator, MerkleTree, fr_random, verify, extract_vk} = require("../lib/index.js");
+let {utxoAccumulator, MerkleTree, fr_random, verify, extract_vk, loadParams} = require("../lib/index.js");
 
 
 let fs = require("fs");
 let mpc_params = fs.readFileSync("../zwaves_setup/mpc_params_accumulator");
+let params = loadParams(mpc_params);
 
 let mt = new MerkleTree(48);
 
@@ -22,6 +23,6 @@ let data = {
 
 console.log(data);
 let vk = extract_vk(mpc_params);
-let res = utxoAccumulator(mpc_params, data);
+let res = utxoAccumulator(params, data);
 console.log(res);
 console.log(verify(vk, res));
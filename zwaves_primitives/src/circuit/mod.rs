@@ -3,4 +3,5 @@ pub mod test;
 
 pub mod merkle_proof;
 pub mod transactions;
-pub mod bitify;
\ No newline at end of file
+pub mod bitify;
+pub mod sparse_merkle_tree;
\ No newline at end of file
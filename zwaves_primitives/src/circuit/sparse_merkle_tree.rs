@@ -0,0 +1,61 @@
+use bellman::{SynthesisError, ConstraintSystem};
+
+use std::io;
+
+use sapling_crypto::jubjub::{JubjubEngine};
+use sapling_crypto::circuit::num::AllocatedNum;
+
+use crate::circuit::merkle_proof::compress;
+use crate::sparse_merkle_tree::personalization;
+
+
+/// Root of the sparse tree with the `leaf` at the index of the lowest `sibling.len()` bits of the `key`.
+pub fn sparse_merkle_root<E: JubjubEngine, CS>(
+    mut cs: CS,
+    key: &AllocatedNum<E>,
+    leaf: &AllocatedNum<E>,
+    sibling: &[AllocatedNum<E>],
+    params: &E::Params
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    let key_bits = key.into_bits_le_strict(cs.namespace(|| "key_bits <== bitify(key)"))?;
+    if sibling.len() > key_bits.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "sibling is longer than the key").into());
+    }
+
+    let mut cur : AllocatedNum<E> = leaf.clone();
+
+    for (i, (path_element, cur_is_right)) in sibling.iter().zip(key_bits.into_iter()).enumerate() {
+        let (xl, xr) = AllocatedNum::conditionally_reverse(
+            cs.namespace(|| format!("conditional reversal of preimage [{}]", i)),
+            &cur,
+            path_element,
+            &cur_is_right
+        )?;
+
+        cur = compress(cs.namespace(|| format!("Merkle hash layer [{}]", i)), personalization(i), &xl, &xr, params)?;
+    }
+    Ok(cur)
+}
+
+
+/// Proves that the nullifier is not in the set with the `root`: its leaf is in the tree and is empty.
+pub fn nullifier_non_membership<E: JubjubEngine, CS>(
+    mut cs: CS,
+    nf: &AllocatedNum<E>,
+    leaf: &AllocatedNum<E>,
+    sibling: &[AllocatedNum<E>],
+    root: &AllocatedNum<E>,
+    params: &E::Params
+) -> Result<(), SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    let root_calculated = sparse_merkle_root(cs.namespace(|| "root_calculated <== sparse_merkle_root(nf, leaf, sibling)"), nf, leaf, sibling, params)?;
+
+    cs.enforce(|| "root_calculated === root", |lc| lc + root.get_variable(), |lc| lc + CS::one(), |lc| lc + root_calculated.get_variable());
+
+    cs.enforce(|| "leaf === 0", |lc| lc + leaf.get_variable(), |lc| lc + CS::one(), |lc| lc);
+
+    Ok(())
+}
@@ -1,2 +1,4 @@
 pub mod compress_test;
-pub mod transaction_test;
\ No newline at end of file
+pub mod transaction_test;
+pub mod packed_asset_test;
+pub mod sparse_merkle_tree_test;
\ No newline at end of file
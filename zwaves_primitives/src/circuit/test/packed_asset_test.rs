@@ -0,0 +1,57 @@
+use bellman::ConstraintSystem;
+
+use sapling_crypto::circuit::num::AllocatedNum;
+use sapling_crypto::circuit::test::TestConstraintSystem;
+
+use pairing::bls12_381::{Bls12, Fr};
+use pairing::PrimeField;
+
+use crate::circuit::transactions::signed_asset_unpack;
+use crate::packed_asset::PackedAsset;
+
+use rand::os::OsRng;
+use rand::Rng;
+
+
+
+fn check_unpack(asset: &PackedAsset) {
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+
+    let packed_asset = AllocatedNum::alloc(cs.namespace(|| "alloc packed_asset"), || Ok(asset.to_fr::<Fr>())).unwrap();
+    let (asset_id, amount, native_amount) = signed_asset_unpack(cs.namespace(|| "unpacking asset"), &packed_asset).unwrap();
+
+    if !cs.is_satisfied() {
+        panic!("Constraints not satisfied: {}", cs.which_is_unsatisfied().unwrap_or(""));
+    }
+
+    assert!(asset_id.get_value().unwrap() == Fr::from_repr(asset.asset_id.into()).unwrap(), "asset_id should be the same for {:?}", asset);
+    assert!(amount.get_value().unwrap() == asset.amount_fr::<Fr>(), "amount should be the same for {:?}", asset);
+    assert!(native_amount.get_value().unwrap() == asset.native_amount_fr::<Fr>(), "native_amount should be the same for {:?}", asset);
+    assert!(PackedAsset::from_fr(&packed_asset.get_value().unwrap()).unwrap() == *asset, "packed asset should be unpacked natively");
+}
+
+
+#[test]
+fn test_packed_asset_unpack_edge_cases() {
+    let edges = [0, 1, -1, i64::max_value(), i64::min_value()];
+
+    for &amount in edges.iter() {
+        for &native_amount in edges.iter() {
+            check_unpack(&PackedAsset::new(u64::max_value(), amount, native_amount));
+        }
+    }
+
+    check_unpack(&PackedAsset::deposit(10000000, 1000000).unwrap());
+    check_unpack(&PackedAsset::withdraw(10000000, 1000000).unwrap());
+    check_unpack(&PackedAsset::transfer(1000000).unwrap());
+}
+
+
+#[test]
+fn test_packed_asset_unpack_random() {
+    let mut rng = OsRng::new().unwrap();
+
+    for _ in 0..100 {
+        check_unpack(&PackedAsset::new(rng.gen(), rng.gen(), rng.gen()));
+    }
+}
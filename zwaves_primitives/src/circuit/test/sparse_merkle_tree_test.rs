@@ -0,0 +1,108 @@
+use bellman::{ConstraintSystem, SynthesisError};
+
+use sapling_crypto::jubjub::JubjubBls12;
+use sapling_crypto::circuit::num::AllocatedNum;
+use sapling_crypto::circuit::test::TestConstraintSystem;
+
+use pairing::bls12_381::{Bls12, Fr, FrRepr};
+use pairing::PrimeField;
+
+use crate::circuit::sparse_merkle_tree::{nullifier_non_membership, sparse_merkle_root};
+use crate::sparse_merkle_tree::{SparseMerkleTree, SparseMerkleProof};
+
+use rand::os::OsRng;
+use rand::Rng;
+
+
+
+fn non_membership_cs(nf: &Fr, proof: &SparseMerkleProof<Bls12>, root: &Fr, params: &JubjubBls12) -> Result<TestConstraintSystem<Bls12>, SynthesisError> {
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+
+    let nf = AllocatedNum::alloc(cs.namespace(|| "alloc nf"), || Ok(*nf)).unwrap();
+    let leaf = AllocatedNum::alloc(cs.namespace(|| "alloc leaf"), || Ok(proof.leaf)).unwrap();
+    let sibling = proof.sibling.iter().enumerate()
+        .map(|(i, e)| AllocatedNum::alloc(cs.namespace(|| format!("alloc sibling[{}]", i)), || Ok(*e)).unwrap())
+        .collect::<Vec<_>>();
+    let root = AllocatedNum::alloc(cs.namespace(|| "alloc root"), || Ok(*root)).unwrap();
+
+    nullifier_non_membership(cs.namespace(|| "non-membership"), &nf, &leaf, &sibling, &root, params)?;
+    Ok(cs)
+}
+
+
+#[test]
+fn test_sparse_merkle_root() {
+    let params = JubjubBls12::new();
+    let rng = &mut OsRng::new().unwrap();
+
+    // more levels than personalizations
+    let mut tree = SparseMerkleTree::<Bls12>::new(70, &params).unwrap();
+    let nf: Fr = rng.gen();
+    tree.insert(&nf, &params).unwrap();
+    let proof = tree.proof(&nf);
+
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+    let key = AllocatedNum::alloc(cs.namespace(|| "alloc nf"), || Ok(nf)).unwrap();
+    let sibling = proof.sibling.iter().enumerate()
+        .map(|(i, e)| AllocatedNum::alloc(cs.namespace(|| format!("alloc sibling[{}]", i)), || Ok(*e)).unwrap())
+        .collect::<Vec<_>>();
+    let root = sparse_merkle_root(cs.namespace(|| "sparse_merkle_root"), &key, &key, &sibling, &params).unwrap();
+
+    if !cs.is_satisfied() {
+        panic!("Constraints not satisfied: {}", cs.which_is_unsatisfied().unwrap_or(""));
+    }
+    assert!(root.get_value().unwrap() == tree.root(), "root should be the same as of the native tree");
+}
+
+
+#[test]
+fn test_nullifier_non_membership() {
+    let params = JubjubBls12::new();
+    let rng = &mut OsRng::new().unwrap();
+
+    let mut tree = SparseMerkleTree::<Bls12>::new(32, &params).unwrap();
+    let nfs = (0..4).map(|_| rng.gen()).collect::<Vec<Fr>>();
+    for nf in nfs[..3].iter() {
+        tree.insert(nf, &params).unwrap();
+    }
+    let root = tree.root();
+
+    let cs = non_membership_cs(&nfs[3], &tree.proof(&nfs[3]), &root, &params).unwrap();
+    if !cs.is_satisfied() {
+        panic!("Constraints not satisfied: {}", cs.which_is_unsatisfied().unwrap_or(""));
+    }
+
+    let cs = non_membership_cs(&nfs[0], &tree.proof(&nfs[0]), &root, &params).unwrap();
+    assert!(!cs.is_satisfied(), "nullifier in the set should not pass");
+
+    let empty_root = SparseMerkleTree::<Bls12>::new(32, &params).unwrap().root();
+    let cs = non_membership_cs(&nfs[3], &tree.proof(&nfs[3]), &empty_root, &params).unwrap();
+    assert!(!cs.is_satisfied(), "proof for another root should not pass");
+}
+
+
+#[test]
+fn test_collision_non_membership() {
+    let params = JubjubBls12::new();
+    let nf = |n: u64| Fr::from_repr(FrRepr([n, 0, 0, 0])).unwrap();
+
+    let mut tree = SparseMerkleTree::<Bls12>::new(4, &params).unwrap();
+    tree.insert(&nf(0x13), &params).unwrap();
+
+    // 0x23 shares the leaf of 0x13
+    let cs = non_membership_cs(&nf(0x23), &tree.proof(&nf(0x23)), &tree.root(), &params).unwrap();
+    assert!(!cs.is_satisfied(), "colliding nullifier should not pass");
+}
+
+
+#[test]
+fn test_sparse_merkle_root_long_sibling() {
+    let params = JubjubBls12::new();
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+
+    let key = AllocatedNum::alloc(cs.namespace(|| "alloc key"), || Ok(Fr::from_str("3").unwrap())).unwrap();
+    let sibling = (0..Fr::NUM_BITS + 1)
+        .map(|i| AllocatedNum::alloc(cs.namespace(|| format!("alloc sibling[{}]", i)), || Ok(Fr::from_str("0").unwrap())).unwrap())
+        .collect::<Vec<_>>();
+    assert!(sparse_merkle_root(cs.namespace(|| "sparse_merkle_root"), &key, &key, &sibling, &params).is_err());
+}
@@ -14,6 +14,7 @@ use pairing::{PrimeField, Field};
 
 
 use crate::pedersen_hasher;
+use crate::merkle_tree::IncrementalMerkleTree;
 use crate::circuit::merkle_proof;
 use crate::transactions::{NoteData, pubkey, note_hash};
 use crate::circuit::transactions::{transfer, Note, nullifier};
@@ -25,72 +26,14 @@ use num::BigInt;
 use num::Num as NumTrait;
 use std::str::FromStr;
 
-struct MerkleTreeAccumulator(Vec<Vec<Fr>>);
-
 const PROOF_LENGTH:usize = 32;
 
 lazy_static! {
     static ref JUBJUB_PARAMS: JubjubBls12 = JubjubBls12::new();
-    static ref MERKLE_DEFAULTS: Vec<Fr> = {
-        let mut c = Fr::zero();
-        let mut res = vec![];
-        for i in 0 .. PROOF_LENGTH+1 {
-            res.push(c);
-            c = pedersen_hasher::compress::<Bls12>(&c, &c,  Personalization::MerkleTree(i), &JUBJUB_PARAMS)
-        }
-        res
-    };
 }
 
 
 
-impl MerkleTreeAccumulator {
-    pub fn new() -> Self {
-        let mut res = MerkleTreeAccumulator(vec![]);
-        for _ in 0 .. PROOF_LENGTH+1 {
-            res.0.push(vec![]);
-        }
-        res
-    }
-
-    pub fn cell(&self, row: usize, index: usize) -> Fr {
-        assert!(row <= PROOF_LENGTH, "too big row");
-        if index < self.0[row].len() {
-            self.0[row][index]
-        } else {
-            MERKLE_DEFAULTS[row]
-        }
-    }
-
-    pub fn size(&self) -> usize {
-        self.0[0].len()
-    }
-
-    pub fn pushMany(&mut self, elements: &[Fr]) {
-        let index = self.size();
-        let s = elements.len();
-        self.0[0].extend_from_slice(elements);
-
-        for i in 1..PROOF_LENGTH+1 {
-            let rl = self.0[i].len();
-            self.0[i].extend(vec![Fr::zero(); 1 + (index+s>>i) - rl]);
-            
-            for j in (index >> i) .. (index+s>>i) + 1 {
-                self.0[i][j] = pedersen_hasher::compress::<Bls12>(&self.cell(i-1, j*2), &self.cell(i-1, j*2+1), Personalization::MerkleTree(i-1), &JUBJUB_PARAMS);
-            }
-        }
-    }
-
-    pub fn root(&self) -> Fr {
-        self.cell(PROOF_LENGTH, 0)
-    }
-
-    pub fn proof(&self, index: usize) -> Vec<Fr> {
-        (0..PROOF_LENGTH).map(|i| self.cell(i, (index >> i) ^ 1)).collect::<Vec<Fr>>()
-    }
-}
-
-
 
 fn gen_rand_fr_limited<R: ::rand::Rng>(n: usize, rng: &mut R) -> Fr {
     let f :Fr = rng.gen();
@@ -198,9 +141,9 @@ pub fn test_merkle_tree_struct(){
     let note_hashes = (0..n_notes).map(|_| rng.gen()).collect::<Vec<_>>();
     let index = rng.gen_range(0, n_notes);
 
-    let mut mt = MerkleTreeAccumulator::new();
-    mt.pushMany(&note_hashes);
-    let sibling = mt.proof(index as usize);
+    let mut mt = IncrementalMerkleTree::<Bls12>::new(PROOF_LENGTH, &JUBJUB_PARAMS);
+    mt.append_many(&note_hashes, &JUBJUB_PARAMS).unwrap();
+    let sibling = mt.proof(index as u64).unwrap();
     let leaf_data = note_hashes[index];
     let cmp_root = crate::pedersen_hasher::merkle_root::<Bls12>(&sibling, index as u64, &leaf_data, &JUBJUB_PARAMS);
     assert!(cmp_root == mt.root(), "merkle proof results should be equal");
@@ -221,11 +164,11 @@ pub fn test_merkle_proof(){
     let index_bits = (0..PROOF_LENGTH).map(|j| (index>>j) & 1 == 1).collect::<Vec<_>>();
 
 
-    let mut mt = MerkleTreeAccumulator::new();
-    mt.pushMany(&note_hashes);
+    let mut mt = IncrementalMerkleTree::<Bls12>::new(PROOF_LENGTH, &JUBJUB_PARAMS);
+    mt.append_many(&note_hashes, &JUBJUB_PARAMS).unwrap();
 
     let mut cs = TestConstraintSystem::<Bls12>::new();
-    let sibling = mt.proof(index as usize);
+    let sibling = mt.proof(index as u64).unwrap();
     let proof_data = sibling.iter().zip(index_bits.iter()).map(|(&f, &b)| (f, b)).collect::<Vec<_>>();
     let proof = alloc_proof_data(cs.namespace(|| "alloc proof {}"), Some(proof_data)).unwrap();
 
@@ -255,8 +198,8 @@ fn test_transaction() {
 
     let note_hashes = notes.iter().map(|n| note_hash::<Bls12>(n, &JUBJUB_PARAMS)).collect::<Vec<_>>();
 
-    let mut mt = MerkleTreeAccumulator::new();
-    mt.pushMany(&note_hashes);
+    let mut mt = IncrementalMerkleTree::<Bls12>::new(PROOF_LENGTH, &JUBJUB_PARAMS);
+    mt.append_many(&note_hashes, &JUBJUB_PARAMS).unwrap();
 
     let i0 = rng.gen_range(0, n_notes);
     let i1 = rng.gen_range(0, n_notes-1);
@@ -270,7 +213,7 @@ fn test_transaction() {
     let in_note = in_note_data.iter().enumerate().map(|(i, note)| alloc_note_data(cs.namespace(|| format!("alloc in_note {}", i)), Some(note.clone())).unwrap()).collect::<Vec<_>>();
     
     let in_proof = indexes.iter().zip(indexes_bits).map(|(&i, bits)| {
-        let proof = mt.proof(i as usize).iter().zip(bits.iter()).map(|(&f, &b)| (f, b)).collect::<Vec<_>>();
+        let proof = mt.proof(i as u64).unwrap().iter().zip(bits.iter()).map(|(&f, &b)| (f, b)).collect::<Vec<_>>();
         alloc_proof_data(cs.namespace(|| format!("alloc in_proof {}", i)), Some(proof)).unwrap()
     }).collect::<Vec<_>>();
 
@@ -335,8 +278,8 @@ fn test_transaction_withdraw() {
 
     let note_hashes = notes.iter().map(|n| note_hash::<Bls12>(n, &JUBJUB_PARAMS)).collect::<Vec<_>>();
 
-    let mut mt = MerkleTreeAccumulator::new();
-    mt.pushMany(&note_hashes);
+    let mut mt = IncrementalMerkleTree::<Bls12>::new(PROOF_LENGTH, &JUBJUB_PARAMS);
+    mt.append_many(&note_hashes, &JUBJUB_PARAMS).unwrap();
 
     let i0 = rng.gen_range(0, n_notes);
     let i1 = rng.gen_range(0, n_notes-1);
@@ -350,7 +293,7 @@ fn test_transaction_withdraw() {
     let in_note = in_note_data.iter().enumerate().map(|(i, note)| alloc_note_data(cs.namespace(|| format!("alloc in_note {}", i)), Some(note.clone())).unwrap()).collect::<Vec<_>>();
     
     let in_proof = indexes.iter().zip(indexes_bits).map(|(&i, bits)| {
-        let proof = mt.proof(i as usize).iter().zip(bits.iter()).map(|(&f, &b)| (f, b)).collect::<Vec<_>>();
+        let proof = mt.proof(i as u64).unwrap().iter().zip(bits.iter()).map(|(&f, &b)| (f, b)).collect::<Vec<_>>();
         alloc_proof_data(cs.namespace(|| format!("alloc in_proof {}", i)), Some(proof)).unwrap()
     }).collect::<Vec<_>>();
 
@@ -0,0 +1,860 @@
+//! Single-threaded Groth16 parameter generation and proving. It follows `bellman::groth16` step by step, only
+//! without the thread pool of bellman, which can not be started in wasm. Parameters and proofs are interchangeable
+//! with the ones of bellman.
+
+use pairing::{
+    Engine,
+    CurveProjective,
+    CurveAffine,
+    PrimeField,
+    Field,
+    Wnaf
+};
+
+use bellman::{
+    Circuit,
+    ConstraintSystem,
+    Index,
+    LinearCombination,
+    SynthesisError,
+    Variable
+};
+
+use bellman::groth16::{
+    Parameters,
+    Proof,
+    VerifyingKey
+};
+
+use rand::Rng;
+
+use std::io;
+use std::sync::Arc;
+
+
+/// Evaluation domain of the constraints, a power of two not less than their number.
+struct Domain<F: PrimeField> {
+    coeffs: Vec<F>,
+    exp: u32,
+    omega: F,
+    omegainv: F,
+    geninv: F,
+    minv: F
+}
+
+impl<F: PrimeField> Domain<F> {
+    fn from_coeffs(mut coeffs: Vec<F>) -> Result<Self, SynthesisError> {
+        let mut m = 1;
+        let mut exp = 0;
+        while m < coeffs.len() {
+            m *= 2;
+            exp += 1;
+
+            if exp >= F::S {
+                return Err(SynthesisError::PolynomialDegreeTooLarge);
+            }
+        }
+
+        let mut omega = F::root_of_unity();
+        for _ in exp..F::S {
+            omega.square();
+        }
+
+        coeffs.resize(m, F::zero());
+
+        Ok(Domain {
+            coeffs,
+            exp,
+            omega,
+            omegainv: omega.inverse().unwrap(),
+            geninv: F::multiplicative_generator().inverse().unwrap(),
+            minv: F::from_str(&format!("{}", m)).unwrap().inverse().unwrap()
+        })
+    }
+
+    fn fft(&mut self) {
+        let omega = self.omega;
+        serial_fft(&mut self.coeffs, &omega, self.exp);
+    }
+
+    fn ifft(&mut self) {
+        let omegainv = self.omegainv;
+        serial_fft(&mut self.coeffs, &omegainv, self.exp);
+
+        let minv = self.minv;
+        for c in self.coeffs.iter_mut() {
+            c.mul_assign(&minv);
+        }
+    }
+
+    fn distribute_powers(&mut self, g: F) {
+        let mut u = F::one();
+        for c in self.coeffs.iter_mut() {
+            c.mul_assign(&u);
+            u.mul_assign(&g);
+        }
+    }
+
+    fn coset_fft(&mut self) {
+        self.distribute_powers(F::multiplicative_generator());
+        self.fft();
+    }
+
+    fn icoset_fft(&mut self) {
+        let geninv = self.geninv;
+        self.ifft();
+        self.distribute_powers(geninv);
+    }
+
+    /// `t(tau) = tau^m - 1`, the polynomial vanishing on the domain.
+    fn z(&self, tau: &F) -> F {
+        let mut tmp = tau.pow(&[self.coeffs.len() as u64]);
+        tmp.sub_assign(&F::one());
+        tmp
+    }
+
+    /// Divides by `t(x)` on the coset `g * domain`, where it is the constant `g^m - 1`.
+    fn divide_by_z_on_coset(&mut self) {
+        let i = self.z(&F::multiplicative_generator()).inverse().unwrap();
+        for c in self.coeffs.iter_mut() {
+            c.mul_assign(&i);
+        }
+    }
+}
+
+fn serial_fft<F: PrimeField>(a: &mut [F], omega: &F, log_n: u32) {
+    fn bitreverse(mut n: u32, l: u32) -> u32 {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    let n = a.len() as u32;
+    assert_eq!(n, 1 << log_n);
+
+    for k in 0..n {
+        let rk = bitreverse(k, log_n);
+        if k < rk {
+            a.swap(rk as usize, k as usize);
+        }
+    }
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        let w_m = omega.pow(&[(n / (2 * m)) as u64]);
+
+        let mut k = 0;
+        while k < n {
+            let mut w = F::one();
+            for j in 0..m {
+                let mut t = a[(k + j + m) as usize];
+                t.mul_assign(&w);
+                let mut tmp = a[(k + j) as usize];
+                tmp.sub_assign(&t);
+                a[(k + j + m) as usize] = tmp;
+                a[(k + j) as usize].add_assign(&t);
+                w.mul_assign(&w_m);
+            }
+
+            k += 2 * m;
+        }
+
+        m *= 2;
+    }
+}
+
+
+/// Sum of `bases[i] * exponents[i]` by the bucket method.
+fn multiexp<G: CurveAffine>(
+    bases: &[G],
+    exponents: &[<G::Scalar as PrimeField>::Repr]
+) -> Result<G::Projective, SynthesisError>
+{
+    if bases.len() < exponents.len() {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "not enough bases").into());
+    }
+    if bases[..exponents.len()].iter().any(|e| e.is_zero()) {
+        return Err(SynthesisError::UnexpectedIdentity);
+    }
+
+    let c = if exponents.len() < 32 { 3 } else { (exponents.len() as f64).ln().ceil() as u32 };
+    let window = |exp: &[u64], skip: u32| {
+        let limb = (skip / 64) as usize;
+        let shift = skip % 64;
+        let mut w = exp[limb] >> shift;
+        if shift + c > 64 && limb + 1 < exp.len() {
+            w |= exp[limb + 1] << (64 - shift);
+        }
+        (w & ((1 << c) - 1)) as usize
+    };
+
+    let num_bits = G::Scalar::NUM_BITS;
+    let mut acc = G::Projective::zero();
+    let mut skip = (num_bits - 1) / c * c;
+    loop {
+        let mut buckets = vec![G::Projective::zero(); (1 << c) - 1];
+        for (base, exp) in bases.iter().zip(exponents.iter()) {
+            let w = window(exp.as_ref(), skip);
+            if w != 0 {
+                buckets[w - 1].add_assign_mixed(base);
+            }
+        }
+
+        let mut running_sum = G::Projective::zero();
+        for b in buckets.into_iter().rev() {
+            running_sum.add_assign(&b);
+            acc.add_assign(&running_sum);
+        }
+
+        if skip == 0 {
+            break;
+        }
+        skip -= c;
+        for _ in 0..c {
+            acc.double();
+        }
+    }
+
+    Ok(acc)
+}
+
+/// The `exponents` of the variables used in the `density`, the bases of the others are not in the parameters.
+fn dense<T: Copy>(exponents: &[T], density: &[bool]) -> Vec<T> {
+    exponents.iter().zip(density.iter()).filter(|&(_, &d)| d).map(|(&e, _)| e).collect()
+}
+
+
+struct KeypairAssembly<E: Engine> {
+    num_inputs: usize,
+    num_aux: usize,
+    num_constraints: usize,
+    at_inputs: Vec<Vec<(E::Fr, usize)>>,
+    bt_inputs: Vec<Vec<(E::Fr, usize)>>,
+    ct_inputs: Vec<Vec<(E::Fr, usize)>>,
+    at_aux: Vec<Vec<(E::Fr, usize)>>,
+    bt_aux: Vec<Vec<(E::Fr, usize)>>,
+    ct_aux: Vec<Vec<(E::Fr, usize)>>
+}
+
+impl<E: Engine> ConstraintSystem<E> for KeypairAssembly<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        // The values are not needed for the parameters
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        self.at_aux.push(vec![]);
+        self.bt_aux.push(vec![]);
+        self.ct_aux.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        self.at_inputs.push(vec![]);
+        self.bt_inputs.push(vec![]);
+        self.ct_inputs.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        a: LA,
+        b: LB,
+        c: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        fn eval<E: Engine>(
+            l: LinearCombination<E>,
+            inputs: &mut [Vec<(E::Fr, usize)>],
+            aux: &mut [Vec<(E::Fr, usize)>],
+            this_constraint: usize
+        )
+        {
+            for &(var, coeff) in l.as_ref() {
+                match var.get_unchecked() {
+                    Index::Input(id) => inputs[id].push((coeff, this_constraint)),
+                    Index::Aux(id) => aux[id].push((coeff, this_constraint))
+                }
+            }
+        }
+
+        let n = self.num_constraints;
+        eval(a(LinearCombination::zero()), &mut self.at_inputs, &mut self.at_aux, n);
+        eval(b(LinearCombination::zero()), &mut self.bt_inputs, &mut self.bt_aux, n);
+        eval(c(LinearCombination::zero()), &mut self.ct_inputs, &mut self.ct_aux, n);
+
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+        where NR: Into<String>, N: FnOnce() -> NR
+    {
+    }
+
+    fn pop_namespace(&mut self)
+    {
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+
+/// Parameters of the `circuit` with random toxic waste, the same as `bellman::groth16::generate_random_parameters`.
+pub fn generate_random_parameters<E, C, R>(
+    circuit: C,
+    rng: &mut R
+) -> Result<Parameters<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>, R: Rng
+{
+    let g1: E::G1 = rng.gen();
+    let g2: E::G2 = rng.gen();
+    let alpha: E::Fr = rng.gen();
+    let beta: E::Fr = rng.gen();
+    let gamma: E::Fr = rng.gen();
+    let delta: E::Fr = rng.gen();
+    let tau: E::Fr = rng.gen();
+
+    let mut assembly = KeypairAssembly {
+        num_inputs: 0,
+        num_aux: 0,
+        num_constraints: 0,
+        at_inputs: vec![],
+        bt_inputs: vec![],
+        ct_inputs: vec![],
+        at_aux: vec![],
+        bt_aux: vec![],
+        ct_aux: vec![]
+    };
+
+    // Allocate the "one" input variable
+    assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+    circuit.synthesize(&mut assembly)?;
+
+    // Input constraints to ensure full density of IC query
+    // x * 0 = 0
+    for i in 0..assembly.num_inputs {
+        assembly.enforce(|| "",
+            |lc| lc + Variable::new_unchecked(Index::Input(i)),
+            |lc| lc,
+            |lc| lc,
+        );
+    }
+
+    let mut powers_of_tau = Domain::from_coeffs(vec![E::Fr::zero(); assembly.num_constraints])?;
+
+    let gamma_inverse = gamma.inverse().ok_or(SynthesisError::UnexpectedIdentity)?;
+    let delta_inverse = delta.inverse().ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    let mut g1_wnaf = Wnaf::new();
+    let mut g1_wnaf = g1_wnaf.base(g1, powers_of_tau.coeffs.len() + 2 * (assembly.num_inputs + assembly.num_aux));
+    let mut g2_wnaf = Wnaf::new();
+    let mut g2_wnaf = g2_wnaf.base(g2, assembly.num_inputs + assembly.num_aux);
+
+    let mut current_tau_power = E::Fr::one();
+    for p in powers_of_tau.coeffs.iter_mut() {
+        *p = current_tau_power;
+        current_tau_power.mul_assign(&tau);
+    }
+
+    // H query, g1^{(tau^i * t(tau)) / delta}
+    let mut coeff = powers_of_tau.z(&tau);
+    coeff.mul_assign(&delta_inverse);
+
+    let h = powers_of_tau.coeffs[..powers_of_tau.coeffs.len() - 1].iter().map(|p| {
+        let mut exp = *p;
+        exp.mul_assign(&coeff);
+        g1_wnaf.scalar(exp.into_repr())
+    }).collect::<Vec<_>>();
+
+    // Lagrange coefficients at tau
+    powers_of_tau.ifft();
+    let powers_of_tau = powers_of_tau.coeffs;
+
+    let mut a = vec![E::G1::zero(); assembly.num_inputs + assembly.num_aux];
+    let mut b_g1 = vec![E::G1::zero(); assembly.num_inputs + assembly.num_aux];
+    let mut b_g2 = vec![E::G2::zero(); assembly.num_inputs + assembly.num_aux];
+    let mut ic = vec![E::G1::zero(); assembly.num_inputs];
+    let mut l = vec![E::G1::zero(); assembly.num_aux];
+
+    {
+        let mut eval = |
+            at: &[Vec<(E::Fr, usize)>],
+            bt: &[Vec<(E::Fr, usize)>],
+            ct: &[Vec<(E::Fr, usize)>],
+            a: &mut [E::G1],
+            b_g1: &mut [E::G1],
+            b_g2: &mut [E::G2],
+            ext: &mut [E::G1],
+            inv: &E::Fr
+        | {
+            let eval_at_tau = |p: &[(E::Fr, usize)]| {
+                let mut acc = E::Fr::zero();
+                for &(ref coeff, index) in p {
+                    let mut n = powers_of_tau[index];
+                    n.mul_assign(coeff);
+                    acc.add_assign(&n);
+                }
+                acc
+            };
+
+            let queries = a.iter_mut().zip(b_g1.iter_mut()).zip(b_g2.iter_mut()).zip(ext.iter_mut());
+            for ((((a, b_g1), b_g2), ext), ((at, bt), ct)) in queries.zip(at.iter().zip(bt.iter()).zip(ct.iter())) {
+                let mut at = eval_at_tau(at);
+                let mut bt = eval_at_tau(bt);
+                let ct = eval_at_tau(ct);
+
+                if !at.is_zero() {
+                    *a = g1_wnaf.scalar(at.into_repr());
+                }
+
+                if !bt.is_zero() {
+                    *b_g1 = g1_wnaf.scalar(bt.into_repr());
+                    *b_g2 = g2_wnaf.scalar(bt.into_repr());
+                }
+
+                at.mul_assign(&beta);
+                bt.mul_assign(&alpha);
+
+                let mut e = at;
+                e.add_assign(&bt);
+                e.add_assign(&ct);
+                e.mul_assign(inv);
+
+                *ext = g1_wnaf.scalar(e.into_repr());
+            }
+        };
+
+        let num_inputs = assembly.num_inputs;
+        eval(
+            &assembly.at_inputs,
+            &assembly.bt_inputs,
+            &assembly.ct_inputs,
+            &mut a[..num_inputs],
+            &mut b_g1[..num_inputs],
+            &mut b_g2[..num_inputs],
+            &mut ic,
+            &gamma_inverse
+        );
+        eval(
+            &assembly.at_aux,
+            &assembly.bt_aux,
+            &assembly.ct_aux,
+            &mut a[num_inputs..],
+            &mut b_g1[num_inputs..],
+            &mut b_g2[num_inputs..],
+            &mut l,
+            &delta_inverse
+        );
+    }
+
+    // Don't allow any elements be unconstrained, so that the L query is always fully dense.
+    if l.iter().any(|e| e.is_zero()) {
+        return Err(SynthesisError::UnconstrainedVariable);
+    }
+
+    fn into_affine<G: CurveProjective>(mut v: Vec<G>) -> Vec<G::Affine> {
+        G::batch_normalization(&mut v);
+        v.into_iter().map(|e| e.into_affine()).collect()
+    }
+
+    // Filter points at infinity away from A/B queries
+    a.retain(|e| !e.is_zero());
+    b_g1.retain(|e| !e.is_zero());
+    b_g2.retain(|e| !e.is_zero());
+
+    let g1 = g1.into_affine();
+    let g2 = g2.into_affine();
+
+    let vk = VerifyingKey::<E> {
+        alpha_g1: g1.mul(alpha).into_affine(),
+        beta_g1: g1.mul(beta).into_affine(),
+        beta_g2: g2.mul(beta).into_affine(),
+        gamma_g2: g2.mul(gamma).into_affine(),
+        delta_g1: g1.mul(delta).into_affine(),
+        delta_g2: g2.mul(delta).into_affine(),
+        ic: into_affine(ic)
+    };
+
+    Ok(Parameters {
+        vk,
+        h: Arc::new(into_affine(h)),
+        l: Arc::new(into_affine(l)),
+        a: Arc::new(into_affine(a)),
+        b_g1: Arc::new(into_affine(b_g1)),
+        b_g2: Arc::new(into_affine(b_g2))
+    })
+}
+
+
+struct ProvingAssignment<E: Engine> {
+    // Variables used in the A and B polynomials, the queries have bases only for them
+    a_aux_density: Vec<bool>,
+    b_input_density: Vec<bool>,
+    b_aux_density: Vec<bool>,
+
+    // Evaluations of the A, B and C polynomials
+    a: Vec<E::Fr>,
+    b: Vec<E::Fr>,
+    c: Vec<E::Fr>,
+
+    input_assignment: Vec<E::Fr>,
+    aux_assignment: Vec<E::Fr>
+}
+
+impl<E: Engine> ConstraintSystem<E> for ProvingAssignment<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        self.aux_assignment.push(f()?);
+        self.a_aux_density.push(false);
+        self.b_aux_density.push(false);
+
+        Ok(Variable::new_unchecked(Index::Aux(self.aux_assignment.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        self.input_assignment.push(f()?);
+        self.b_input_density.push(false);
+
+        Ok(Variable::new_unchecked(Index::Input(self.input_assignment.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        a: LA,
+        b: LB,
+        c: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        fn eval<E: Engine>(
+            lc: &LinearCombination<E>,
+            mut input_density: Option<&mut Vec<bool>>,
+            mut aux_density: Option<&mut Vec<bool>>,
+            input_assignment: &[E::Fr],
+            aux_assignment: &[E::Fr]
+        ) -> E::Fr
+        {
+            let mut acc = E::Fr::zero();
+
+            for &(index, coeff) in lc.as_ref() {
+                let mut tmp;
+
+                match index.get_unchecked() {
+                    Index::Input(i) => {
+                        tmp = input_assignment[i];
+                        if let Some(ref mut v) = input_density {
+                            v[i] = true;
+                        }
+                    },
+                    Index::Aux(i) => {
+                        tmp = aux_assignment[i];
+                        if let Some(ref mut v) = aux_density {
+                            v[i] = true;
+                        }
+                    }
+                }
+
+                tmp.mul_assign(&coeff);
+                acc.add_assign(&tmp);
+            }
+
+            acc
+        }
+
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+
+        let a = eval(&a, None, Some(&mut self.a_aux_density), &self.input_assignment, &self.aux_assignment);
+        let b = eval(&b, Some(&mut self.b_input_density), Some(&mut self.b_aux_density), &self.input_assignment, &self.aux_assignment);
+        let c = eval(&c, None, None, &self.input_assignment, &self.aux_assignment);
+
+        self.a.push(a);
+        self.b.push(b);
+        self.c.push(c);
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+        where NR: Into<String>, N: FnOnce() -> NR
+    {
+    }
+
+    fn pop_namespace(&mut self)
+    {
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+
+/// Proof of the `circuit` with the randomness of the `rng`, the same as `bellman::groth16::create_random_proof`.
+pub fn create_random_proof<E, C, R>(
+    circuit: C,
+    params: &Parameters<E>,
+    rng: &mut R
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>, R: Rng
+{
+    let r = rng.gen();
+    let s = rng.gen();
+
+    create_proof::<E, C>(circuit, params, r, s)
+}
+
+/// Proof of the `circuit` with the given randomness `r` and `s`.
+pub fn create_proof<E, C>(
+    circuit: C,
+    params: &Parameters<E>,
+    r: E::Fr,
+    s: E::Fr
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>
+{
+    let mut prover = ProvingAssignment {
+        a_aux_density: vec![],
+        b_input_density: vec![],
+        b_aux_density: vec![],
+        a: vec![],
+        b: vec![],
+        c: vec![],
+        input_assignment: vec![],
+        aux_assignment: vec![]
+    };
+
+    prover.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+    circuit.synthesize(&mut prover)?;
+
+    for i in 0..prover.input_assignment.len() {
+        prover.enforce(|| "",
+            |lc| lc + Variable::new_unchecked(Index::Input(i)),
+            |lc| lc,
+            |lc| lc,
+        );
+    }
+
+    let vk = &params.vk;
+
+    let h = {
+        let mut a = Domain::from_coeffs(prover.a)?;
+        let mut b = Domain::from_coeffs(prover.b)?;
+        let mut c = Domain::from_coeffs(prover.c)?;
+        a.ifft();
+        a.coset_fft();
+        b.ifft();
+        b.coset_fft();
+        c.ifft();
+        c.coset_fft();
+
+        for ((a, b), c) in a.coeffs.iter_mut().zip(b.coeffs.iter()).zip(c.coeffs.iter()) {
+            a.mul_assign(b);
+            a.sub_assign(c);
+        }
+        a.divide_by_z_on_coset();
+        a.icoset_fft();
+
+        let mut a = a.coeffs;
+        let a_len = a.len() - 1;
+        a.truncate(a_len);
+
+        let a = a.into_iter().map(|e| e.into_repr()).collect::<Vec<_>>();
+        multiexp(&params.h, &a)?
+    };
+
+    let input_assignment = prover.input_assignment.into_iter().map(|e| e.into_repr()).collect::<Vec<_>>();
+    let aux_assignment = prover.aux_assignment.into_iter().map(|e| e.into_repr()).collect::<Vec<_>>();
+
+    let l = multiexp(&params.l, &aux_assignment)?;
+
+    // The A and B queries are the bases of the inputs followed by the bases of the used auxiliary variables
+    let split = |bases_len: usize, num_inputs: usize| {
+        if bases_len < num_inputs {
+            Err(SynthesisError::from(io::Error::new(io::ErrorKind::UnexpectedEof, "not enough bases")))
+        } else {
+            Ok(num_inputs)
+        }
+    };
+
+    let a_inputs_len = split(params.a.len(), input_assignment.len())?;
+    let a_inputs = multiexp(&params.a[..a_inputs_len], &input_assignment)?;
+    let a_aux = multiexp(&params.a[a_inputs_len..], &dense(&aux_assignment, &prover.a_aux_density))?;
+
+    let b_inputs = dense(&input_assignment, &prover.b_input_density);
+    let b_aux = dense(&aux_assignment, &prover.b_aux_density);
+
+    let b_g1_inputs_len = split(params.b_g1.len(), b_inputs.len())?;
+    let b_g1_inputs = multiexp(&params.b_g1[..b_g1_inputs_len], &b_inputs)?;
+    let b_g1_aux = multiexp(&params.b_g1[b_g1_inputs_len..], &b_aux)?;
+
+    let b_g2_inputs_len = split(params.b_g2.len(), b_inputs.len())?;
+    let b_g2_inputs = multiexp(&params.b_g2[..b_g2_inputs_len], &b_inputs)?;
+    let b_g2_aux = multiexp(&params.b_g2[b_g2_inputs_len..], &b_aux)?;
+
+    if vk.delta_g1.is_zero() || vk.delta_g2.is_zero() {
+        // If this element is zero, someone is trying to perform a
+        // subversion-CRS attack.
+        return Err(SynthesisError::UnexpectedIdentity);
+    }
+
+    let mut g_a = vk.delta_g1.mul(r);
+    g_a.add_assign_mixed(&vk.alpha_g1);
+    let mut g_b = vk.delta_g2.mul(s);
+    g_b.add_assign_mixed(&vk.beta_g2);
+    let mut g_c = {
+        let mut rs = r;
+        rs.mul_assign(&s);
+
+        let mut g_c = vk.delta_g1.mul(rs);
+        g_c.add_assign(&vk.alpha_g1.mul(s));
+        g_c.add_assign(&vk.beta_g1.mul(r));
+        g_c
+    };
+    let mut a_answer = a_inputs;
+    a_answer.add_assign(&a_aux);
+    g_a.add_assign(&a_answer);
+    a_answer.mul_assign(s);
+    g_c.add_assign(&a_answer);
+
+    let mut b1_answer = b_g1_inputs;
+    b1_answer.add_assign(&b_g1_aux);
+    let mut b2_answer = b_g2_inputs;
+    b2_answer.add_assign(&b_g2_aux);
+
+    g_b.add_assign(&b2_answer);
+    b1_answer.mul_assign(r);
+    g_c.add_assign(&b1_answer);
+    g_c.add_assign(&h);
+    g_c.add_assign(&l);
+
+    Ok(Proof {
+        a: g_a.into_affine(),
+        b: g_b.into_affine(),
+        c: g_c.into_affine()
+    })
+}
+
+
+#[cfg(test)]
+mod groth16_tests {
+    use super::*;
+    use bellman::groth16::{prepare_verifying_key, verify_proof};
+    use pairing::bls12_381::{Bls12, Fr};
+    use rand::os::OsRng;
+
+    /// `x^3 + x + 5 = y` with the public `y`.
+    struct CubeDemo<E: Engine> {
+        x: Option<E::Fr>
+    }
+
+    impl<E: Engine> Circuit<E> for CubeDemo<E> {
+        fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+            let x_val = self.x;
+            let x2_val = x_val.map(|mut e| { e.square(); e });
+            let y_val = x_val.and_then(|x| x2_val.map(|mut e| {
+                e.mul_assign(&x);
+                e.add_assign(&x);
+                e.add_assign(&E::Fr::from_str("5").unwrap());
+                e
+            }));
+
+            let x = cs.alloc(|| "x", || x_val.ok_or(SynthesisError::AssignmentMissing))?;
+            let x2 = cs.alloc(|| "x2", || x2_val.ok_or(SynthesisError::AssignmentMissing))?;
+            let y = cs.alloc_input(|| "y", || y_val.ok_or(SynthesisError::AssignmentMissing))?;
+
+            cs.enforce(|| "x2 = x * x", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+            cs.enforce(|| "y - x - 5 = x2 * x", |lc| lc + x2, |lc| lc + x,
+                |lc| lc + y - x - (E::Fr::from_str("5").unwrap(), CS::one()));
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_bellman_compatibility() {
+        let rng = &mut OsRng::new().unwrap();
+        let x = Fr::from_str("3").unwrap();
+        let y = Fr::from_str("35").unwrap();
+
+        let params = generate_random_parameters::<Bls12, _, _>(CubeDemo { x: None }, rng).unwrap();
+        let bellman_params = bellman::groth16::generate_random_parameters::<Bls12, _, _>(CubeDemo { x: None }, rng).unwrap();
+
+        for params in [&params, &bellman_params].iter() {
+            let pvk = prepare_verifying_key(&params.vk);
+
+            let proof = create_random_proof(CubeDemo { x: Some(x) }, params, rng).unwrap();
+            assert!(verify_proof(&pvk, &proof, &[y]).unwrap(), "proof should be valid");
+            assert!(!verify_proof(&pvk, &proof, &[x]).unwrap(), "proof should be invalid for other inputs");
+
+            let proof = bellman::groth16::create_random_proof(CubeDemo { x: Some(x) }, params, rng).unwrap();
+            assert!(verify_proof(&pvk, &proof, &[y]).unwrap(), "bellman proof should be valid");
+        }
+    }
+
+    #[test]
+    fn test_same_proof_as_bellman() {
+        let rng = &mut OsRng::new().unwrap();
+        let x = Fr::from_str("3").unwrap();
+        let params = bellman::groth16::generate_random_parameters::<Bls12, _, _>(CubeDemo { x: None }, rng).unwrap();
+
+        let write = |proof: Proof<Bls12>| {
+            let mut res = vec![];
+            proof.write(&mut res).unwrap();
+            res
+        };
+
+        for _ in 0..3 {
+            let (r, s): (Fr, Fr) = (rng.gen(), rng.gen());
+            let proof = create_proof(CubeDemo { x: Some(x) }, &params, r, s).unwrap();
+            let bellman_proof = bellman::groth16::create_proof(CubeDemo { x: Some(x) }, &params, r, s).unwrap();
+            assert_eq!(write(proof), write(bellman_proof), "proofs with the same r and s should be the same");
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Wallet keys derived from a BIP-39 seed.
+//!
+//! `ExtendedSpendingKey` is a node of the hardened derivation tree: the master node is Blake2b of the seed,
+//! a child is Blake2b of the parent key and the index keyed by the parent chain code. The spending key `sk`
+//! of a node is the key of `transactions`: the owner of notes is `pubkey(sk)` and the nullifiers are keyed
+//! by `sk * ProofGenerationKey`, which makes it the `ProofGenerationKey` and `ViewingKey` of `sapling_crypto`
+//! with `ask = nsk = sk`.
+//!
+//! The incoming viewing key is `ivk` of that `ViewingKey` with the owner, it decrypts and recognises the notes
+//! of the owner but cannot spend them. `ShieldedAddress` is the owner with the transmission key `pubkey(ivk)`
+//! the notes are encrypted to.
+//!
+//! Keys and addresses are exported as Base58 strings of a type byte, the payload and a Blake2s checksum.
+
+use sapling_crypto::jubjub::{JubjubEngine, FixedGenerators, JubjubParams};
+use sapling_crypto::primitives::{ProofGenerationKey, ViewingKey};
+
+use pairing::{PrimeField, PrimeFieldRepr};
+
+use blake2_rfc::blake2b::Blake2b;
+use blake2_rfc::blake2s::Blake2s;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
+use rand::Rng;
+
+use std::{fmt, io};
+
+use crate::fieldtools;
+use crate::note_encryption;
+use crate::serialization::read_fr_repr_be;
+use crate::transactions::{NoteData, nullifier, pubkey};
+
+
+pub const MIN_SEED_LEN: usize = 16;
+pub const MAX_SEED_LEN: usize = 64;
+
+const MASTER_PERSONALIZATION: &[u8; 16] = b"ZWaves_MasterKey";
+const CHILD_PERSONALIZATION: &[u8; 16] = b"ZWaves_ChildKey_";
+const SPENDING_KEY_PERSONALIZATION: &[u8; 16] = b"ZWaves_SpendKey_";
+const CHECKSUM_PERSONALIZATION: &[u8; 8] = b"ZWKeyChk";
+
+const CHECKSUM_LEN: usize = 4;
+
+const EXTENDED_SPENDING_KEY_TYPE: u8 = 1;
+const INCOMING_VIEWING_KEY_TYPE: u8 = 2;
+const SHIELDED_ADDRESS_TYPE: u8 = 3;
+
+
+fn error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_fr<E: JubjubEngine>(data: &[u8]) -> io::Result<E::Fr> {
+    let repr = read_fr_repr_be::<E::Fr>(data)?;
+    E::Fr::from_repr(repr).map_err(|_| error("not a field element"))
+}
+
+fn write_fr<E: JubjubEngine>(fr: &E::Fr, res: &mut Vec<u8>) {
+    fr.into_repr().write_be(res).unwrap();
+}
+
+fn checksum(data: &[u8]) -> Vec<u8> {
+    let mut h = Blake2s::with_params(32, &[], &[], CHECKSUM_PERSONALIZATION);
+    h.update(data);
+    h.finalize().as_ref()[..CHECKSUM_LEN].to_vec()
+}
+
+fn encode(key_type: u8, payload: &[u8]) -> String {
+    let mut data = vec![key_type];
+    data.extend_from_slice(payload);
+    let checksum = checksum(&data);
+    data.extend(checksum);
+    bs58::encode(data).into_string()
+}
+
+fn decode(key_type: u8, len: usize, s: &str) -> io::Result<Vec<u8>> {
+    let data = bs58::decode(s).into_vec().map_err(|_| error("not a Base58 string"))?;
+    if data.len() != 1 + len + CHECKSUM_LEN {
+        return Err(error("wrong key length"));
+    }
+
+    let (data, sum) = data.split_at(1 + len);
+    if checksum(data) != sum {
+        return Err(error("wrong key checksum"));
+    }
+    if data[0] != key_type {
+        return Err(error("wrong key type"));
+    }
+    Ok(data[1..].to_vec())
+}
+
+
+/// Node of the hardened derivation tree.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedSpendingKey {
+    pub depth: u8,
+    pub child_index: u32,
+    pub chain_code: [u8; 32],
+    pub key: [u8; 32]
+}
+
+/// The key and the chain code are secret, they are not printed.
+impl fmt::Debug for ExtendedSpendingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedSpendingKey")
+            .field("depth", &self.depth)
+            .field("child_index", &self.child_index)
+            .field("chain_code", &"<hidden>")
+            .field("key", &"<hidden>")
+            .finish()
+    }
+}
+
+impl ExtendedSpendingKey {
+    const LEN: usize = 1 + 4 + 32 + 32;
+
+    fn from_hash(depth: u8, child_index: u32, h: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        key.copy_from_slice(&h[..32]);
+        chain_code.copy_from_slice(&h[32..64]);
+        ExtendedSpendingKey { depth, child_index, chain_code, key }
+    }
+
+    /// Master key of the 16 to 64 bytes seed, a BIP-39 seed is 64 bytes.
+    pub fn master(seed: &[u8]) -> io::Result<Self> {
+        if seed.len() < MIN_SEED_LEN || seed.len() > MAX_SEED_LEN {
+            return Err(error("wrong seed length"));
+        }
+
+        let mut h = Blake2b::with_params(64, &[], &[], MASTER_PERSONALIZATION);
+        h.update(seed);
+        Ok(Self::from_hash(0, 0, h.finalize().as_ref()))
+    }
+
+    /// Hardened child at the `index`.
+    pub fn derive_child(&self, index: u32) -> io::Result<Self> {
+        let depth = self.depth.checked_add(1).ok_or_else(|| error("derivation path is too long"))?;
+
+        let mut data = self.key.to_vec();
+        data.write_u32::<LittleEndian>(index).unwrap();
+
+        let mut h = Blake2b::with_params(64, &self.chain_code, &[], CHILD_PERSONALIZATION);
+        h.update(&data);
+        Ok(Self::from_hash(depth, index, h.finalize().as_ref()))
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> io::Result<Self> {
+        path.iter().try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    pub fn spending_key<E: JubjubEngine>(&self) -> SpendingKey<E> {
+        let mut h = Blake2b::with_params(64, &[], &[], SPENDING_KEY_PERSONALIZATION);
+        h.update(&self.key);
+        let mut h = h.finalize().as_ref()[..32].to_vec();
+
+        // Drop the most significant two bits, so it is less than the modulus.
+        h[31] &= 0b0011_1111;
+
+        let mut repr = <E::Fr as PrimeField>::Repr::default();
+        repr.read_le(&h[..]).unwrap();
+        SpendingKey { sk: E::Fr::from_repr(repr).expect("should be a valid field element") }
+    }
+
+    pub fn to_base58(&self) -> String {
+        let mut payload = vec![self.depth];
+        payload.write_u32::<BigEndian>(self.child_index).unwrap();
+        payload.extend_from_slice(&self.key);
+        payload.extend_from_slice(&self.chain_code);
+        encode(EXTENDED_SPENDING_KEY_TYPE, &payload)
+    }
+
+    pub fn from_base58(s: &str) -> io::Result<Self> {
+        let data = decode(EXTENDED_SPENDING_KEY_TYPE, Self::LEN, s)?;
+        Ok(Self::from_hash(data[0], BigEndian::read_u32(&data[1..5]), &data[5..]))
+    }
+}
+
+
+/// The `sk` of `transactions`, it spends the notes of the owner `pubkey(sk)`.
+#[derive(Clone)]
+pub struct SpendingKey<E: JubjubEngine> {
+    pub sk: E::Fr
+}
+
+impl<E: JubjubEngine> SpendingKey<E> {
+    pub fn proof_generation_key(&self, params: &E::Params) -> ProofGenerationKey<E> {
+        let sk = fieldtools::f2f::<E::Fr, E::Fs>(&self.sk);
+        ProofGenerationKey {
+            ak: params.generator(FixedGenerators::SpendingKeyGenerator).mul(sk, params),
+            nsk: sk
+        }
+    }
+
+    pub fn viewing_key(&self, params: &E::Params) -> ViewingKey<E> {
+        self.proof_generation_key(params).into_viewing_key(params)
+    }
+
+    pub fn incoming_viewing_key(&self, params: &E::Params) -> IncomingViewingKey<E> {
+        IncomingViewingKey {
+            ivk: fieldtools::f2f::<E::Fs, E::Fr>(&self.viewing_key(params).ivk()),
+            owner: self.owner(params)
+        }
+    }
+
+    pub fn address(&self, params: &E::Params) -> ShieldedAddress<E> {
+        self.incoming_viewing_key(params).address(params)
+    }
+
+    pub fn owner(&self, params: &E::Params) -> E::Fr {
+        pubkey::<E>(&self.sk, params)
+    }
+
+    pub fn nullifier(&self, note_hash: &E::Fr, params: &E::Params) -> E::Fr {
+        nullifier::<E>(note_hash, &self.sk, params)
+    }
+}
+
+
+/// Decrypts and recognises the notes of the `owner`, it cannot compute their nullifiers nor spend them.
+#[derive(Clone)]
+pub struct IncomingViewingKey<E: JubjubEngine> {
+    pub ivk: E::Fr,
+    pub owner: E::Fr
+}
+
+impl<E: JubjubEngine> IncomingViewingKey<E> {
+    pub fn address(&self, params: &E::Params) -> ShieldedAddress<E> {
+        ShieldedAddress {
+            owner: self.owner,
+            transmission_key: pubkey::<E>(&self.ivk, params)
+        }
+    }
+
+    pub fn try_decrypt_note(&self, data: &[u8], params: &E::Params) -> Option<NoteData<E>> {
+        note_encryption::try_decrypt_note_with(data, &self.ivk, &self.owner, params)
+    }
+
+    pub fn to_base58(&self) -> String {
+        let mut payload = vec![];
+        write_fr::<E>(&self.ivk, &mut payload);
+        write_fr::<E>(&self.owner, &mut payload);
+        encode(INCOMING_VIEWING_KEY_TYPE, &payload)
+    }
+
+    pub fn from_base58(s: &str) -> io::Result<Self> {
+        let data = decode(INCOMING_VIEWING_KEY_TYPE, 64, s)?;
+        Ok(IncomingViewingKey { ivk: read_fr::<E>(&data[..32])?, owner: read_fr::<E>(&data[32..])? })
+    }
+}
+
+
+/// Payment address: the owner of the notes and the transmission key of their encryption.
+#[derive(Clone)]
+pub struct ShieldedAddress<E: JubjubEngine> {
+    pub owner: E::Fr,
+    pub transmission_key: E::Fr
+}
+
+impl<E: JubjubEngine> PartialEq for ShieldedAddress<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.owner == other.owner && self.transmission_key == other.transmission_key
+    }
+}
+
+impl<E: JubjubEngine> ShieldedAddress<E> {
+    /// Encrypts the note to the address, the note should belong to its owner.
+    pub fn encrypt_note<R: Rng>(&self, note: &NoteData<E>, rng: &mut R, params: &E::Params) -> io::Result<Vec<u8>> {
+        if note.owner != self.owner {
+            return Err(error("note does not belong to the address"));
+        }
+        note_encryption::encrypt_note_to(note, &self.transmission_key, rng, params)
+    }
+
+    pub fn to_base58(&self) -> String {
+        let mut payload = vec![];
+        write_fr::<E>(&self.owner, &mut payload);
+        write_fr::<E>(&self.transmission_key, &mut payload);
+        encode(SHIELDED_ADDRESS_TYPE, &payload)
+    }
+
+    pub fn from_base58(s: &str) -> io::Result<Self> {
+        let data = decode(SHIELDED_ADDRESS_TYPE, 64, s)?;
+        Ok(ShieldedAddress { owner: read_fr::<E>(&data[..32])?, transmission_key: read_fr::<E>(&data[32..])? })
+    }
+}
+
+
+#[cfg(test)]
+mod keys_tests {
+    use super::*;
+    use crate::transactions;
+    use pairing::bls12_381::{Bls12, Fr};
+    use sapling_crypto::jubjub::JubjubBls12;
+    use rand::os::OsRng;
+
+    fn note(owner: Fr, rng: &mut OsRng) -> NoteData<Bls12> {
+        NoteData {
+            asset_id: Fr::from_str("1").unwrap(),
+            amount: Fr::from_str("1000").unwrap(),
+            native_amount: Fr::from_str("10").unwrap(),
+            txid: rng.gen(),
+            owner
+        }
+    }
+
+    #[test]
+    fn test_derivation() {
+        let master = ExtendedSpendingKey::master(&[7u8; 64]).unwrap();
+        let child = master.derive_path(&[44, 1, 0]).unwrap();
+
+        assert_eq!(child.depth, 3);
+        assert_eq!(child.child_index, 0);
+        assert_eq!(child, master.derive_child(44).unwrap().derive_child(1).unwrap().derive_child(0).unwrap());
+        assert!(child != master.derive_path(&[44, 1, 1]).unwrap());
+        assert!(master != ExtendedSpendingKey::master(&[8u8; 64]).unwrap());
+        assert_eq!(format!("{:?}", child), r#"ExtendedSpendingKey { depth: 3, child_index: 0, chain_code: "<hidden>", key: "<hidden>" }"#);
+
+        assert!(ExtendedSpendingKey::master(&[7u8; 15]).is_err());
+        assert!(ExtendedSpendingKey::master(&[7u8; 65]).is_err());
+    }
+
+    #[test]
+    fn test_keys() {
+        let params = JubjubBls12::new();
+        let sk = ExtendedSpendingKey::master(&[7u8; 32]).unwrap().spending_key::<Bls12>();
+
+        let pgk = sk.proof_generation_key(&params);
+        assert!(pgk.ak.into_xy().0 == transactions::pubkey::<Bls12>(&sk.sk, &params), "ak should be the owner");
+
+        let ivk = sk.incoming_viewing_key(&params);
+        let address = sk.address(&params);
+        assert!(address.owner == sk.owner(&params));
+        assert!(address == ivk.address(&params));
+        assert!(address.transmission_key != address.owner);
+    }
+
+    #[test]
+    fn test_note_encryption() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let master = ExtendedSpendingKey::master(&[7u8; 32]).unwrap();
+        let (sk_a, sk_b) = (master.derive_child(0).unwrap().spending_key::<Bls12>(), master.derive_child(1).unwrap().spending_key::<Bls12>());
+
+        let address = sk_a.address(&params);
+        let note = note(address.owner, rng);
+        let data = address.encrypt_note(&note, rng, &params).unwrap();
+
+        let decrypted = sk_a.incoming_viewing_key(&params).try_decrypt_note(&data, &params).unwrap();
+        assert!(transactions::note_hash(&decrypted, &params) == transactions::note_hash(&note, &params));
+        assert!(sk_b.incoming_viewing_key(&params).try_decrypt_note(&data, &params).is_none());
+
+        // the viewing key is not the spending key
+        assert!(note_encryption::try_decrypt_note(&data, &sk_a.sk, &params).is_none());
+        assert!(sk_b.address(&params).encrypt_note(&note, rng, &params).is_err());
+    }
+
+    #[test]
+    fn test_strings() {
+        let params = JubjubBls12::new();
+        let key = ExtendedSpendingKey::master(&[7u8; 32]).unwrap().derive_path(&[1, 2]).unwrap();
+        let sk = key.spending_key::<Bls12>();
+        let (ivk, address) = (sk.incoming_viewing_key(&params), sk.address(&params));
+
+        assert_eq!(ExtendedSpendingKey::from_base58(&key.to_base58()).unwrap(), key);
+        let ivk_imported = IncomingViewingKey::<Bls12>::from_base58(&ivk.to_base58()).unwrap();
+        assert!(ivk_imported.ivk == ivk.ivk && ivk_imported.owner == ivk.owner);
+        assert!(ShieldedAddress::<Bls12>::from_base58(&address.to_base58()).unwrap() == address);
+
+        assert!(ShieldedAddress::<Bls12>::from_base58(&ivk.to_base58()).is_err(), "key type should be checked");
+        let mut s = address.to_base58().into_bytes();
+        s[10] = if s[10] == b'2' { b'3' } else { b'2' };
+        assert!(ShieldedAddress::<Bls12>::from_base58(&String::from_utf8(s).unwrap()).is_err(), "checksum should be checked");
+    }
+}
@@ -4,6 +4,14 @@ extern crate lazy_static;
 pub mod pedersen_hasher;
 pub mod circuit;
 pub mod verifier;
+pub mod groth16;
 pub mod serialization;
 pub mod fieldtools;
-pub mod transactions;
\ No newline at end of file
+pub mod transactions;
+pub mod snarkjs;
+pub mod public_inputs;
+pub mod packed_asset;
+pub mod merkle_tree;
+pub mod sparse_merkle_tree;
+pub mod note_encryption;
+pub mod keys;
\ No newline at end of file
@@ -0,0 +1,303 @@
+//! Append-only Pedersen Merkle tree with the hashing of `pedersen_hasher::merkle_root`: the nodes at the `level`
+//! are compressed with `Personalization::MerkleTree(level)`, empty subtrees are `merkle_defaults`.
+
+use sapling_crypto::jubjub::JubjubEngine;
+use sapling_crypto::pedersen_hash::Personalization;
+
+use pairing::Field;
+
+use std::io;
+
+use crate::pedersen_hasher::{compress, merkle_defaults, merkle_root};
+
+
+/// Levels are personalized with 6 bits.
+pub const MAX_DEPTH: usize = 63;
+
+
+#[derive(Clone)]
+enum Nodes<E: JubjubEngine> {
+    /// Nodes by levels, leaves first. Nodes right of the last leaf are not stored.
+    Full(Vec<Vec<E::Fr>>),
+    /// Proof of the next empty leaf only: the hashes of the complete subtrees left of it and defaults.
+    Frontier(Vec<E::Fr>)
+}
+
+#[derive(Clone)]
+pub struct IncrementalMerkleTree<E: JubjubEngine> {
+    depth: usize,
+    size: u64,
+    root: E::Fr,
+    defaults: Vec<E::Fr>,
+    nodes: Nodes<E>
+}
+
+fn error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<E: JubjubEngine> IncrementalMerkleTree<E> {
+    /// Empty tree keeping all the nodes, it gives proofs for any index.
+    pub fn new(depth: usize, params: &E::Params) -> io::Result<Self> {
+        if depth > MAX_DEPTH {
+            return Err(error("wrong merkle proof length"));
+        }
+
+        let defaults = merkle_defaults::<E>(depth + 1, params);
+        Ok(IncrementalMerkleTree {
+            depth,
+            size: 0,
+            root: defaults[depth],
+            nodes: Nodes::Full(vec![vec![]; depth + 1]),
+            defaults
+        })
+    }
+
+    /// Empty tree keeping only the frontier, it gives only the proof of the next leaf.
+    pub fn new_frontier(depth: usize, params: &E::Params) -> io::Result<Self> {
+        let mut res = Self::new(depth, params)?;
+        res.nodes = Nodes::Frontier(res.defaults[..depth].to_vec());
+        Ok(res)
+    }
+
+    /// Frontier tree with `size` leaves, `sibling` is the proof of the leaf at the index `size`.
+    pub fn from_frontier(depth: usize, size: u64, sibling: &[E::Fr], params: &E::Params) -> io::Result<Self> {
+        if depth > MAX_DEPTH || sibling.len() != depth || size >> depth != 0 {
+            return Err(error("wrong merkle proof length"));
+        }
+
+        let mut res = Self::new_frontier(depth, params)?;
+
+        // the subtrees right of the next leaf are empty
+        if (0..depth).any(|i| (size >> i) & 1 == 0 && sibling[i] != res.defaults[i]) {
+            return Err(error("sibling is not a proof of the next leaf"));
+        }
+
+        res.size = size;
+        res.nodes = Nodes::Frontier(sibling.to_vec());
+        res.root = res.frontier_root(params);
+        Ok(res)
+    }
+
+    /// The same tree keeping only the frontier.
+    pub fn to_frontier(&self) -> Self {
+        if self.is_frontier() {
+            return self.clone();
+        }
+
+        let frontier = (0..self.depth).map(|i| {
+            if (self.size >> i) & 1 == 1 { self.node(i, (self.size >> i) ^ 1) } else { self.defaults[i] }
+        }).collect();
+
+        IncrementalMerkleTree { nodes: Nodes::Frontier(frontier), ..self.clone() }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of the leaves, the index of the next one.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn root(&self) -> E::Fr {
+        self.root
+    }
+
+    pub fn is_frontier(&self) -> bool {
+        match self.nodes {
+            Nodes::Frontier(_) => true,
+            Nodes::Full(_) => false
+        }
+    }
+
+    /// Siblings of the leaf at the `index` from the bottom up. A frontier tree has only the proof of the next leaf.
+    pub fn proof(&self, index: u64) -> io::Result<Vec<E::Fr>> {
+        if index >> self.depth != 0 {
+            return Err(error("index does not fit the tree"));
+        }
+
+        match &self.nodes {
+            Nodes::Full(_) => Ok((0..self.depth).map(|i| self.node(i, (index >> i) ^ 1)).collect()),
+            Nodes::Frontier(frontier) if index == self.size => Ok(frontier.clone()),
+            Nodes::Frontier(_) => Err(error("frontier tree has only the proof of the next leaf"))
+        }
+    }
+
+    /// Appends the leaf and returns its index.
+    pub fn append(&mut self, leaf: E::Fr, params: &E::Params) -> io::Result<u64> {
+        self.append_many(&[leaf], params)
+    }
+
+    /// Appends the leaves and returns the index of the first one.
+    pub fn append_many(&mut self, leaves: &[E::Fr], params: &E::Params) -> io::Result<u64> {
+        let index = self.size;
+        let size = index.checked_add(leaves.len() as u64).filter(|&size| size <= 1 << self.depth)
+            .ok_or_else(|| error("leaves do not fit the tree"))?;
+
+        if leaves.is_empty() {
+            return Ok(index);
+        }
+
+        let defaults = &self.defaults;
+
+        match &mut self.nodes {
+            Nodes::Full(nodes) => {
+                nodes[0].extend_from_slice(leaves);
+
+                // only the nodes over the new leaves change, the first of them may be partial
+                for i in 1..self.depth + 1 {
+                    let (lower, upper) = nodes.split_at_mut(i);
+                    let (lower, level) = (&lower[i - 1], &mut upper[0]);
+                    let cell = |j: u64| lower.get(j as usize).cloned().unwrap_or(defaults[i - 1]);
+
+                    level.truncate((index >> i) as usize);
+                    for j in (index >> i)..((size - 1) >> i) + 1 {
+                        level.push(compress::<E>(&cell(2 * j), &cell(2 * j + 1), Personalization::MerkleTree(i - 1), params));
+                    }
+                }
+
+                self.size = size;
+                self.root = nodes[self.depth][0];
+            },
+            Nodes::Frontier(frontier) => {
+                for (k, leaf) in leaves.iter().enumerate() {
+                    let next = index + k as u64 + 1;
+                    let zeros = (next.trailing_zeros() as usize).min(self.depth);
+
+                    // the leaf completes the subtrees up to the level of the lowest set bit of the next index
+                    let mut cur = *leaf;
+                    for i in 0..zeros {
+                        cur = compress::<E>(&frontier[i], &cur, Personalization::MerkleTree(i), params);
+                        frontier[i] = defaults[i];
+                    }
+
+                    if zeros < self.depth {
+                        frontier[zeros] = cur;
+                    } else {
+                        self.root = cur;
+                    }
+                }
+
+                self.size = size;
+                if size >> self.depth == 0 {
+                    self.root = self.frontier_root(params);
+                }
+            }
+        }
+
+        Ok(index)
+    }
+
+    fn node(&self, level: usize, index: u64) -> E::Fr {
+        match &self.nodes {
+            Nodes::Full(nodes) => nodes[level].get(index as usize).cloned().unwrap_or(self.defaults[level]),
+            Nodes::Frontier(_) => unreachable!()
+        }
+    }
+
+    fn frontier_root(&self, params: &E::Params) -> E::Fr {
+        match &self.nodes {
+            Nodes::Frontier(frontier) => merkle_root::<E>(frontier, self.size, &E::Fr::zero(), params),
+            Nodes::Full(_) => unreachable!()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod merkle_tree_tests {
+    use super::*;
+    use crate::pedersen_hasher;
+    use pairing::bls12_381::{Bls12, Fr};
+    use sapling_crypto::jubjub::JubjubBls12;
+    use rand::os::OsRng;
+    use rand::Rng;
+
+    #[test]
+    fn test_proofs() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let leaves = (0..23).map(|_| rng.gen()).collect::<Vec<Fr>>();
+
+        let mut tree = IncrementalMerkleTree::<Bls12>::new(8, &params).unwrap();
+        assert_eq!(tree.append_many(&leaves[..5], &params).unwrap(), 0);
+        assert_eq!(tree.append(leaves[5], &params).unwrap(), 5);
+        assert_eq!(tree.append_many(&leaves[6..], &params).unwrap(), 6);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i as u64).unwrap();
+            assert!(pedersen_hasher::merkle_root::<Bls12>(&proof, i as u64, leaf, &params) == tree.root(), "proof of the leaf should give the root");
+        }
+
+        let proof = tree.proof(200).unwrap();
+        assert!(pedersen_hasher::merkle_root::<Bls12>(&proof, 200, &Fr::zero(), &params) == tree.root(), "proof of an empty leaf should give the root");
+        assert!(tree.proof(256).is_err());
+    }
+
+    #[test]
+    fn test_frontier() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let leaves = (0..37).map(|_| rng.gen()).collect::<Vec<Fr>>();
+
+        let mut full = IncrementalMerkleTree::<Bls12>::new(6, &params).unwrap();
+        let mut frontier = IncrementalMerkleTree::<Bls12>::new_frontier(6, &params).unwrap();
+        assert!(full.root() == frontier.root());
+
+        for chunk in leaves.chunks(5) {
+            full.append_many(chunk, &params).unwrap();
+            frontier.append_many(chunk, &params).unwrap();
+
+            assert!(full.root() == frontier.root(), "roots should be the same");
+            assert!(full.proof(full.size()).unwrap() == frontier.proof(frontier.size()).unwrap(), "frontiers should be the same");
+            assert!(full.to_frontier().proof(full.size()).unwrap() == frontier.proof(frontier.size()).unwrap());
+        }
+
+        assert!(frontier.proof(0).is_err());
+
+        let sibling = frontier.proof(frontier.size()).unwrap();
+        let resumed = IncrementalMerkleTree::<Bls12>::from_frontier(6, frontier.size(), &sibling, &params).unwrap();
+        assert!(resumed.root() == frontier.root());
+        assert!(IncrementalMerkleTree::<Bls12>::from_frontier(6, frontier.size() + 1, &sibling, &params).is_err());
+    }
+
+    #[test]
+    fn test_update_merkle_proof() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let leaves = (0..19).map(|_| rng.gen()).collect::<Vec<Fr>>();
+        let defaults = merkle_defaults::<Bls12>(16, &params);
+
+        let mut tree = IncrementalMerkleTree::<Bls12>::new_frontier(16, &params).unwrap();
+        tree.append_many(&leaves[..7], &params).unwrap();
+        let (old_root, sibling) = (tree.root(), tree.proof(7).unwrap());
+        tree.append_many(&leaves[7..], &params).unwrap();
+
+        let (root, proof) = pedersen_hasher::update_merkle_root_and_proof::<Bls12>(&old_root, &sibling, 7, &leaves[7..], &defaults, &params).unwrap();
+        assert!(root == tree.root(), "roots should be the same as of pedersen_hasher");
+        assert!(proof == tree.proof(19).unwrap(), "proofs should be the same as of pedersen_hasher");
+    }
+
+    #[test]
+    fn test_full_tree() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let leaves = (0..8).map(|_| rng.gen()).collect::<Vec<Fr>>();
+
+        let mut full = IncrementalMerkleTree::<Bls12>::new(3, &params).unwrap();
+        let mut frontier = IncrementalMerkleTree::<Bls12>::new_frontier(3, &params).unwrap();
+        full.append_many(&leaves, &params).unwrap();
+        frontier.append_many(&leaves[..3], &params).unwrap();
+        frontier.append_many(&leaves[3..], &params).unwrap();
+
+        assert!(full.root() == frontier.root());
+        assert!(full.append(leaves[0], &params).is_err());
+        assert!(frontier.append(leaves[0], &params).is_err());
+        assert!(frontier.proof(8).is_err());
+
+        assert!(IncrementalMerkleTree::<Bls12>::new(MAX_DEPTH + 1, &params).is_err());
+        assert!(IncrementalMerkleTree::<Bls12>::new_frontier(MAX_DEPTH + 1, &params).is_err());
+    }
+}
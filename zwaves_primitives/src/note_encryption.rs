@@ -0,0 +1,200 @@
+//! Encryption of notes to their owners for the `m` blob of `transferExternal`.
+//!
+//! The sender takes a random ephemeral key `esk`, the symmetric key is Blake2s of `edh(owner, esk)` and
+//! `epk = pubkey(esk)`, the note plaintext is encrypted with ChaCha20-Poly1305 under it. The owner gets the same
+//! key from `edh(epk, sk)`, so a wallet finds its notes by trial decryption of every message with its secret key.
+//! The addresses of `keys` encrypt to a separate transmission key instead of the owner, so the incoming viewing key
+//! decrypts the notes without the spending key.
+//!
+//! Ciphertext is `epk || ChaCha20-Poly1305(plaintext) || tag`. Plaintext is `asset_id || amount || native_amount`
+//! as 8 bytes big-endian each and `txid || owner` as 32 bytes big-endian field elements.
+
+use sapling_crypto::jubjub::JubjubEngine;
+
+use pairing::{PrimeField, PrimeFieldRepr};
+
+use blake2_rfc::blake2s::Blake2s;
+use byteorder::{BigEndian, ByteOrder};
+use chacha20poly1305::ChaCha20Poly1305;
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::aead::generic_array::GenericArray;
+use rand::Rng;
+
+use std::io;
+
+use crate::serialization::read_fr_repr_be;
+use crate::transactions::{NoteData, edh, pubkey};
+
+
+pub const FR_LEN: usize = 32;
+pub const NOTE_PLAINTEXT_LEN: usize = 3 * 8 + 2 * FR_LEN;
+pub const TAG_LEN: usize = 16;
+pub const NOTE_CIPHERTEXT_LEN: usize = FR_LEN + NOTE_PLAINTEXT_LEN + TAG_LEN;
+
+const NOTE_KDF_PERSONALIZATION: &[u8; 8] = b"ZWNoteKD";
+
+/// Every key is derived from a fresh ephemeral key and encrypts one note only, so the nonce is constant.
+const NOTE_NONCE: [u8; 12] = [0u8; 12];
+
+
+fn error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_fr<E: JubjubEngine>(data: &[u8]) -> io::Result<E::Fr> {
+    let repr = read_fr_repr_be::<E::Fr>(data)?;
+    E::Fr::from_repr(repr).map_err(|_| error("not a field element"))
+}
+
+fn write_fr<E: JubjubEngine>(fr: &E::Fr, res: &mut Vec<u8>) {
+    fr.into_repr().write_be(res).unwrap();
+}
+
+fn fr_to_u64<E: JubjubEngine>(fr: &E::Fr) -> io::Result<u64> {
+    let repr = fr.into_repr();
+    if repr.as_ref()[1..].iter().any(|&limb| limb != 0) {
+        return Err(error("value does not fit 64 bits"));
+    }
+    Ok(repr.as_ref()[0])
+}
+
+fn u64_to_fr<E: JubjubEngine>(n: u64) -> E::Fr {
+    E::Fr::from_repr(n.into()).unwrap()
+}
+
+
+pub fn note_to_bytes<E: JubjubEngine>(note: &NoteData<E>) -> io::Result<Vec<u8>> {
+    let mut res = vec![0u8; 24];
+    BigEndian::write_u64(&mut res[0..8], fr_to_u64::<E>(&note.asset_id)?);
+    BigEndian::write_u64(&mut res[8..16], fr_to_u64::<E>(&note.amount)?);
+    BigEndian::write_u64(&mut res[16..24], fr_to_u64::<E>(&note.native_amount)?);
+    write_fr::<E>(&note.txid, &mut res);
+    write_fr::<E>(&note.owner, &mut res);
+    Ok(res)
+}
+
+pub fn note_from_bytes<E: JubjubEngine>(data: &[u8]) -> io::Result<NoteData<E>> {
+    if data.len() != NOTE_PLAINTEXT_LEN {
+        return Err(error("wrong note plaintext length"));
+    }
+
+    Ok(NoteData {
+        asset_id: u64_to_fr::<E>(BigEndian::read_u64(&data[0..8])),
+        amount: u64_to_fr::<E>(BigEndian::read_u64(&data[8..16])),
+        native_amount: u64_to_fr::<E>(BigEndian::read_u64(&data[16..24])),
+        txid: read_fr::<E>(&data[24..24 + FR_LEN])?,
+        owner: read_fr::<E>(&data[24 + FR_LEN..])?
+    })
+}
+
+fn note_key<E: JubjubEngine>(shared: &E::Fr, epk: &E::Fr) -> ChaCha20Poly1305 {
+    let mut data = vec![];
+    write_fr::<E>(shared, &mut data);
+    write_fr::<E>(epk, &mut data);
+
+    let mut h = Blake2s::with_params(32, &[], &[], NOTE_KDF_PERSONALIZATION);
+    h.update(&data);
+    ChaCha20Poly1305::new(GenericArray::from_slice(h.finalize().as_ref()))
+}
+
+
+/// Encrypts the note to its owner, the result is `NOTE_CIPHERTEXT_LEN` bytes.
+pub fn encrypt_note<E: JubjubEngine, R: Rng>(note: &NoteData<E>, rng: &mut R, params: &E::Params) -> io::Result<Vec<u8>> {
+    encrypt_note_to(note, &note.owner, rng, params)
+}
+
+/// Encrypts the note to the `transmission_key`, the public key of the decrypting secret key.
+pub fn encrypt_note_to<E: JubjubEngine, R: Rng>(note: &NoteData<E>, transmission_key: &E::Fr, rng: &mut R, params: &E::Params) -> io::Result<Vec<u8>> {
+    let plaintext = note_to_bytes(note)?;
+
+    let esk: E::Fr = rng.gen();
+    let epk = pubkey::<E>(&esk, params);
+    let shared = edh::<E>(transmission_key, &esk, params).ok_or_else(|| error("transmission key is not an elliptic curve point"))?;
+
+    let ciphertext = note_key::<E>(&shared, &epk).encrypt(GenericArray::from_slice(&NOTE_NONCE), &plaintext[..])
+        .map_err(|_| error("could not encrypt note"))?;
+
+    let mut res = vec![];
+    write_fr::<E>(&epk, &mut res);
+    res.extend(ciphertext);
+    Ok(res)
+}
+
+/// Decrypts the note if it is encrypted to the owner of the secret key, `None` for notes of other owners
+/// and malformed ciphertexts.
+pub fn try_decrypt_note<E: JubjubEngine>(data: &[u8], sk: &E::Fr, params: &E::Params) -> Option<NoteData<E>> {
+    try_decrypt_note_with(data, sk, &pubkey::<E>(sk, params), params)
+}
+
+/// Decrypts the note encrypted to the public key of `dk` if it belongs to the `owner`.
+pub fn try_decrypt_note_with<E: JubjubEngine>(data: &[u8], dk: &E::Fr, owner: &E::Fr, params: &E::Params) -> Option<NoteData<E>> {
+    if data.len() != NOTE_CIPHERTEXT_LEN {
+        return None;
+    }
+
+    let epk = read_fr::<E>(&data[..FR_LEN]).ok()?;
+    let shared = edh::<E>(&epk, dk, params)?;
+
+    let plaintext = note_key::<E>(&shared, &epk).decrypt(GenericArray::from_slice(&NOTE_NONCE), &data[FR_LEN..]).ok()?;
+    let note = note_from_bytes::<E>(&plaintext).ok()?;
+
+    if note.owner != *owner {
+        return None;
+    }
+    Some(note)
+}
+
+
+#[cfg(test)]
+mod note_encryption_tests {
+    use super::*;
+    use pairing::bls12_381::{Bls12, Fr};
+    use sapling_crypto::jubjub::JubjubBls12;
+    use rand::os::OsRng;
+
+    fn random_note(owner: Fr, rng: &mut OsRng) -> NoteData<Bls12> {
+        NoteData {
+            asset_id: u64_to_fr::<Bls12>(rng.gen()),
+            amount: u64_to_fr::<Bls12>(rng.gen()),
+            native_amount: u64_to_fr::<Bls12>(rng.gen()),
+            txid: rng.gen(),
+            owner
+        }
+    }
+
+    fn note_eq(a: &NoteData<Bls12>, b: &NoteData<Bls12>) -> bool {
+        note_to_bytes(a).unwrap() == note_to_bytes(b).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+
+        let (sk_a, sk_b): (Fr, Fr) = (rng.gen(), rng.gen());
+        let note = random_note(pubkey::<Bls12>(&sk_a, &params), rng);
+
+        let data = encrypt_note(&note, rng, &params).unwrap();
+        assert_eq!(data.len(), NOTE_CIPHERTEXT_LEN);
+        assert!(note_eq(&try_decrypt_note::<Bls12>(&data, &sk_a, &params).unwrap(), &note), "owner should decrypt the note");
+        assert!(try_decrypt_note::<Bls12>(&data, &sk_b, &params).is_none(), "other key should not decrypt the note");
+
+        let other = encrypt_note(&note, rng, &params).unwrap();
+        assert!(data != other, "ephemeral keys should be random");
+
+        let mut tampered = data.clone();
+        tampered[FR_LEN + 3] ^= 1;
+        assert!(try_decrypt_note::<Bls12>(&tampered, &sk_a, &params).is_none());
+        assert!(try_decrypt_note::<Bls12>(&data[1..], &sk_a, &params).is_none());
+    }
+
+    #[test]
+    fn test_wrong_note() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+
+        let mut note = random_note(pubkey::<Bls12>(&rng.gen(), &params), rng);
+        note.amount = Fr::from_str("18446744073709551616").unwrap();
+        assert!(note_to_bytes(&note).is_err(), "amount should fit 64 bits");
+    }
+}
@@ -0,0 +1,144 @@
+//! The `packed_asset` public input of the Transfer circuit, unpacked by `circuit::transactions::signed_asset_unpack`.
+//!
+//! The amounts are the change of the pool balance: the inputs of a transfer plus the amounts are its outputs,
+//! so a deposit is positive and a withdrawal is negative. The contract reads them with `toInt`, negative values
+//! are packed in two's complement.
+
+use pairing::{PrimeField, PrimeFieldRepr, Field};
+
+use std::convert::TryFrom;
+use std::io;
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PackedAsset {
+    pub asset_id: u64,
+    pub amount: i64,
+    pub native_amount: i64
+}
+
+fn overflow() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "amount overflow")
+}
+
+fn signed_fr<F: PrimeField>(x: i64) -> F {
+    let mut res = F::from_repr(F::Repr::from(x.unsigned_abs())).unwrap();
+    if x < 0 {
+        res.negate();
+    }
+    res
+}
+
+impl PackedAsset {
+    pub fn new(asset_id: u64, amount: i64, native_amount: i64) -> Self {
+        PackedAsset { asset_id, amount, native_amount }
+    }
+
+    /// Deposit of the `payment` of the native asset, the `fee` of the accumulator is taken from it.
+    pub fn deposit(payment: u64, fee: u64) -> io::Result<Self> {
+        let native_amount = payment.checked_sub(fee).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "fee exceeds the deposit"))?;
+        let native_amount = i64::try_from(native_amount).map_err(|_| overflow())?;
+        Ok(PackedAsset::new(0, 0, native_amount))
+    }
+
+    /// Withdrawal of the `amount` of the native asset, the `fee` is paid from the pool too.
+    pub fn withdraw(amount: u64, fee: u64) -> io::Result<Self> {
+        let total = amount.checked_add(fee).ok_or_else(overflow)?;
+        let native_amount = i64::try_from(total).map_err(|_| overflow())?;
+        Ok(PackedAsset::new(0, 0, -native_amount))
+    }
+
+    /// Transfer inside the pool, only the `fee` leaves it.
+    pub fn transfer(fee: u64) -> io::Result<Self> {
+        PackedAsset::withdraw(0, fee)
+    }
+
+    /// asset_id in the bits 0..64, amount in 64..128 and native_amount in 128..192.
+    pub fn to_fr<F: PrimeField>(&self) -> F {
+        let mut repr = F::Repr::default();
+        {
+            let limbs = repr.as_mut();
+            limbs[0] = self.asset_id;
+            limbs[1] = self.amount as u64;
+            limbs[2] = self.native_amount as u64;
+        }
+        F::from_repr(repr).unwrap()
+    }
+
+    pub fn from_fr<F: PrimeField>(fr: &F) -> io::Result<Self> {
+        let repr = fr.into_repr();
+        let limbs = repr.as_ref();
+
+        if limbs[3..].iter().any(|&e| e != 0) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "packed asset should fit in 192 bits"));
+        }
+
+        Ok(PackedAsset::new(limbs[0], limbs[1] as i64, limbs[2] as i64))
+    }
+
+    /// The amount as the field element of `signed_asset_unpack`, negative values are `-|amount|`.
+    pub fn amount_fr<F: PrimeField>(&self) -> F {
+        signed_fr(self.amount)
+    }
+
+    pub fn native_amount_fr<F: PrimeField>(&self) -> F {
+        signed_fr(self.native_amount)
+    }
+}
+
+
+#[cfg(test)]
+mod packed_asset_tests {
+    use super::*;
+    use pairing::bls12_381::{Fr, FrRepr};
+    use rand::os::OsRng;
+    use rand::Rng;
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(PackedAsset::deposit(10000000, 1000000).unwrap(), PackedAsset::new(0, 0, 9000000));
+        assert_eq!(PackedAsset::withdraw(10000000, 1000000).unwrap(), PackedAsset::new(0, 0, -11000000));
+        assert_eq!(PackedAsset::transfer(1000000).unwrap(), PackedAsset::new(0, 0, -1000000));
+
+        assert!(PackedAsset::deposit(1, 2).is_err());
+        assert!(PackedAsset::deposit(u64::max_value(), 0).is_err());
+        assert!(PackedAsset::withdraw(i64::max_value() as u64, 1).is_err());
+        assert!(PackedAsset::withdraw(u64::max_value(), 1).is_err());
+    }
+
+    #[test]
+    fn test_deposit_packing() {
+        // the same as `(deposit_amount - accumulatorFee) << 128n` of the demo
+        let fr: Fr = PackedAsset::deposit(10000000, 1000000).unwrap().to_fr();
+        assert_eq!(fr, Fr::from_repr(FrRepr([0, 0, 9000000, 0])).unwrap());
+    }
+
+    #[test]
+    fn test_fr_roundtrip() {
+        let rng = &mut OsRng::new().unwrap();
+
+        for _ in 0..1000 {
+            let asset = PackedAsset::new(rng.gen(), rng.gen(), rng.gen());
+            assert_eq!(PackedAsset::from_fr(&asset.to_fr::<Fr>()).unwrap(), asset);
+        }
+
+        let mut big = Fr::one();
+        for _ in 0..192 {
+            big.double();
+        }
+        assert!(PackedAsset::from_fr(&big).is_err());
+    }
+
+    #[test]
+    fn test_signed_fr() {
+        let mut minus_one = Fr::one();
+        minus_one.negate();
+
+        assert_eq!(PackedAsset::new(0, -1, 0).amount_fr::<Fr>(), minus_one);
+        assert_eq!(PackedAsset::new(0, 0, 1).native_amount_fr::<Fr>(), Fr::one());
+
+        let mut min = PackedAsset::new(0, 0, i64::min_value()).native_amount_fr::<Fr>();
+        min.add_assign(&PackedAsset::new(0, 0, i64::max_value()).native_amount_fr::<Fr>());
+        assert_eq!(min, minus_one);
+    }
+}
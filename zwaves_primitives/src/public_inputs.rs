@@ -0,0 +1,343 @@
+//! Public inputs of the Transfer and UtxoAccumulator circuits in the byte layout of the
+//! Ride contract (`zwaves_demo/ride/zwaves.ride`): concatenated 32 bytes big-endian field elements.
+
+use pairing::{Engine, PrimeField, PrimeFieldRepr};
+
+use blake2_rfc::blake2b::Blake2b;
+
+use std::{fmt, io};
+
+use crate::packed_asset::PackedAsset;
+use crate::serialization::read_fr_repr_be;
+
+
+pub const FR_LEN: usize = 32;
+pub const TRANSFER_INPUTS_LEN: usize = 7 * FR_LEN;
+pub const ACCUMULATOR_INPUTS_LEN: usize = 5 * FR_LEN;
+pub const BOUND_TRANSFER_INPUTS_LEN: usize = 8 * FR_LEN;
+
+
+/// Public inputs of the Transfer circuit.
+#[derive(Clone)]
+pub struct TransferPublicInputs<E: Engine> {
+    pub receiver: E::Fr,
+    pub root_hash: E::Fr,
+    pub packed_asset: PackedAsset,
+    pub out_hash: [E::Fr; 2],
+    pub nf: [E::Fr; 2]
+}
+
+impl<E: Engine> TransferPublicInputs<E> {
+    /// Inputs in the order of the circuit: receiver, root_hash, packed_asset, out_hash and nf.
+    pub fn to_vec(&self) -> Vec<E::Fr> {
+        let mut res = vec![self.receiver, self.root_hash, self.packed_asset.to_fr()];
+        res.extend_from_slice(&self.out_hash);
+        res.extend_from_slice(&self.nf);
+        res
+    }
+
+    pub fn from_vec(inputs: &[E::Fr]) -> io::Result<Self> {
+        if inputs.len() != 7 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong number of transfer inputs"));
+        }
+
+        Ok(TransferPublicInputs {
+            receiver: inputs[0],
+            root_hash: inputs[1],
+            packed_asset: PackedAsset::from_fr(&inputs[2])?,
+            out_hash: [inputs[3], inputs[4]],
+            nf: [inputs[5], inputs[6]]
+        })
+    }
+
+    /// `TRANSFER_INPUTS_LEN` bytes for the `groth16Verify` of the contract, the address of the receiver
+    /// is read from the bytes 6..32 and the signed amounts from the bytes 72..80 and 80..88.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        write_fr_vec::<E>(&self.to_vec())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        if data.len() != TRANSFER_INPUTS_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong transfer inputs length"));
+        }
+
+        Self::from_vec(&read_fr_vec::<E>(data)?)
+    }
+}
+
+
+/// Hash of the `fee` and the `memo` of a transfer for the BoundTransfer circuit: Blake2b-256 of the fee as 8 bytes
+/// big-endian and the memo with the first byte set to zero to fit the field. In Ride it is
+/// `base58'1' + drop(blake2b256(toBytes(fee) + m), 1)`.
+pub fn memo_hash<E: Engine>(fee: u64, memo: &[u8]) -> E::Fr {
+    let mut h = Blake2b::new(32);
+    h.update(&fee.to_be_bytes());
+    h.update(memo);
+
+    let mut data = h.finalize().as_bytes().to_vec();
+    data[0] = 0;
+    E::Fr::from_repr(read_fr_repr_be::<E::Fr>(&data).unwrap()).unwrap()
+}
+
+
+/// Public inputs of the BoundTransfer circuit: the inputs of the Transfer circuit and the `memo_hash`.
+#[derive(Clone)]
+pub struct BoundTransferPublicInputs<E: Engine> {
+    pub transfer: TransferPublicInputs<E>,
+    pub memo_hash: E::Fr
+}
+
+impl<E: Engine> BoundTransferPublicInputs<E> {
+    pub fn to_vec(&self) -> Vec<E::Fr> {
+        let mut res = self.transfer.to_vec();
+        res.push(self.memo_hash);
+        res
+    }
+
+    pub fn from_vec(inputs: &[E::Fr]) -> io::Result<Self> {
+        if inputs.len() != 8 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong number of bound transfer inputs"));
+        }
+
+        Ok(BoundTransferPublicInputs {
+            transfer: TransferPublicInputs::from_vec(&inputs[..7])?,
+            memo_hash: inputs[7]
+        })
+    }
+
+    /// `BOUND_TRANSFER_INPUTS_LEN` bytes, the layout of the Transfer inputs followed by the `memo_hash`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        write_fr_vec::<E>(&self.to_vec())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        if data.len() != BOUND_TRANSFER_INPUTS_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong bound transfer inputs length"));
+        }
+
+        Self::from_vec(&read_fr_vec::<E>(data)?)
+    }
+}
+
+
+/// Public inputs of the UtxoAccumulator circuit. `index` is the leaf of the first note, the contract
+/// passes it as `rn*2` for the message `rn`.
+#[derive(Clone)]
+pub struct AccumulatorPublicInputs<E: Engine> {
+    pub note_hashes: [E::Fr; 2],
+    pub index: u64,
+    pub old_root: E::Fr,
+    pub new_root: E::Fr
+}
+
+impl<E: Engine> AccumulatorPublicInputs<E> {
+    pub fn index_fr(&self) -> E::Fr {
+        let mut repr = <E::Fr as PrimeField>::Repr::default();
+        repr.as_mut()[0] = self.index;
+        E::Fr::from_repr(repr).unwrap()
+    }
+
+    /// Inputs in the order of the circuit: note_hashes, index, old_root and new_root.
+    pub fn to_vec(&self) -> Vec<E::Fr> {
+        vec![self.note_hashes[0], self.note_hashes[1], self.index_fr(), self.old_root, self.new_root]
+    }
+
+    pub fn from_vec(inputs: &[E::Fr]) -> io::Result<Self> {
+        if inputs.len() != 5 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong number of accumulator inputs"));
+        }
+
+        let index = inputs[2].into_repr();
+        if index.as_ref()[1..].iter().any(|&e| e != 0) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "index should fit in 64 bits"));
+        }
+
+        Ok(AccumulatorPublicInputs {
+            note_hashes: [inputs[0], inputs[1]],
+            index: index.as_ref()[0],
+            old_root: inputs[3],
+            new_root: inputs[4]
+        })
+    }
+
+    /// `ACCUMULATOR_INPUTS_LEN` bytes for the `groth16Verify` of the contract, the index is
+    /// 24 zero bytes and `toBytes` of the index.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        write_fr_vec::<E>(&self.to_vec())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        if data.len() != ACCUMULATOR_INPUTS_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong accumulator inputs length"));
+        }
+
+        Self::from_vec(&read_fr_vec::<E>(data)?)
+    }
+}
+
+
+// Engines are not comparable, so the derived impls would not apply to them.
+impl<E: Engine> PartialEq for TransferPublicInputs<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_vec() == other.to_vec()
+    }
+}
+
+impl<E: Engine> Eq for TransferPublicInputs<E> {}
+
+impl<E: Engine> fmt::Debug for TransferPublicInputs<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TransferPublicInputs")
+            .field("receiver", &self.receiver)
+            .field("root_hash", &self.root_hash)
+            .field("packed_asset", &self.packed_asset)
+            .field("out_hash", &self.out_hash)
+            .field("nf", &self.nf)
+            .finish()
+    }
+}
+
+impl<E: Engine> PartialEq for BoundTransferPublicInputs<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_vec() == other.to_vec()
+    }
+}
+
+impl<E: Engine> Eq for BoundTransferPublicInputs<E> {}
+
+impl<E: Engine> fmt::Debug for BoundTransferPublicInputs<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoundTransferPublicInputs")
+            .field("transfer", &self.transfer)
+            .field("memo_hash", &self.memo_hash)
+            .finish()
+    }
+}
+
+impl<E: Engine> PartialEq for AccumulatorPublicInputs<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_vec() == other.to_vec()
+    }
+}
+
+impl<E: Engine> Eq for AccumulatorPublicInputs<E> {}
+
+impl<E: Engine> fmt::Debug for AccumulatorPublicInputs<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AccumulatorPublicInputs")
+            .field("note_hashes", &self.note_hashes)
+            .field("index", &self.index)
+            .field("old_root", &self.old_root)
+            .field("new_root", &self.new_root)
+            .finish()
+    }
+}
+
+
+/// Unlike `serialization::read_fr_vec`, fails on every non canonical field element.
+fn read_fr_vec<E: Engine>(data: &[u8]) -> io::Result<Vec<E::Fr>> {
+    data.chunks(FR_LEN).map(|chunk| {
+        let repr = read_fr_repr_be::<E::Fr>(chunk)?;
+        E::Fr::from_repr(repr).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "not in field"))
+    }).collect()
+}
+
+fn write_fr_vec<E: Engine>(inputs: &[E::Fr]) -> Vec<u8> {
+    let mut res = vec![];
+    for e in inputs {
+        e.into_repr().write_be(&mut res).unwrap();
+    }
+    res
+}
+
+
+#[cfg(test)]
+mod public_inputs_tests {
+    use super::*;
+    use pairing::bls12_381::{Bls12, Fr, FrRepr};
+    use rand::Rng;
+    use rand::os::OsRng;
+
+    fn transfer_inputs() -> TransferPublicInputs<Bls12> {
+        let rng = &mut OsRng::new().unwrap();
+
+        TransferPublicInputs {
+            receiver: rng.gen(),
+            root_hash: rng.gen(),
+            packed_asset: PackedAsset::new(7, -5, 1_000_000),
+            out_hash: [rng.gen(), rng.gen()],
+            nf: [rng.gen(), rng.gen()]
+        }
+    }
+
+    #[test]
+    fn test_transfer_layout() {
+        let inputs = transfer_inputs();
+        let bytes = inputs.to_bytes();
+
+        assert_eq!(bytes.len(), TRANSFER_INPUTS_LEN);
+        assert_eq!(&bytes[64..72], &[0u8; 8]);
+        assert_eq!(&bytes[72..80], &1_000_000i64.to_be_bytes());
+        assert_eq!(&bytes[80..88], &(-5i64).to_be_bytes());
+        assert_eq!(&bytes[88..96], &7i64.to_be_bytes());
+
+        let packed = Fr::from_repr(FrRepr([7, (-5i64) as u64, 1_000_000, 0])).unwrap();
+        assert_eq!(inputs.to_vec()[2], packed);
+
+        assert_eq!(TransferPublicInputs::<Bls12>::from_bytes(&bytes).unwrap(), inputs);
+    }
+
+    #[test]
+    fn test_transfer_wrong_bytes() {
+        let bytes = transfer_inputs().to_bytes();
+
+        assert!(TransferPublicInputs::<Bls12>::from_bytes(&bytes[..TRANSFER_INPUTS_LEN - 1]).is_err());
+
+        let mut wrong = bytes.clone();
+        wrong[71] = 1;
+        assert!(TransferPublicInputs::<Bls12>::from_bytes(&wrong).is_err(), "packed asset is only 192 bits");
+
+        let mut wrong = bytes.clone();
+        wrong[96] = 0xff;
+        assert!(TransferPublicInputs::<Bls12>::from_bytes(&wrong).is_err(), "out_hash is not in field");
+    }
+
+    #[test]
+    fn test_bound_transfer_layout() {
+        let inputs = BoundTransferPublicInputs::<Bls12> {
+            transfer: transfer_inputs(),
+            memo_hash: memo_hash::<Bls12>(400000, b"memo")
+        };
+        let bytes = inputs.to_bytes();
+
+        assert_eq!(bytes.len(), BOUND_TRANSFER_INPUTS_LEN);
+        assert_eq!(&bytes[..TRANSFER_INPUTS_LEN], &inputs.transfer.to_bytes()[..]);
+        assert_eq!(bytes[TRANSFER_INPUTS_LEN], 0);
+        assert_eq!(BoundTransferPublicInputs::<Bls12>::from_bytes(&bytes).unwrap(), inputs);
+        assert!(BoundTransferPublicInputs::<Bls12>::from_bytes(&bytes[..TRANSFER_INPUTS_LEN]).is_err());
+
+        assert!(memo_hash::<Bls12>(400000, b"memo") != memo_hash::<Bls12>(400000, b"other memo"));
+        assert!(memo_hash::<Bls12>(400000, b"memo") != memo_hash::<Bls12>(500000, b"memo"));
+    }
+
+    #[test]
+    fn test_accumulator_layout() {
+        let rng = &mut OsRng::new().unwrap();
+        let inputs = AccumulatorPublicInputs::<Bls12> {
+            note_hashes: [rng.gen(), rng.gen()],
+            index: 2 * 12345,
+            old_root: rng.gen(),
+            new_root: rng.gen()
+        };
+        let bytes = inputs.to_bytes();
+
+        assert_eq!(bytes.len(), ACCUMULATOR_INPUTS_LEN);
+        assert_eq!(&bytes[64..88], &[0u8; 24]);
+        assert_eq!(&bytes[88..96], &(2 * 12345i64).to_be_bytes());
+        assert_eq!(AccumulatorPublicInputs::<Bls12>::from_bytes(&bytes).unwrap(), inputs);
+
+        let mut wrong = bytes.clone();
+        wrong[87] = 1;
+        assert!(AccumulatorPublicInputs::<Bls12>::from_bytes(&wrong).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! Import of snarkjs (circom) JSON files: `verification_key.json`, `proof.json` and `public.json`
+//! of the groth16 and plonk protocols.
+//!
+//! snarkjs writes points in projective form with decimal coordinates, `[x, y, "1"]` for G1 and
+//! `[[x.c0, x.c1], [y.c0, y.c1], ["1", "0"]]` for G2, with `z = 0` for the point at infinity.
+
+use pairing::{
+    bls12_381::{Bls12, Fr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed},
+    CurveAffine,
+    EncodedPoint
+};
+
+use bellman::groth16::Proof;
+
+use num::BigUint;
+use serde::{Deserialize, de::DeserializeOwned};
+
+use std::io;
+
+use crate::serialization::read_fr_vec;
+use crate::verifier::TruncatedVerifyingKey;
+
+pub const CURVE_BN128: &str = "bn128";
+pub const CURVE_BLS12_381: &str = "bls12381";
+
+pub const PROTOCOL_GROTH16: &str = "groth16";
+pub const PROTOCOL_PLONK: &str = "plonk";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct VerificationKeyJson {
+    pub protocol: String,
+    #[serde(default)]
+    pub curve: Option<String>,
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub vk_alpha_1: Vec<String>,
+    pub vk_beta_2: Vec<Vec<String>>,
+    pub vk_gamma_2: Vec<Vec<String>>,
+    pub vk_delta_2: Vec<Vec<String>>,
+    #[serde(rename = "IC")]
+    pub ic: Vec<Vec<String>>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProofJson {
+    pub protocol: String,
+    #[serde(default)]
+    pub curve: Option<String>,
+    pub pi_a: Vec<String>,
+    pub pi_b: Vec<Vec<String>>,
+    pub pi_c: Vec<String>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PlonkVerificationKeyJson {
+    pub protocol: String,
+    #[serde(default)]
+    pub curve: Option<String>,
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub power: u32,
+    pub k1: String,
+    pub k2: String,
+    #[serde(rename = "Qm")]
+    pub qm: Vec<String>,
+    #[serde(rename = "Ql")]
+    pub ql: Vec<String>,
+    #[serde(rename = "Qr")]
+    pub qr: Vec<String>,
+    #[serde(rename = "Qo")]
+    pub qo: Vec<String>,
+    #[serde(rename = "Qc")]
+    pub qc: Vec<String>,
+    #[serde(rename = "S1")]
+    pub s1: Vec<String>,
+    #[serde(rename = "S2")]
+    pub s2: Vec<String>,
+    #[serde(rename = "S3")]
+    pub s3: Vec<String>,
+    #[serde(rename = "X_2")]
+    pub x_2: Vec<Vec<String>>,
+    pub w: String
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PlonkProofJson {
+    pub protocol: String,
+    #[serde(default)]
+    pub curve: Option<String>,
+    #[serde(rename = "A")]
+    pub a: Vec<String>,
+    #[serde(rename = "B")]
+    pub b: Vec<String>,
+    #[serde(rename = "C")]
+    pub c: Vec<String>,
+    #[serde(rename = "Z")]
+    pub z: Vec<String>,
+    #[serde(rename = "T1")]
+    pub t1: Vec<String>,
+    #[serde(rename = "T2")]
+    pub t2: Vec<String>,
+    #[serde(rename = "T3")]
+    pub t3: Vec<String>,
+    #[serde(rename = "Wxi")]
+    pub wxi: Vec<String>,
+    #[serde(rename = "Wxiw")]
+    pub wxiw: Vec<String>,
+    pub eval_a: String,
+    pub eval_b: String,
+    pub eval_c: String,
+    pub eval_s1: String,
+    pub eval_s2: String,
+    pub eval_zw: String
+}
+
+impl VerificationKeyJson {
+    /// Checks that the vk is a groth16 one for `curve` with `nPublic + 1` IC points.
+    pub fn check(&self, curve: &str) -> io::Result<()> {
+        check_header(&self.protocol, &self.curve, PROTOCOL_GROTH16, curve)?;
+
+        if self.ic.len() != self.n_public + 1 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "IC length does not match nPublic"));
+        }
+        Ok(())
+    }
+}
+
+impl ProofJson {
+    pub fn check(&self, curve: &str) -> io::Result<()> {
+        check_header(&self.protocol, &self.curve, PROTOCOL_GROTH16, curve)
+    }
+}
+
+impl PlonkVerificationKeyJson {
+    pub fn check(&self, curve: &str) -> io::Result<()> {
+        check_header(&self.protocol, &self.curve, PROTOCOL_PLONK, curve)
+    }
+}
+
+impl PlonkProofJson {
+    pub fn check(&self, curve: &str) -> io::Result<()> {
+        check_header(&self.protocol, &self.curve, PROTOCOL_PLONK, curve)
+    }
+}
+
+fn check_header(protocol: &str, curve: &Option<String>, expected_protocol: &str, expected_curve: &str) -> io::Result<()> {
+    if protocol != expected_protocol {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported protocol"));
+    }
+
+    // Older snarkjs versions do not write the curve, their files are always bn128
+    match curve {
+        Some(curve) if curve != expected_curve => Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected curve")),
+        None if expected_curve != CURVE_BN128 => Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected curve")),
+        _ => Ok(())
+    }
+}
+
+pub fn parse_json<T: DeserializeOwned>(json: &str) -> io::Result<T> {
+    serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes a decimal number as a `len` bytes big-endian integer.
+pub fn decimal_to_bytes_be(s: &str, len: usize) -> io::Result<Vec<u8>> {
+    let n = BigUint::parse_bytes(s.as_bytes(), 10)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a decimal number"))?;
+    let bytes = n.to_bytes_be();
+
+    if bytes.len() > len {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not in field"));
+    }
+
+    let mut res = vec![0u8; len - bytes.len()];
+    res.extend_from_slice(&bytes);
+    Ok(res)
+}
+
+/// Affine `(x, y)` of a G1 point, `None` for the point at infinity.
+pub fn g1_coords(p: &[String]) -> io::Result<Option<(&str, &str)>> {
+    match p {
+        [x, y, z] if z == "1" => Ok(Some((x.as_str(), y.as_str()))),
+        [_, _, z] if z == "0" => Ok(None),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed G1 point"))
+    }
+}
+
+/// Affine `([x.c0, x.c1], [y.c0, y.c1])` of a G2 point, `None` for the point at infinity.
+pub fn g2_coords(p: &[Vec<String>]) -> io::Result<Option<([&str; 2], [&str; 2])>> {
+    match p {
+        [x, y, z] => match fq2_coords(z)? {
+            ["1", "0"] => Ok(Some((fq2_coords(x)?, fq2_coords(y)?))),
+            ["0", "0"] => Ok(None),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed G2 point"))
+        },
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed G2 point"))
+    }
+}
+
+fn fq2_coords(c: &[String]) -> io::Result<[&str; 2]> {
+    match c {
+        [c0, c1] => Ok([c0.as_str(), c1.as_str()]),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed G2 point"))
+    }
+}
+
+/// Public signals are already in the byte layout of groth16 inputs: 32 bytes big-endian each.
+pub fn public_signals_to_bytes(json: &str) -> io::Result<Vec<u8>> {
+    let signals: Vec<String> = parse_json(json)?;
+
+    let mut res = vec![];
+    for s in signals.iter() {
+        res.extend_from_slice(&decimal_to_bytes_be(s, 32)?);
+    }
+    Ok(res)
+}
+
+// Top 3 bits of the uncompressed encoding are flags, a valid coordinate never has them
+fn bls12_fq_bytes(s: &str) -> io::Result<Vec<u8>> {
+    let bytes = decimal_to_bytes_be(s, 48)?;
+    if bytes[0] & 0xe0 != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not in field"));
+    }
+    Ok(bytes)
+}
+
+fn bls12_g1(p: &[String]) -> io::Result<G1Affine> {
+    let mut repr = G1Uncompressed::empty();
+
+    match g1_coords(p)? {
+        Some((x, y)) => {
+            repr.as_mut()[..48].copy_from_slice(&bls12_fq_bytes(x)?);
+            repr.as_mut()[48..].copy_from_slice(&bls12_fq_bytes(y)?);
+        },
+        None => repr.as_mut()[0] = 0x40
+    }
+
+    non_zero(repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?)
+}
+
+fn bls12_g2(p: &[Vec<String>]) -> io::Result<G2Affine> {
+    let mut repr = G2Uncompressed::empty();
+
+    match g2_coords(p)? {
+        Some(([x_c0, x_c1], [y_c0, y_c1])) => {
+            repr.as_mut()[..48].copy_from_slice(&bls12_fq_bytes(x_c1)?);
+            repr.as_mut()[48..96].copy_from_slice(&bls12_fq_bytes(x_c0)?);
+            repr.as_mut()[96..144].copy_from_slice(&bls12_fq_bytes(y_c1)?);
+            repr.as_mut()[144..].copy_from_slice(&bls12_fq_bytes(y_c0)?);
+        },
+        None => repr.as_mut()[0] = 0x40
+    }
+
+    non_zero(repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?)
+}
+
+fn non_zero<G: CurveAffine>(p: G) -> io::Result<G> {
+    if p.is_zero() {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"))
+    } else {
+        Ok(p)
+    }
+}
+
+pub fn read_vk_bls12(json: &str) -> io::Result<TruncatedVerifyingKey<Bls12>> {
+    let vk: VerificationKeyJson = parse_json(json)?;
+    vk.check(CURVE_BLS12_381)?;
+
+    Ok(TruncatedVerifyingKey {
+        alpha_g1: bls12_g1(&vk.vk_alpha_1)?,
+        beta_g2: bls12_g2(&vk.vk_beta_2)?,
+        gamma_g2: bls12_g2(&vk.vk_gamma_2)?,
+        delta_g2: bls12_g2(&vk.vk_delta_2)?,
+        ic: vk.ic.iter().map(|p| bls12_g1(p)).collect::<io::Result<_>>()?
+    })
+}
+
+pub fn read_proof_bls12(json: &str) -> io::Result<Proof<Bls12>> {
+    let proof: ProofJson = parse_json(json)?;
+    proof.check(CURVE_BLS12_381)?;
+
+    Ok(Proof {
+        a: bls12_g1(&proof.pi_a)?,
+        b: bls12_g2(&proof.pi_b)?,
+        c: bls12_g1(&proof.pi_c)?
+    })
+}
+
+pub fn read_public_signals_bls12(json: &str) -> io::Result<Vec<Fr>> {
+    read_fr_vec::<Fr>(&public_signals_to_bytes(json)?)
+}
+
+
+#[cfg(test)]
+mod snarkjs_tests {
+    use super::*;
+    use pairing::{Field, PrimeField};
+
+    const G1_GEN: &str = r#"[
+        "3685416753713387016781088315183077757961620795782546409894578378688607592378376318836054947676345821548104185464507",
+        "1339506544944476473020471379941921221584933875938349620426543736416511423956333506472724655353366534992391756441569",
+        "1"
+    ]"#;
+
+    const G2_GEN: &str = r#"[
+        [
+            "352701069587466618187139116011060144890029952792775240219908644239793785735715026873347600343865175952761926303160",
+            "3059144344244213709971259814753781636986470325476647558659373206291635324768958432433509563104347017837885763365758"
+        ],
+        [
+            "1985150602287291935568054521177171638300868978215655730859378665066344726373823718423869104263333984641494340347905",
+            "927553665492332455747201965776037880757740193453592970025027978793976877002675564980949289727957565575433344219582"
+        ],
+        ["1", "0"]
+    ]"#;
+
+    #[test]
+    fn test_read_bls12() {
+        let vk_json = format!(
+            r#"{{"protocol": "groth16", "curve": "bls12381", "nPublic": 1, "vk_alpha_1": {g1}, "vk_beta_2": {g2}, "vk_gamma_2": {g2}, "vk_delta_2": {g2}, "IC": [{g1}, {g1}]}}"#,
+            g1 = G1_GEN, g2 = G2_GEN
+        );
+        let vk = read_vk_bls12(&vk_json).unwrap();
+        assert!(vk.alpha_g1 == G1Affine::one());
+        assert!(vk.beta_g2 == G2Affine::one());
+        assert!(vk.ic.len() == 2);
+
+        let proof_json = format!(
+            r#"{{"protocol": "groth16", "curve": "bls12381", "pi_a": {g1}, "pi_b": {g2}, "pi_c": {g1}}}"#,
+            g1 = G1_GEN, g2 = G2_GEN
+        );
+        let proof = read_proof_bls12(&proof_json).unwrap();
+        assert!(proof.a == G1Affine::one() && proof.b == G2Affine::one() && proof.c == G1Affine::one());
+
+        // bn128 is the default curve of snarkjs files
+        assert!(read_proof_bls12(&proof_json.replace(r#""curve": "bls12381", "#, "")).is_err());
+        assert!(read_vk_bls12(&vk_json.replace(r#""nPublic": 1"#, r#""nPublic": 2"#)).is_err());
+
+        let inputs = read_public_signals_bls12(r#"["0", "1", "255"]"#).unwrap();
+        assert!(inputs == vec![Fr::zero(), Fr::one(), Fr::from_str("255").unwrap()]);
+        assert!(read_public_signals_bls12(r#"["-1"]"#).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Sparse Pedersen Merkle tree of nullifiers. The leaf of a nullifier is at the index given by its lowest `depth`
+//! bits and holds the nullifier itself, empty leaves are zeros. With the depth of `E::Fr::NUM_BITS` every
+//! nullifier has its own leaf, a smaller depth allows collisions and a colliding nullifier cannot be inserted.
+//!
+//! A nullifier is absent from the set if its leaf is empty, the proof of the leaf is enough for the non-membership
+//! check, see `circuit::sparse_merkle_tree` for the gadget. A colliding nullifier is never absent, it can not be
+//! spent once its leaf is taken.
+
+use sapling_crypto::jubjub::JubjubEngine;
+use sapling_crypto::pedersen_hash::Personalization;
+
+use pairing::{Field, PrimeField, PrimeFieldRepr};
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::fieldtools;
+use crate::pedersen_hasher::compress;
+
+
+/// The 6 bits of the personalization are not enough for a tree of field elements, levels above 62 reuse them.
+/// The depth of the tree is fixed, so the nodes of different levels are still never hashed together.
+pub fn personalization(level: usize) -> Personalization {
+    Personalization::MerkleTree(level % 63)
+}
+
+/// Root of the tree with the `leaf` at the index of the `key` and its siblings from the bottom up.
+pub fn sparse_merkle_root<E: JubjubEngine>(sibling: &[E::Fr], key: &E::Fr, leaf: &E::Fr, params: &E::Params) -> E::Fr {
+    fieldtools::fr_to_repr_bool(key).into_iter().zip(sibling.iter()).enumerate()
+        .fold(*leaf, |cur, (i, (is_right, s))| {
+            let (left, right) = if is_right { (s, &cur) } else { (&cur, s) };
+            compress::<E>(left, right, personalization(i), params)
+        })
+}
+
+
+/// Proof of the leaf of a nullifier: a membership proof if the leaf is the nullifier, a non-membership one if it is empty.
+#[derive(Clone)]
+pub struct SparseMerkleProof<E: JubjubEngine> {
+    pub leaf: E::Fr,
+    pub sibling: Vec<E::Fr>
+}
+
+impl<E: JubjubEngine> SparseMerkleProof<E> {
+    pub fn root(&self, nf: &E::Fr, params: &E::Params) -> E::Fr {
+        sparse_merkle_root::<E>(&self.sibling, nf, &self.leaf, params)
+    }
+
+    pub fn verify_membership(&self, root: &E::Fr, nf: &E::Fr, params: &E::Params) -> bool {
+        self.leaf == *nf && self.root(nf, params) == *root
+    }
+
+    pub fn verify_non_membership(&self, root: &E::Fr, nf: &E::Fr, params: &E::Params) -> bool {
+        self.leaf.is_zero() && self.root(nf, params) == *root
+    }
+}
+
+
+#[derive(Clone)]
+pub struct SparseMerkleTree<E: JubjubEngine> {
+    depth: usize,
+    defaults: Vec<E::Fr>,
+    /// Nonempty nodes by the level and the index at the level.
+    nodes: HashMap<(usize, Vec<u64>), E::Fr>
+}
+
+impl<E: JubjubEngine> SparseMerkleTree<E> {
+    pub fn new(depth: usize, params: &E::Params) -> io::Result<Self> {
+        if depth > E::Fr::NUM_BITS as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("depth should be less or equal {}", E::Fr::NUM_BITS)));
+        }
+
+        let mut defaults = vec![E::Fr::zero()];
+        for i in 0..depth {
+            let cur = defaults[i];
+            defaults.push(compress::<E>(&cur, &cur, personalization(i), params));
+        }
+
+        Ok(SparseMerkleTree { depth, defaults, nodes: HashMap::new() })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> E::Fr {
+        self.node(self.depth, &<E::Fr as PrimeField>::Repr::default())
+    }
+
+    pub fn contains(&self, nf: &E::Fr) -> bool {
+        self.node(0, &self.index(nf)) == *nf
+    }
+
+    /// Inserts the nullifier, fails if it is zero, already inserted or its leaf is taken by another one.
+    pub fn insert(&mut self, nf: &E::Fr, params: &E::Params) -> io::Result<()> {
+        if nf.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "zero nullifier"));
+        }
+
+        let mut index = self.index(nf);
+        match self.node(0, &index) {
+            leaf if leaf == *nf => return Err(io::Error::new(io::ErrorKind::InvalidData, "nullifier is already in the set")),
+            leaf if !leaf.is_zero() => return Err(io::Error::new(io::ErrorKind::InvalidData, "leaf of the nullifier is taken")),
+            _ => {}
+        }
+
+        let mut cur = *nf;
+        for i in 0..self.depth {
+            let s = self.node(i, &sibling_index(&index));
+            self.nodes.insert((i, index.as_ref().to_vec()), cur);
+
+            let is_right = index.as_ref()[0] & 1 == 1;
+            let (left, right) = if is_right { (&s, &cur) } else { (&cur, &s) };
+            cur = compress::<E>(left, right, personalization(i), params);
+            index.shr(1);
+        }
+        self.nodes.insert((self.depth, index.as_ref().to_vec()), cur);
+
+        Ok(())
+    }
+
+    /// Proof of the leaf of the nullifier, it is a membership proof if the nullifier is in the set.
+    pub fn proof(&self, nf: &E::Fr) -> SparseMerkleProof<E> {
+        let mut index = self.index(nf);
+        let leaf = self.node(0, &index);
+
+        let sibling = (0..self.depth).map(|i| {
+            let s = self.node(i, &sibling_index(&index));
+            index.shr(1);
+            s
+        }).collect();
+
+        SparseMerkleProof { leaf, sibling }
+    }
+
+    /// The lowest `depth` bits of the nullifier.
+    fn index(&self, nf: &E::Fr) -> <E::Fr as PrimeField>::Repr {
+        let mut index = nf.into_repr();
+        for (i, limb) in index.as_mut().iter_mut().enumerate() {
+            let bits = self.depth.saturating_sub(i * 64);
+            if bits < 64 {
+                *limb &= (1u64 << bits) - 1;
+            }
+        }
+        index
+    }
+
+    fn node(&self, level: usize, index: &<E::Fr as PrimeField>::Repr) -> E::Fr {
+        self.nodes.get(&(level, index.as_ref().to_vec())).cloned().unwrap_or(self.defaults[level])
+    }
+}
+
+fn sibling_index<R: PrimeFieldRepr>(index: &R) -> R {
+    let mut res = *index;
+    res.as_mut()[0] ^= 1;
+    res
+}
+
+
+#[cfg(test)]
+mod sparse_merkle_tree_tests {
+    use super::*;
+    use pairing::bls12_381::{Bls12, Fr, FrRepr};
+    use sapling_crypto::jubjub::JubjubBls12;
+    use rand::os::OsRng;
+    use rand::Rng;
+
+    #[test]
+    fn test_membership() {
+        let params = JubjubBls12::new();
+        let rng = &mut OsRng::new().unwrap();
+        let nfs = (0..4).map(|_| rng.gen()).collect::<Vec<Fr>>();
+
+        let mut tree = SparseMerkleTree::<Bls12>::new(Fr::NUM_BITS as usize, &params).unwrap();
+        let empty_root = tree.root();
+        assert!(tree.proof(&nfs[0]).verify_non_membership(&empty_root, &nfs[0], &params));
+
+        for nf in nfs[..3].iter() {
+            tree.insert(nf, &params).unwrap();
+        }
+        let root = tree.root();
+        assert!(root != empty_root);
+
+        for nf in nfs[..3].iter() {
+            let proof = tree.proof(nf);
+            assert!(tree.contains(nf));
+            assert!(proof.verify_membership(&root, nf, &params), "inserted nullifier should be in the set");
+            assert!(!proof.verify_non_membership(&root, nf, &params));
+        }
+
+        let proof = tree.proof(&nfs[3]);
+        assert!(!tree.contains(&nfs[3]));
+        assert!(proof.verify_non_membership(&root, &nfs[3], &params), "other nullifier should not be in the set");
+        assert!(!proof.verify_non_membership(&empty_root, &nfs[3], &params));
+
+        assert!(tree.insert(&nfs[0], &params).is_err());
+        assert!(tree.insert(&Fr::zero(), &params).is_err());
+    }
+
+    #[test]
+    fn test_collision() {
+        let params = JubjubBls12::new();
+        let nf = |n: u64| Fr::from_repr(FrRepr([n, 0, 0, 0])).unwrap();
+
+        let mut tree = SparseMerkleTree::<Bls12>::new(4, &params).unwrap();
+        tree.insert(&nf(0x13), &params).unwrap();
+
+        // 0x23 has the same lowest 4 bits, its leaf is taken by 0x13
+        let proof = tree.proof(&nf(0x23));
+        assert!(proof.leaf == nf(0x13));
+        assert!(!proof.verify_non_membership(&tree.root(), &nf(0x23), &params), "colliding nullifier should not be absent");
+        assert!(tree.insert(&nf(0x23), &params).is_err());
+
+        tree.insert(&nf(0x24), &params).unwrap();
+        assert!(tree.proof(&nf(0x13)).verify_membership(&tree.root(), &nf(0x13), &params));
+    }
+
+    #[test]
+    fn test_wrong_depth() {
+        let params = JubjubBls12::new();
+        assert!(SparseMerkleTree::<Bls12>::new(Fr::NUM_BITS as usize + 1, &params).is_err());
+    }
+}
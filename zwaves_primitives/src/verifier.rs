@@ -14,6 +14,8 @@ use bellman::groth16::{
 
 use bellman::SynthesisError;
 
+use rand::{Rand, Rng};
+
 use std::io::{Read, Write};
 use std::io;
 
@@ -128,6 +130,150 @@ pub fn truncate_verifying_key<E: Engine>(
     }
 }
 
+/// Prepared verifying keys and the batch verification of Groth16 proofs, expanded by the verifiers of every curve
+/// crate: `zwaves_jni::bn256::verifier` works with the `pairing_ce` traits, which are not the ones of `pairing`.
+///
+/// The expanding module should have `Engine`, the curve and field traits, `Rand`, `Rng`, `Proof`,
+/// `TruncatedVerifyingKey` and `SynthesisError` in scope, and `fn b_in_subgroup<E: Engine>(&E::G2Affine) -> bool`
+/// for the curves whose decoding does not check the subgroup of G2.
+#[macro_export]
+macro_rules! groth16_verifier {
+    () => {
+        /// Verifying key with the fixed part of the pairing check precomputed, reusable across proofs.
+        pub struct PreparedVerifyingKey<E: Engine> {
+            /// Pairing result of alpha and beta
+            pub alpha_g1_beta_g2: E::Fqk,
+            /// -gamma in G2
+            pub neg_gamma_g2: <E::G2Affine as CurveAffine>::Prepared,
+            /// -delta in G2
+            pub neg_delta_g2: <E::G2Affine as CurveAffine>::Prepared,
+            /// Copy of IC from `TruncatedVerifyingKey`
+            pub ic: Vec<E::G1Affine>
+        }
+
+        pub fn prepare_verifying_key<E: Engine>(tvk: &TruncatedVerifyingKey<E>) -> PreparedVerifyingKey<E> {
+            let mut gamma = tvk.gamma_g2;
+            gamma.negate();
+            let mut delta = tvk.delta_g2;
+            delta.negate();
+
+            PreparedVerifyingKey {
+                alpha_g1_beta_g2: E::pairing(tvk.alpha_g1, tvk.beta_g2),
+                neg_gamma_g2: gamma.prepare(),
+                neg_delta_g2: delta.prepare(),
+                ic: tvk.ic.clone()
+            }
+        }
+
+        pub fn verify_prepared_proof<'a, E: Engine>(
+            pvk: &'a PreparedVerifyingKey<E>,
+            proof: &Proof<E>,
+            public_inputs: &[E::Fr]
+        ) -> Result<bool, SynthesisError>
+        {
+            if (public_inputs.len() + 1) != pvk.ic.len() {
+                return Err(SynthesisError::MalformedVerifyingKey);
+            }
+
+            if !b_in_subgroup::<E>(&proof.b) {
+                return Ok(false);
+            }
+
+            let mut acc = pvk.ic[0].into_projective();
+
+            for (i, b) in public_inputs.iter().zip(pvk.ic.iter().skip(1)) {
+                acc.add_assign(&b.mul(i.into_repr()));
+            }
+
+            // Same equation as in verify_proof, but alpha * beta is moved to the right side:
+            // A * B + inputs * (-gamma) + C * (-delta) == alpha * beta
+
+            Ok(E::final_exponentiation(&E::miller_loop(&[
+                (&proof.a.prepare(), &proof.b.prepare()),
+                (&acc.into_affine().prepare(), &pvk.neg_gamma_g2),
+                (&proof.c.prepare(), &pvk.neg_delta_g2)
+            ])).unwrap() == pvk.alpha_g1_beta_g2)
+        }
+
+        pub fn verify_proofs_batch<'a, E: Engine, R: Rng>(
+            tvk: &'a TruncatedVerifyingKey<E>,
+            proofs: &[(Proof<E>, Vec<E::Fr>)],
+            rng: &mut R
+        ) -> Result<bool, SynthesisError>
+        {
+            if proofs.iter().any(|(_, public_inputs)| (public_inputs.len() + 1) != tvk.ic.len()) {
+                return Err(SynthesisError::MalformedVerifyingKey);
+            }
+
+            if proofs.iter().any(|(proof, _)| !b_in_subgroup::<E>(&proof.b)) {
+                return Ok(false);
+            }
+
+            if proofs.is_empty() {
+                return Ok(true);
+            }
+
+            // Every proof satisfies
+            // A_i * B_i = alpha * beta + inputs_i * gamma + C_i * delta
+            // ... so for random r_i the combination holds as well:
+            // sum(r_i * A_i * B_i) = sum(r_i) * alpha * beta + sum(r_i * inputs_i) * gamma + sum(r_i * C_i) * delta
+            // ... and we check it the same way as verify_proof with one final exponentiation:
+            // sum((-r_i * A_i) * B_i) + sum(r_i) * alpha * beta + sum(r_i * inputs_i) * gamma + sum(r_i * C_i) * delta == 1
+
+            let mut r_sum = E::Fr::zero();
+            let mut input_coeffs = vec![E::Fr::zero(); tvk.ic.len() - 1];
+            let mut c_acc = E::G1::zero();
+            let mut neg_a = Vec::with_capacity(proofs.len());
+
+            for (proof, public_inputs) in proofs {
+                let r = E::Fr::rand(rng);
+                r_sum.add_assign(&r);
+
+                for (coeff, input) in input_coeffs.iter_mut().zip(public_inputs.iter()) {
+                    let mut t = *input;
+                    t.mul_assign(&r);
+                    coeff.add_assign(&t);
+                }
+
+                c_acc.add_assign(&proof.c.mul(r.into_repr()));
+
+                let mut a = proof.a.mul(r.into_repr());
+                a.negate();
+                neg_a.push(a.into_affine().prepare());
+            }
+
+            let mut acc = tvk.ic[0].mul(r_sum.into_repr());
+
+            for (i, b) in input_coeffs.iter().zip(tvk.ic.iter().skip(1)) {
+                acc.add_assign(&b.mul(i.into_repr()));
+            }
+
+            let b = proofs.iter().map(|(proof, _)| proof.b.prepare()).collect::<Vec<_>>();
+            let alpha = tvk.alpha_g1.mul(r_sum.into_repr()).into_affine().prepare();
+            let beta = tvk.beta_g2.prepare();
+            let acc = acc.into_affine().prepare();
+            let gamma = tvk.gamma_g2.prepare();
+            let c_acc = c_acc.into_affine().prepare();
+            let delta = tvk.delta_g2.prepare();
+
+            let mut pairs = neg_a.iter().zip(b.iter()).collect::<Vec<_>>();
+            pairs.push((&alpha, &beta));
+            pairs.push((&acc, &gamma));
+            pairs.push((&c_acc, &delta));
+
+            Ok(E::final_exponentiation(&E::miller_loop(&pairs)).unwrap() == E::Fqk::one())
+        }
+    };
+}
+
+
+/// Decoding of `pairing` checks the subgroup already.
+fn b_in_subgroup<E: Engine>(_b: &E::G2Affine) -> bool {
+    true
+}
+
+groth16_verifier!();
+
 pub fn verify_proof<'a, E: Engine>(
     tvk: &'a TruncatedVerifyingKey<E>,
     proof: &Proof<E>,
@@ -161,3 +307,4 @@ pub fn verify_proof<'a, E: Engine>(
         ].into_iter())
     ).unwrap() == E::Fqk::one())
 }
+
@@ -0,0 +1,48 @@
+use sapling_crypto::jubjub::JubjubBls12;
+use pairing::bls12_381::Bls12;
+use rand::os::OsRng;
+
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use zwaves_circuit::accumulator::{AccumulatorWorker, LineSource, UtxoSource};
+
+
+// Proves the accumulation of the utxo pairs of the file, one `UM:<n>` value per line starting from `UM:0`.
+// The first `root_num` pairs are accumulated already, they only update the tree.
+// Prints the arguments of the `utxoAccumulator` calls in order: `<n> base64:<proof> base64:<newRoot>`.
+fn main() -> std::io::Result<()> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("Usage: accumulator <utxo pairs file> [root_num]");
+        std::process::exit(1);
+    }
+
+    let root_num = match args.get(2) {
+        Some(n) => n.parse::<u64>().map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "root_num should be a number"))?,
+        None => 0
+    };
+
+    let rng = &mut OsRng::new().unwrap();
+    let jubjub_params = JubjubBls12::new();
+
+    let params_file = File::open("mpc_params_accumulator")?;
+    let params = phase2::MPCParameters::read(&params_file, false)?;
+    drop(params_file);
+
+    let mut source = LineSource::new(BufReader::new(File::open(&args[1])?));
+    let mut worker = AccumulatorWorker::<Bls12>::new(&jubjub_params);
+
+    for _ in 0..root_num {
+        let pair = UtxoSource::<Bls12>::next_pair(&mut source)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "fewer utxo pairs than root_num"))?;
+        worker.append(pair, &jubjub_params)?;
+    }
+
+    while let Some(call) = worker.next_call(&mut source, params.get_params(), &jubjub_params, rng)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("could not create proof: {:?}", e)))? {
+        println!("{} base64:{} base64:{}", call.root_num, base64::encode(&call.proof), base64::encode(&call.new_root));
+    }
+
+    Ok(())
+}
@@ -6,7 +6,8 @@ use rand::Rng;
 
 use std::fs::File;
 use std::io::{Write, Read};
-use zwaves_circuit::circuit::{UtxoAccumulator, Transfer};
+use std::path::Path;
+use zwaves_circuit::circuit::{UtxoAccumulator, Transfer, BoundTransfer};
 
 use hex::encode;
 
@@ -19,13 +20,7 @@ fn main() -> std::io::Result<()> {
     let mut params = phase2::MPCParameters::read(&params_file, true)?;
     drop(params_file);
 
-    let contributions = params.verify(UtxoAccumulator::<Bls12> {
-        params: &jubjub_params,
-        note_hashes: [None, None],
-        index: None,
-        old_proof: None,
-        new_proof: None
-    }).expect("parameters should be valid!");
+    let contributions = params.verify(UtxoAccumulator::<Bls12>::blank(&jubjub_params)).expect("parameters should be valid!");
 
 
     let hash = params.contribute(rng);
@@ -41,16 +36,7 @@ fn main() -> std::io::Result<()> {
     let mut params = phase2::MPCParameters::read(&params_file, true)?;
     drop(params_file);
 
-    let contributions = params.verify(Transfer::<Bls12> {
-        params: &jubjub_params,
-        receiver: None,
-        in_note: [None, None],
-        out_note: [None, None],
-        in_proof: [None, None],
-        root_hash: None,
-        sk: None,
-        packed_asset:None,
-    }).expect("parameters should be valid!");
+    let contributions = params.verify(Transfer::<Bls12>::blank(&jubjub_params)).expect("parameters should be valid!");
 
 
     let hash = params.contribute(rng);
@@ -62,6 +48,24 @@ fn main() -> std::io::Result<()> {
     params.write(params_file)?;
 
 
+    if Path::new("mpc_params_bound_transfer").exists() {
+        let params_file = File::open("mpc_params_bound_transfer")?;
+        let mut params = phase2::MPCParameters::read(&params_file, true)?;
+        drop(params_file);
+
+        let contributions = params.verify(BoundTransfer::<Bls12>::blank(&jubjub_params)).expect("parameters should be valid!");
+
+
+        let hash = params.contribute(rng);
+
+        println!("Contributed with hash {}", encode(hash.as_ref()));
+
+
+        let params_file = File::create("mpc_params_bound_transfer")?;
+        params.write(params_file)?;
+    }
+
+
     println!("MPC params saved OK");
     Ok(())
 }
@@ -0,0 +1,19 @@
+use sapling_crypto::jubjub::JubjubBls12;
+use pairing::bls12_381::Bls12;
+use std::fs::File;
+
+use zwaves_circuit::circuit::BoundTransfer;
+
+
+// The BoundTransfer circuit has its own parameters, `contribute` and `list` process them if the file exists.
+fn main() -> std::io::Result<()> {
+    let jubjub_params = JubjubBls12::new();
+
+    let params = phase2::MPCParameters::new(BoundTransfer::<Bls12>::blank(&jubjub_params)).unwrap();
+
+    let params_file = File::create("mpc_params_bound_transfer")?;
+    params.write(params_file)?;
+
+    println!("MPC params saved OK");
+    Ok(())
+}
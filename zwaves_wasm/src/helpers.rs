@@ -0,0 +1,106 @@
+use js_sys::{Array, Error, Object, Reflect, Uint8Array};
+use pairing::{
+    bls12_381::{Bls12, Fr},
+    PrimeField, PrimeFieldRepr,
+};
+use rand::{ChaChaRng, SeedableRng};
+use sapling_crypto::jubjub::JubjubBls12;
+use wasm_bindgen::{JsCast, JsValue};
+
+use zwaves_primitives::{serialization::read_fr_repr_be, transactions::NoteData};
+
+lazy_static! {
+    pub static ref JUBJUB_PARAMS: JubjubBls12 = JubjubBls12::new();
+}
+
+pub fn error(msg: &str) -> JsValue {
+    Error::new(msg).into()
+}
+
+pub fn read_fr(data: &[u8]) -> Result<Fr, JsValue> {
+    if data.len() != 32 {
+        return Err(error("Buffer must be uint256 BE number"));
+    }
+
+    let repr = read_fr_repr_be::<Fr>(data).map_err(|_| error("Buffer must be uint256 BE number"))?;
+    Fr::from_repr(repr).map_err(|_| error("Wrong field element"))
+}
+
+pub fn read_val_fr(val: &JsValue) -> Result<Fr, JsValue> {
+    let buff = val
+        .dyn_ref::<Uint8Array>()
+        .ok_or_else(|| error("could not downcast value to Uint8Array"))?;
+    read_fr(&buff.to_vec())
+}
+
+pub fn read_obj_fr(obj: &JsValue, key: &str) -> Result<Fr, JsValue> {
+    read_val_fr(&Reflect::get(obj, &JsValue::from_str(key))?)
+}
+
+pub fn fr_to_bytes(fr: &Fr) -> Vec<u8> {
+    let mut res = vec![];
+    fr.into_repr().write_be(&mut res).unwrap();
+    res
+}
+
+pub fn parse_note_data(note_obj: &JsValue) -> Result<NoteData<Bls12>, JsValue> {
+    Ok(NoteData::<Bls12> {
+        asset_id: read_obj_fr(note_obj, "asset_id")?,
+        amount: read_obj_fr(note_obj, "amount")?,
+        native_amount: read_obj_fr(note_obj, "native_amount")?,
+        txid: read_obj_fr(note_obj, "txid")?,
+        owner: read_obj_fr(note_obj, "owner")?,
+    })
+}
+
+pub fn note_data_to_js(note: &NoteData<Bls12>) -> Result<JsValue, JsValue> {
+    let res = Object::new();
+    for (key, value) in [
+        ("asset_id", &note.asset_id),
+        ("amount", &note.amount),
+        ("native_amount", &note.native_amount),
+        ("txid", &note.txid),
+        ("owner", &note.owner),
+    ]
+    .iter()
+    {
+        Reflect::set(
+            &res,
+            &JsValue::from_str(key),
+            &Uint8Array::from(&fr_to_bytes(value)[..]),
+        )?;
+    }
+    Ok(res.into())
+}
+
+pub fn parse_array(value: &JsValue) -> Result<Vec<JsValue>, JsValue> {
+    let value = value
+        .dyn_ref::<Array>()
+        .ok_or_else(|| error("Could not downcast value to Array"))?;
+    Ok(value.iter().collect())
+}
+
+pub fn parse_pair(obj: &JsValue, key: &str) -> Result<[JsValue; 2], JsValue> {
+    let mut value = parse_array(&Reflect::get(obj, &JsValue::from_str(key))?)?;
+
+    if value.len() != 2 {
+        return Err(error(&format!("{} length should be 2", key)));
+    }
+
+    let second = value.pop().unwrap();
+    Ok([value.pop().unwrap(), second])
+}
+
+/// Proving randomness can not come from the OS in the browser, so the caller passes
+/// 32 bytes from `crypto.getRandomValues`.
+pub fn seeded_rng(seed: &[u8]) -> Result<ChaChaRng, JsValue> {
+    if seed.len() != 32 {
+        return Err(error("Seed must be 32 bytes"));
+    }
+
+    let seed = seed
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect::<Vec<_>>();
+    Ok(ChaChaRng::from_seed(&seed[..]))
+}
@@ -0,0 +1,255 @@
+//! wasm-bindgen bindings of the zwaves primitives and provers, the browser counterpart
+//! of the Neon module in `zwaves_node/native`.
+//!
+//! Field elements are 32 bytes big-endian `Uint8Array`s, notes and proving data are plain objects
+//! with the same fields as in the Node module. The MPC parameters are the files of `zwaves_setup`,
+//! parsed once into `Params`.
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod helpers;
+
+use bellman::groth16::{Parameters, Proof};
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use pairing::{
+    bls12_381::{Bls12, Fr},
+    PrimeField,
+};
+use sapling_crypto::pedersen_hash::Personalization;
+use wasm_bindgen::prelude::*;
+
+use zwaves_circuit::{
+    circuit::{UtxoAccumulator, MERKLE_PROOF_LEN},
+    prover::{prove_transfer, TransferWitness},
+};
+use zwaves_primitives::{
+    fieldtools::fr_to_repr_bool,
+    groth16::create_random_proof,
+    serialization::read_fr_vec,
+    verifier::{truncate_verifying_key, verify_proof, TruncatedVerifyingKey},
+};
+
+use crate::helpers::*;
+
+/// The MPC parameters file starts with the Groth16 parameters, the contributions after them are not needed.
+fn read_params(mpc_params: &[u8]) -> Result<Parameters<Bls12>, JsValue> {
+    Parameters::<Bls12>::read(mpc_params, false).map_err(|_| error("Could not read mpc params"))
+}
+
+/// Groth16 parameters parsed once from the MPC parameters, the provers take them instead of the file.
+#[wasm_bindgen]
+pub struct Params {
+    inner: Parameters<Bls12>,
+}
+
+#[wasm_bindgen]
+impl Params {
+    #[wasm_bindgen(constructor)]
+    pub fn new(mpc_params: &[u8]) -> Result<Params, JsValue> {
+        Ok(Params {
+            inner: read_params(mpc_params)?,
+        })
+    }
+}
+
+fn proof_to_bytes(proof: &Proof<Bls12>) -> Vec<u8> {
+    let mut res = vec![];
+    proof.write(&mut res).unwrap();
+    res
+}
+
+#[wasm_bindgen]
+pub fn extract_vk(mpc_params: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let params = read_params(mpc_params)?;
+    let tvk = truncate_verifying_key(&params.vk);
+
+    let mut res = vec![];
+    tvk.write(&mut res).unwrap();
+    Ok(res)
+}
+
+/// Groth16 verification with the Ride `groth16Verify` layout, the inputs are concatenated field elements.
+#[wasm_bindgen]
+pub fn verify(vk: &[u8], proof: &[u8], inputs: &[u8]) -> Result<bool, JsValue> {
+    if (vk.len() % 48 != 0) || (inputs.len() % 32 != 0) || (vk.len() / 48 != inputs.len() / 32 + 8) {
+        return Err(error("Wrong buffer length"));
+    }
+
+    let tvk = TruncatedVerifyingKey::<Bls12>::read(vk).map_err(|_| error("Wrong vk format"))?;
+    let proof = Proof::<Bls12>::read(proof).map_err(|_| error("Wrong proof format"))?;
+    let inputs = read_fr_vec::<Fr>(inputs).map_err(|_| error("Wrong field element"))?;
+
+    verify_proof(&tvk, &proof, &inputs).map_err(|_| error("Error during proof verification"))
+}
+
+#[wasm_bindgen]
+pub fn note_hash(note: &JsValue) -> Result<Vec<u8>, JsValue> {
+    let note = parse_note_data(note)?;
+
+    let hash = zwaves_primitives::transactions::note_hash(&note, &JUBJUB_PARAMS);
+    Ok(fr_to_bytes(&hash))
+}
+
+#[wasm_bindgen]
+pub fn nullifier(note_hash: &[u8], sk: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let note_hash = read_fr(note_hash)?;
+    let sk = read_fr(sk)?;
+
+    let nf = zwaves_primitives::transactions::nullifier::<Bls12>(&note_hash, &sk, &JUBJUB_PARAMS);
+    Ok(fr_to_bytes(&nf))
+}
+
+#[wasm_bindgen]
+pub fn pubkey(sk: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let sk = read_fr(sk)?;
+
+    let pk = zwaves_primitives::transactions::pubkey::<Bls12>(&sk, &JUBJUB_PARAMS);
+    Ok(fr_to_bytes(&pk))
+}
+
+#[wasm_bindgen]
+pub fn edh(pk: &[u8], sk: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let pk = read_fr(pk)?;
+    let sk = read_fr(sk)?;
+
+    let res = zwaves_primitives::transactions::edh::<Bls12>(&pk, &sk, &JUBJUB_PARAMS)
+        .ok_or_else(|| error("Not an elliptic curve point"))?;
+    Ok(fr_to_bytes(&res))
+}
+
+/// Encrypts the note to its owner, `seed` is 32 random bytes for the ephemeral key.
+#[wasm_bindgen]
+pub fn encrypt_note(note: &JsValue, seed: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut rng = seeded_rng(seed)?;
+    let note = parse_note_data(note)?;
+
+    zwaves_primitives::note_encryption::encrypt_note(&note, &mut rng, &JUBJUB_PARAMS).map_err(|e| error(&e.to_string()))
+}
+
+/// The note object if it is encrypted to the owner of `sk`, `undefined` otherwise.
+#[wasm_bindgen]
+pub fn try_decrypt_note(data: &[u8], sk: &[u8]) -> Result<JsValue, JsValue> {
+    let sk = read_fr(sk)?;
+
+    match zwaves_primitives::note_encryption::try_decrypt_note::<Bls12>(data, &sk, &JUBJUB_PARAMS) {
+        Some(note) => note_data_to_js(&note),
+        None => Ok(JsValue::UNDEFINED),
+    }
+}
+
+#[wasm_bindgen]
+pub fn merkle_hash(left: &[u8], right: &[u8], level: u32) -> Result<Vec<u8>, JsValue> {
+    let left = read_fr(left)?;
+    let right = read_fr(right)?;
+
+    let hash = zwaves_primitives::pedersen_hasher::compress::<Bls12>(
+        &left,
+        &right,
+        Personalization::MerkleTree(level as usize),
+        &JUBJUB_PARAMS,
+    );
+    Ok(fr_to_bytes(&hash))
+}
+
+fn parse_transfer(transfer_obj: &JsValue) -> Result<TransferWitness<Bls12>, JsValue> {
+    let [in_note_0, in_note_1] = parse_pair(transfer_obj, "in_note")?;
+    let [out_note_0, out_note_1] = parse_pair(transfer_obj, "out_note")?;
+    let [in_index_0, in_index_1] = parse_pair(transfer_obj, "in_proof_index")?;
+    let [in_proof_0, in_proof_1] = parse_pair(transfer_obj, "in_proof_sibling")?;
+
+    Ok(TransferWitness {
+        receiver: read_obj_fr(transfer_obj, "receiver")?,
+        in_note: [parse_note_data(&in_note_0)?, parse_note_data(&in_note_1)?],
+        in_proof_sibling: [parse_merkle_proof(&in_proof_0)?, parse_merkle_proof(&in_proof_1)?],
+        in_proof_index: [parse_merkle_index(&in_index_0)?, parse_merkle_index(&in_index_1)?],
+        out_note: [parse_note_data(&out_note_0)?, parse_note_data(&out_note_1)?],
+        root_hash: read_obj_fr(transfer_obj, "root_hash")?,
+        sk: read_obj_fr(transfer_obj, "sk")?,
+        packed_asset: read_obj_fr(transfer_obj, "packed_asset")?,
+    })
+}
+
+fn parse_merkle_proof(proof: &JsValue) -> Result<Vec<Fr>, JsValue> {
+    let proof = parse_array(proof)?;
+
+    if proof.len() != MERKLE_PROOF_LEN {
+        return Err(error(&format!("Merkle proof length should be {}.", MERKLE_PROOF_LEN)));
+    }
+
+    proof.iter().map(read_val_fr).collect()
+}
+
+fn parse_merkle_index(index: &JsValue) -> Result<u64, JsValue> {
+    let index = read_val_fr(index)?;
+
+    if fr_to_repr_bool::<Fr>(&index)
+        .into_iter()
+        .skip(MERKLE_PROOF_LEN)
+        .any(|e| e)
+    {
+        return Err(error("Index value should not be bigger than 2^MERKLE_PROOF_LEN"));
+    }
+
+    Ok(index.into_repr().as_ref()[0])
+}
+
+/// Proves a Transfer, `seed` is 32 random bytes. Returns `{proof, inputs}` with the public inputs
+/// in the order of the circuit.
+#[wasm_bindgen]
+pub fn transfer(params: &Params, data: &JsValue, seed: &[u8]) -> Result<JsValue, JsValue> {
+    let mut rng = seeded_rng(seed)?;
+
+    let witness = parse_transfer(data)?;
+    let (proof, inputs) = prove_transfer(&params.inner, &witness, &JUBJUB_PARAMS, &mut rng)
+        .map_err(|_| error("Could not create proof"))?;
+
+    let inputs = inputs
+        .to_vec()
+        .iter()
+        .map(|e| Uint8Array::from(&fr_to_bytes(e)[..]))
+        .collect::<Array>();
+
+    let res = Object::new();
+    Reflect::set(&res, &"proof".into(), &Uint8Array::from(&proof_to_bytes(&proof)[..]))?;
+    Reflect::set(&res, &"inputs".into(), &inputs)?;
+    Ok(res.into())
+}
+
+fn parse_utxo_accumulator(acc_obj: &JsValue) -> Result<UtxoAccumulator<'static, Bls12>, JsValue> {
+    let [note_hash_0, note_hash_1] = parse_pair(acc_obj, "note_hashes")?;
+    let note_hashes = [Some(read_val_fr(&note_hash_0)?), Some(read_val_fr(&note_hash_1)?)];
+
+    let [old_proof, new_proof] = parse_pair(acc_obj, "proof_sibling")?;
+    let parse_proof = |proof: &JsValue| {
+        let proof = parse_array(proof)?;
+
+        if proof.len() != MERKLE_PROOF_LEN - 1 {
+            return Err(error(&format!(
+                "Merkle proof length should be {}.",
+                MERKLE_PROOF_LEN - 1
+            )));
+        }
+
+        proof.iter().map(read_val_fr).collect::<Result<Vec<_>, _>>()
+    };
+
+    Ok(UtxoAccumulator {
+        note_hashes,
+        index: Some(read_obj_fr(acc_obj, "proof_index")?),
+        old_proof: Some(parse_proof(&old_proof)?),
+        new_proof: Some(parse_proof(&new_proof)?),
+        params: &JUBJUB_PARAMS,
+    })
+}
+
+/// Proves a UtxoAccumulator, `seed` is 32 random bytes.
+#[wasm_bindgen]
+pub fn utxo_accumulator(params: &Params, data: &JsValue, seed: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut rng = seeded_rng(seed)?;
+
+    let c = parse_utxo_accumulator(data)?;
+    let proof = create_random_proof(c, &params.inner, &mut rng).map_err(|_| error("Could not create proof"))?;
+
+    Ok(proof_to_bytes(&proof))
+}
@@ -0,0 +1,124 @@
+//! Run with `wasm-pack test --node zwaves_wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use pairing::bls12_381::Bls12;
+use rand::{ChaChaRng, SeedableRng};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use zwaves_circuit::circuit::{UtxoAccumulator, MERKLE_PROOF_LEN};
+use zwaves_primitives::groth16::generate_random_parameters;
+use zwaves_wasm::{helpers::JUBJUB_PARAMS, *};
+
+fn fr(x: u8) -> Vec<u8> {
+    let mut res = vec![0; 32];
+    res[31] = x;
+    res
+}
+
+fn note(fields: &[(&str, u8)]) -> JsValue {
+    let obj = Object::new();
+    for (key, value) in fields {
+        Reflect::set(&obj, &JsValue::from_str(key), &Uint8Array::from(&fr(*value)[..])).unwrap();
+    }
+    obj.into()
+}
+
+#[wasm_bindgen_test]
+fn edh_test() {
+    let (sk_a, sk_b) = (fr(2), fr(3));
+    let (pk_a, pk_b) = (pubkey(&sk_a).unwrap(), pubkey(&sk_b).unwrap());
+
+    assert_eq!(edh(&pk_b, &sk_a).unwrap(), edh(&pk_a, &sk_b).unwrap());
+    assert!(pubkey(&[0xff; 32]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn merkle_hash_test() {
+    let hash = merkle_hash(&fr(0), &fr(0), 0).unwrap();
+
+    assert_eq!(hash.len(), 32);
+    assert_ne!(hash, merkle_hash(&fr(0), &fr(0), 1).unwrap());
+    assert_ne!(
+        merkle_hash(&fr(1), &fr(2), 0).unwrap(),
+        merkle_hash(&fr(2), &fr(1), 0).unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn note_hash_test() {
+    let fields = [
+        ("asset_id", 0),
+        ("amount", 1),
+        ("native_amount", 2),
+        ("txid", 3),
+        ("owner", 4),
+    ];
+
+    let mut changed = fields;
+    changed[1] = ("amount", 2);
+
+    let hash = note_hash(&note(&fields)).unwrap();
+    assert_eq!(nullifier(&hash, &fr(5)).unwrap().len(), 32);
+    assert_ne!(hash, note_hash(&note(&changed)).unwrap());
+    assert!(note_hash(&note(&fields[1..])).is_err());
+}
+
+#[wasm_bindgen_test]
+fn note_encryption_test() {
+    let pk = pubkey(&fr(7)).unwrap();
+    let obj = note(&[("asset_id", 0), ("amount", 1), ("native_amount", 2), ("txid", 3)]);
+    Reflect::set(&obj, &JsValue::from_str("owner"), &Uint8Array::from(&pk[..])).unwrap();
+
+    let data = encrypt_note(&obj, &[1; 32]).unwrap();
+    let decrypted = try_decrypt_note(&data, &fr(7)).unwrap();
+    assert_eq!(note_hash(&decrypted).unwrap(), note_hash(&obj).unwrap());
+
+    assert!(try_decrypt_note(&data, &fr(8)).unwrap().is_undefined());
+    assert!(encrypt_note(&obj, &[1; 31]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn malformed_input_test() {
+    assert!(verify(&[0; 48 * 8], &[0; 192], &fr(1)).is_err());
+    assert!(Params::new(&[]).is_err());
+}
+
+/// Proves with random parameters of the UtxoAccumulator, generated without threads as in the browser. Slow,
+/// the circuit has the full tree depth.
+#[wasm_bindgen_test]
+fn utxo_accumulator_proof_test() {
+    let c = UtxoAccumulator::<Bls12>::blank(&JUBJUB_PARAMS);
+    let params = generate_random_parameters(c, &mut ChaChaRng::from_seed(&[1, 2, 3, 4])).unwrap();
+    // the MPC parameters file starts with the Groth16 parameters
+    let mut mpc_params = vec![];
+    params.write(&mut mpc_params).unwrap();
+
+    // leaves 0 and 1 of the tree with zero siblings above them
+    let root = |left: &[u8], right: &[u8]| {
+        (1..MERKLE_PROOF_LEN).fold(merkle_hash(left, right, 0).unwrap(), |cur, level| {
+            merkle_hash(&cur, &fr(0), level as u32).unwrap()
+        })
+    };
+    let (old_root, new_root) = (root(&fr(0), &fr(0)), root(&fr(1), &fr(2)));
+
+    let sibling = (1..MERKLE_PROOF_LEN)
+        .map(|_| JsValue::from(Uint8Array::from(&fr(0)[..])))
+        .collect::<Array>();
+    let data = Object::new();
+    let pair = |a: Vec<u8>, b: Vec<u8>| Array::of2(&Uint8Array::from(&a[..]), &Uint8Array::from(&b[..]));
+    Reflect::set(&data, &"note_hashes".into(), &pair(fr(1), fr(2))).unwrap();
+    Reflect::set(&data, &"proof_index".into(), &Uint8Array::from(&fr(0)[..])).unwrap();
+    Reflect::set(&data, &"proof_sibling".into(), &Array::of2(&sibling, &sibling)).unwrap();
+
+    let proof = utxo_accumulator(&Params::new(&mpc_params).unwrap(), &data.into(), &[1; 32]).unwrap();
+    let vk = extract_vk(&mpc_params).unwrap();
+
+    let inputs = [fr(1), fr(2), fr(0), old_root.clone(), new_root].concat();
+    assert!(verify(&vk, &proof, &inputs).unwrap());
+
+    let inputs = [fr(1), fr(2), fr(0), old_root.clone(), old_root].concat();
+    assert!(!verify(&vk, &proof, &inputs).unwrap());
+}